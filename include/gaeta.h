@@ -0,0 +1,46 @@
+/*
+ * C interface to the gaeta ETA estimator.
+ *
+ * Build the library with `cargo rustc --release --features ffi --crate-type staticlib` (or
+ * `cdylib`) and link against it. This header matches the output of `cbindgen` for `src/ffi.rs` (see `cbindgen.toml`).
+ */
+
+#ifndef GAETA_H
+#define GAETA_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An opaque estimator handle. */
+typedef struct GaetaContext GaetaContext;
+
+/* A clock callback. Receives the user pointer passed to `gaeta_create_with_clock`, and returns
+ * the current time in any unit. */
+typedef uint64_t (*GaetaClockFn)(void *user);
+
+/* Creates an estimator measuring time in milliseconds with the system's monotonic clock. */
+GaetaContext *gaeta_create(void);
+
+/* Creates an estimator reading the time from `clock`. Returns NULL if `clock` is NULL. */
+GaetaContext *gaeta_create_with_clock(GaetaClockFn clock, void *user);
+
+/* Updates the estimator with the current progress. */
+void gaeta_update(GaetaContext *ctx, uint64_t cur_prog, uint64_t max_prog);
+
+/* Returns the remaining time, in the units of the clock. */
+int64_t gaeta_remaining(const GaetaContext *ctx);
+
+/* Returns the current speed, in percent per unit of time. */
+double gaeta_speed(const GaetaContext *ctx);
+
+/* Releases an estimator. Does nothing if `ctx` is NULL. */
+void gaeta_destroy(GaetaContext *ctx);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GAETA_H */
@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+
+use crate::{clamp_time, GetTimestamp, TimeContext};
+
+/// Describes how an `Aggregate` combines the remaining times of its items.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Mode {
+    /// Items share the overall throughput, and the ETA is calculated from the overall progress
+    /// and speed. This is the default.
+    Average,
+
+    /// Items run in parallel and the job is finished when the slowest one finishes, e.g. all
+    /// shards of a backup. The ETA is the maximum of the remaining times of unfinished items.
+    Slowest,
+}
+
+/// Combines several independent `TimeContext`s into one overall estimate.
+///
+/// A typical use is a multi-file download, where every file has its own context. Every item has a
+/// weight (e.g. the size of the file in bytes, or an estimated cost), and contributes to the
+/// overall progress proportionally to it. Items added with `add` have a weight of 1, so they all
+/// count equally. Items may start and finish at different times:
+/// items which weren't updated yet count as 0%, and finished items no longer contribute to the
+/// overall throughput.
+pub struct Aggregate<T> {
+    items: Vec<TimeContext<T>>,
+    weights: Vec<f64>,    // Weight of every item, indexed like `items`.
+    total_weight: f64,    // Sum of all weights.
+    mode: Mode,
+}
+
+impl<T: GetTimestamp> Aggregate<T> {
+    /// Creates an empty `Aggregate`.
+    pub fn new() -> Aggregate<T> {
+        Aggregate {
+            items: Vec::new(),
+            weights: Vec::new(),
+            total_weight: 0f64,
+            mode: Mode::Average,
+        }
+    }
+
+    /// Sets the way the remaining times of items are combined. See `Mode`.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    /// Returns the way the remaining times of items are combined.
+    pub fn get_mode(&self) -> Mode { self.mode }
+
+    /// Adds a new item with a weight of 1 and returns its index.
+    pub fn add(&mut self, ctx: TimeContext<T>) -> usize {
+        self.add_weighted(ctx, 1.0f64)
+    }
+
+    /// Adds a new item with a given `weight` and returns its index.
+    ///
+    /// The weight can be expressed in any unit (bytes, estimated seconds), as long as all items
+    /// use the same one. Negative weights are treated as 0.
+    pub fn add_weighted(&mut self, ctx: TimeContext<T>, weight: f64) -> usize {
+        let weight = if weight > 0f64 { weight } else { 0f64 };
+
+        self.items.push(ctx);
+        self.weights.push(weight);
+        self.total_weight += weight;
+        self.items.len() - 1
+    }
+
+    /// Returns the weight of the item at `index`.
+    pub fn get_weight(&self, index: usize) -> Option<f64> {
+        self.weights.get(index).copied()
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no items were added.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Updates the state of the item at `index`. See `TimeContext::update_eta`.
+    ///
+    /// Does nothing if there's no such item.
+    pub fn update_eta(&mut self, index: usize, cur_prog: u64, max_prog: u64) {
+        if let Some(ctx) = self.items.get_mut(index) {
+            ctx.update_eta(cur_prog, max_prog);
+        }
+    }
+
+    /// Gets a reference to the item at `index`.
+    pub fn get(&self, index: usize) -> Option<&TimeContext<T>> {
+        self.items.get(index)
+    }
+
+    /// Gets a mutable reference to the item at `index`.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut TimeContext<T>> {
+        self.items.get_mut(index)
+    }
+
+    /// Returns the overall progress, in percent.
+    pub fn get_progress(&self) -> f64 {
+        if self.total_weight <= 0f64 {
+            return 0f64;
+        }
+
+        let sum = self.items.iter().zip(self.weights.iter())
+            .fold(0f64, |acc, (ctx, w)| acc + progress_of(ctx) * *w);
+
+        sum / self.total_weight
+    }
+
+    /// Returns the overall speed, in percent of the whole job per unit of time.
+    ///
+    /// It's the sum of the speeds of all unfinished items, scaled by each item's share of the job
+    /// (its weight divided by the sum of all weights).
+    pub fn calc_speed_per_unit(&self) -> f64 {
+        if self.total_weight <= 0f64 {
+            return 0f64;
+        }
+
+        let sum = self.items.iter().zip(self.weights.iter())
+            .filter(|&(ctx, _)| progress_of(ctx) < 100.0f64)
+            .fold(0f64, |acc, (ctx, w)| acc + ctx.calc_speed_per_unit() * *w);
+
+        sum / self.total_weight
+    }
+
+    /// Returns the overall remaining time (ETA).
+    ///
+    /// In `Mode::Average`, returns 0 if the speed is not known yet. In `Mode::Slowest`, items
+    /// whose speed is not known yet are skipped.
+    pub fn get_remaining_time(&self) -> i64 {
+        if self.mode == Mode::Slowest {
+            return self.items.iter()
+                .filter(|ctx| progress_of(*ctx) < 100.0f64)
+                .map(|ctx| ctx.get_remaining_time())
+                .max()
+                .unwrap_or(0i64);
+        }
+
+        let speed = self.calc_speed_per_unit();
+        if speed <= 0f64 {
+            return 0;
+        }
+
+        let remaining = (100.0f64 - self.get_progress()) / speed;
+        clamp_time(remaining)
+    }
+}
+
+impl<T: GetTimestamp> Default for Aggregate<T> {
+    fn default() -> Aggregate<T> { Aggregate::new() }
+}
+
+fn progress_of<T: GetTimestamp>(ctx: &TimeContext<T>) -> f64 {
+    let progress = ctx.snapshot().progress;
+    if progress > 100.0f64 { 100.0f64 } else { progress }
+}
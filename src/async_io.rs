@@ -0,0 +1,174 @@
+use std::pin::Pin;
+
+use crate::{EtaHandle, GetTimestamp, TimeContext};
+
+/// An asynchronous reader which counts the bytes read through it and updates a context.
+///
+/// `AsyncRead` is implemented for the `tokio` flavor of the trait when the `tokio` feature is
+/// enabled, and for the `futures-io` flavor when the `futures` feature is enabled.
+pub struct EtaAsyncReader<R, T> {
+    inner: R,
+    counter: Counter<T>,
+}
+
+/// An asynchronous writer which counts the bytes written through it and updates a context.
+///
+/// `AsyncWrite` is implemented for the `tokio` flavor of the trait when the `tokio` feature is
+/// enabled, and for the `futures-io` flavor when the `futures` feature is enabled.
+pub struct EtaAsyncWriter<W, T> {
+    inner: W,
+    counter: Counter<T>,
+}
+
+struct Counter<T> {
+    handle: EtaHandle<T>,
+    count: u64,           // Bytes transferred so far.
+    total: u64,           // Expected number of bytes.
+}
+
+impl<T: GetTimestamp> Counter<T> {
+    fn advance(&mut self, n: usize) {
+        if n > 0 {
+            self.count = self.count.saturating_add(n as u64);
+            self.handle.update_eta(self.count, self.total);
+        }
+    }
+}
+
+macro_rules! impl_common {
+    ($name:ident, $inner:ident) => {
+        impl<$inner, T: GetTimestamp> $name<$inner, T> {
+            /// Wraps `inner`, which is expected to transfer `total` bytes.
+            pub fn new(inner: $inner, total: u64, timefunc: T) -> $name<$inner, T> {
+                $name {
+                    inner,
+                    counter: Counter {
+                        handle: EtaHandle::new(TimeContext::new(timefunc)),
+                        count: 0u64,
+                        total,
+                    },
+                }
+            }
+
+            /// Returns a handle which can be used to read the live ETA.
+            pub fn handle(&self) -> EtaHandle<T> {
+                self.counter.handle.clone()
+            }
+
+            /// Returns the number of bytes transferred so far.
+            pub fn count(&self) -> u64 {
+                self.counter.count
+            }
+
+            /// Unwraps this struct, returning the underlying I/O object.
+            pub fn into_inner(self) -> $inner {
+                self.inner
+            }
+
+            fn project(self: Pin<&mut Self>) -> (Pin<&mut $inner>, &mut Counter<T>) {
+                // The inner object is structurally pinned: it's never moved out while pinned,
+                // and the wrapper has no `Drop` implementation. The counter is not pinned.
+                unsafe {
+                    let this = self.get_unchecked_mut();
+                    (Pin::new_unchecked(&mut this.inner), &mut this.counter)
+                }
+            }
+        }
+    }
+}
+
+impl_common!(EtaAsyncReader, R);
+impl_common!(EtaAsyncWriter, W);
+
+#[cfg(feature = "tokio")]
+mod tokio_impl {
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+    use super::{EtaAsyncReader, EtaAsyncWriter};
+    use crate::GetTimestamp;
+
+    impl<R: AsyncRead, T: GetTimestamp> AsyncRead for EtaAsyncReader<R, T> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf)
+            -> Poll<io::Result<()>> {
+            let (inner, counter) = self.project();
+            let before = buf.filled().len();
+            let poll = inner.poll_read(cx, buf);
+
+            if let Poll::Ready(Ok(())) = poll {
+                counter.advance(buf.filled().len() - before);
+            }
+            poll
+        }
+    }
+
+    impl<W: AsyncWrite, T: GetTimestamp> AsyncWrite for EtaAsyncWriter<W, T> {
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8])
+            -> Poll<io::Result<usize>> {
+            let (inner, counter) = self.project();
+            let poll = inner.poll_write(cx, buf);
+
+            if let Poll::Ready(Ok(n)) = poll {
+                counter.advance(n);
+            }
+            poll
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+            self.project().0.poll_flush(cx)
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+            self.project().0.poll_shutdown(cx)
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+mod futures_impl {
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures::io::{AsyncRead, AsyncWrite};
+
+    use super::{EtaAsyncReader, EtaAsyncWriter};
+    use crate::GetTimestamp;
+
+    impl<R: AsyncRead, T: GetTimestamp> AsyncRead for EtaAsyncReader<R, T> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8])
+            -> Poll<io::Result<usize>> {
+            let (inner, counter) = self.project();
+            let poll = inner.poll_read(cx, buf);
+
+            if let Poll::Ready(Ok(n)) = poll {
+                counter.advance(n);
+            }
+            poll
+        }
+    }
+
+    impl<W: AsyncWrite, T: GetTimestamp> AsyncWrite for EtaAsyncWriter<W, T> {
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8])
+            -> Poll<io::Result<usize>> {
+            let (inner, counter) = self.project();
+            let poll = inner.poll_write(cx, buf);
+
+            if let Poll::Ready(Ok(n)) = poll {
+                counter.advance(n);
+            }
+            poll
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+            self.project().0.poll_flush(cx)
+        }
+
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+            self.project().0.poll_close(cx)
+        }
+    }
+}
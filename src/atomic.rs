@@ -0,0 +1,213 @@
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{GetProgress, GetTimestamp, TimeContext, Window};
+
+/// A lock-free progress counter which can be shared between worker threads.
+///
+/// Workers only call `inc` (which is a single `fetch_add`), so the hot path never touches the
+/// estimator. The `TimeContext` reads the counter when `sample` is called, which usually happens
+/// from a different thread, e.g. the one responsible for rendering the progress.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{AtomicProgress, SystemTimer, TimeContext};
+///   # use std::sync::Arc;
+///   # use std::thread;
+///   # let files = ["a", "b", "c"];
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   let progress = Arc::new(AtomicProgress::new(files.len() as u64));
+///
+///   // In a worker thread:
+///   # let shared = progress.clone();
+///   # thread::spawn(move || { let progress = shared;
+///   progress.inc(1);
+///   # }).join().unwrap();
+///
+///   // In the rendering thread:
+///   ctx.sample(&*progress);
+///   println!("{} remaining", ctx.get_remaining_time());
+/// ```
+pub struct AtomicProgress {
+    cur: AtomicU64,       // Current progress value.
+    max: AtomicU64,       // Maximum progress value.
+}
+
+impl AtomicProgress {
+    /// Creates a new counter, starting at zero, which reaches 100% when it gets to `max_prog`.
+    pub fn new(max_prog: u64) -> AtomicProgress {
+        AtomicProgress {
+            cur: AtomicU64::new(0u64),
+            max: AtomicU64::new(max_prog),
+        }
+    }
+
+    /// Advances the counter by `n` units.
+    pub fn inc(&self, n: u64) {
+        self.cur.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Sets the counter to `cur_prog`.
+    pub fn set(&self, cur_prog: u64) {
+        self.cur.store(cur_prog, Ordering::Relaxed);
+    }
+
+    /// Changes the value that symbolizes 100%.
+    pub fn set_max(&self, max_prog: u64) {
+        self.max.store(max_prog, Ordering::Relaxed);
+    }
+
+    /// Returns the current `(cur_prog, max_prog)` pair.
+    pub fn get(&self) -> (u64, u64) {
+        (self.cur.load(Ordering::Relaxed), self.max.load(Ordering::Relaxed))
+    }
+
+    /// Returns a guard which advances the counter by one unit when it's dropped.
+    ///
+    /// The unit is accounted for even if the scope is left early with `return`, `break`, `?`, or
+    /// by a panic unwinding the stack:
+    ///
+    /// ```rust
+    ///   # use gaeta::AtomicProgress;
+    ///   # fn skip(file: &str) -> bool { file.is_empty() }
+    ///   # fn process(_file: &str) -> std::io::Result<()> { Ok(()) }
+    ///   # fn main() -> std::io::Result<()> {
+    ///   # let files = ["a", "", "b"];
+    ///   # let progress = AtomicProgress::new(files.len() as u64);
+    ///   for file in files.iter() {
+    ///       let _unit = progress.unit();
+    ///       if skip(file) {
+    ///           continue;
+    ///       }
+    ///       process(file)?;
+    ///   }
+    ///   # assert_eq!(progress.get(), (3, 3));
+    ///   # Ok(())
+    ///   # }
+    /// ```
+    pub fn unit(&self) -> Unit<'_> {
+        self.units(1u64)
+    }
+
+    /// Returns a guard which advances the counter by `n` units when it's dropped.
+    pub fn units(&self, n: u64) -> Unit<'_> {
+        Unit { progress: self, n }
+    }
+
+    /// Returns a `Worker` which accumulates progress locally and adds it to this counter every
+    /// `flush_every` units, and when it's dropped.
+    ///
+    /// To move a worker into a thread which outlives the counter's borrow, create it with
+    /// `Worker::new` from an `Arc<AtomicProgress>` instead.
+    pub fn worker(&self, flush_every: u64) -> Worker<&AtomicProgress> {
+        Worker::new(self, flush_every)
+    }
+}
+
+/// A per-thread child of an `AtomicProgress`.
+///
+/// For embarrassingly parallel workloads, even a single `fetch_add` per item can cause
+/// noticeable contention on the shared counter. A `Worker` counts the progress in a plain local
+/// variable and folds it into the parent counter only every `flush_every` units, and when it's
+/// dropped, so no progress is lost.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::AtomicProgress;
+///   # use std::thread;
+///   # fn process(_item: &u32) {}
+///   # let items: Vec<u32> = (0..2500).collect();
+///   let progress = AtomicProgress::new(items.len() as u64);
+///
+///   thread::scope(|s| {
+///       for chunk in items.chunks(1000) {
+///           let mut worker = progress.worker(100);
+///           s.spawn(move || {
+///               for item in chunk {
+///                   process(item);
+///                   worker.inc(1);
+///               }
+///           });
+///       }
+///   });
+///   # assert_eq!(progress.get(), (2500, 2500));
+/// ```
+pub struct Worker<P: Borrow<AtomicProgress>> {
+    parent: P,
+    local: u64,           // Progress not yet added to the parent.
+    flush_every: u64,
+}
+
+impl<P: Borrow<AtomicProgress>> Worker<P> {
+    /// Creates a new worker of `parent`, which can be a reference or an `Arc`. See
+    /// `AtomicProgress::worker`.
+    pub fn new(parent: P, flush_every: u64) -> Worker<P> {
+        Worker {
+            parent,
+            local: 0u64,
+            flush_every: if flush_every > 0 { flush_every } else { 1u64 },
+        }
+    }
+
+    /// Advances the local progress by `n` units, folding it into the parent if the threshold was
+    /// reached.
+    pub fn inc(&mut self, n: u64) {
+        self.local = self.local.saturating_add(n);
+
+        if self.local >= self.flush_every {
+            self.flush();
+        }
+    }
+
+    /// Adds the local progress to the parent immediately.
+    pub fn flush(&mut self) {
+        if self.local > 0 {
+            self.parent.borrow().inc(self.local);
+            self.local = 0u64;
+        }
+    }
+}
+
+impl<P: Borrow<AtomicProgress>> Drop for Worker<P> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// A guard which advances an `AtomicProgress` when it's dropped.
+///
+/// It's created by the `unit` and `units` methods of `AtomicProgress`.
+#[must_use]
+pub struct Unit<'a> {
+    progress: &'a AtomicProgress,
+    n: u64,
+}
+
+impl<'a> Drop for Unit<'a> {
+    fn drop(&mut self) {
+        self.progress.inc(self.n);
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Reads the current value of `progress` and updates the state of this `TimeContext`
+    /// instance, as if `update_eta` was called with the counter's values.
+    pub fn sample(&mut self, progress: &AtomicProgress) {
+        self.poll_update(progress);
+    }
+}
+
+impl GetProgress for AtomicProgress {
+    fn get_progress(&self) -> (u64, u64) {
+        self.get()
+    }
+}
+
+impl GetProgress for Arc<AtomicProgress> {
+    fn get_progress(&self) -> (u64, u64) {
+        self.get()
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{GetTimestamp, TimeContext, Window};
+
+// Weight of the newest prediction in the moving averages.
+const BIAS_WEIGHT: f64 = 0.05;
+
+// The correction factor is limited to this factor in both directions.
+const MAX_CORRECTION: f64 = 4.0;
+
+// Number of samples a prediction is checked after.
+const HORIZON: usize = 8;
+
+// The state of the bias correction of a `TimeContext`: the averages of the progress predicted at
+// every sample for the following `HORIZON` samples, and of the progress actually made.
+#[derive(Copy, Clone, Debug)]
+pub struct Bias {
+    predicted: f64,       // Average predicted progress, in percent.
+    actual: f64,          // Average actual progress, in percent.
+    recent: [Option<(u64, f64, f64)>; HORIZON],
+                          // Timestamp, percent and speed at the recent samples.
+    next: usize,          // Index of the oldest of the recent samples.
+}
+
+impl Bias {
+    fn get_factor(&self) -> f64 {
+        if self.predicted > 0f64 && self.actual > 0f64 {
+            (self.predicted / self.actual).clamp(1f64 / MAX_CORRECTION, MAX_CORRECTION)
+        } else {
+            1f64
+        }
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Makes the context correct its estimates by how wrong its earlier predictions were.
+    ///
+    /// At every sample, the progress predicted with the speed measured a few samples earlier is
+    /// compared with the progress actually made since. If the predictions are systematically too
+    /// optimistic, e.g. because the job keeps slowing down, the remaining time is multiplied by
+    /// the ratio of the predicted to the actual progress, recently weighted, and vice versa. The
+    /// factor is limited to between a quarter and four.
+    ///
+    /// It helps with jobs whose error keeps the same direction; with jobs which alternate between
+    /// bursts and pauses, the factor follows the bursts and can make the estimate worse.
+    pub fn enable_bias_correction(&mut self) {
+        self.bias = Some(Bias {
+            predicted: 0f64,
+            actual: 0f64,
+            recent: [None; HORIZON],
+            next: 0,
+        });
+    }
+
+    /// Returns the factor the remaining time is currently multiplied by, which is 1 unless the
+    /// bias correction is enabled with `enable_bias_correction`.
+    pub fn get_bias_factor(&self) -> f64 {
+        self.bias.map_or(1f64, |bias| bias.get_factor())
+    }
+
+    // Called after every accepted sample, when the speed was measured.
+    pub(crate) fn correct_bias(&mut self, ts: u64) {
+        let mut bias = match self.bias {
+            Some(bias) => bias,
+            None => return,
+        };
+
+        let percent = self.get_percent();
+
+        if let Some((timestamp, previous, speed)) = bias.recent[bias.next] {
+            let predicted = speed * ts.saturating_sub(timestamp) as f64;
+            let actual = percent - previous;
+
+            if predicted > 0f64 && actual >= 0f64 {
+                bias.predicted = bias.predicted * (1f64 - BIAS_WEIGHT) + predicted * BIAS_WEIGHT;
+                bias.actual = bias.actual * (1f64 - BIAS_WEIGHT) + actual * BIAS_WEIGHT;
+            }
+        }
+
+        bias.recent[bias.next] = Some((ts, percent, self.curspeed));
+        bias.next = (bias.next + 1) % HORIZON;
+        self.bias = Some(bias);
+    }
+}
@@ -0,0 +1,31 @@
+//! Prints the status of a job which runs a status server (see `gaeta::spawn_status_server`).
+//!
+//! Usage: `gaeta-status <socket>`
+
+use std::env;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let path = match env::args_os().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: gaeta-status <socket>");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut reply = Vec::new();
+    let result = UnixStream::connect(&path)
+        .and_then(|mut stream| stream.read_to_end(&mut reply))
+        .and_then(|_| io::stdout().write_all(&reply));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("gaeta-status: {}: {}", path.to_string_lossy(), err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{sample_speed, GetTimestamp, Sample, TimeContext, Window};
+
+// Number of window spans compared by the calibration.
+const CANDIDATES: usize = 4;
+
+// Weight of the newest error in the moving averages.
+const ERROR_WEIGHT: f64 = 0.2;
+
+// The state of the automatic calibration of a `TimeContext`: the average error of the progress
+// predicted with every candidate span, and the span currently used.
+#[derive(Copy, Clone, Debug)]
+pub struct Calibration {
+    errors: [f64; CANDIDATES],
+    span: usize,          // Number of intervals the speed is measured over.
+}
+
+impl Calibration {
+    pub fn new(limit: usize) -> Calibration {
+        Calibration {
+            errors: [0f64; CANDIDATES],
+            span: candidate(limit, CANDIDATES - 1),
+        }
+    }
+
+    pub fn get_span(&self) -> usize { self.span }
+}
+
+// The span of the candidate `index`: a quarter, a half, three quarters of the window and the
+// whole window, but at least one interval.
+fn candidate(limit: usize, index: usize) -> usize {
+    let intervals = limit.saturating_sub(1);
+    let span = (intervals * (index + 1)).div_ceil(CANDIDATES);
+    span.max(1)
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Makes the context choose the number of recent samples the speed is measured over by
+    /// itself, instead of always using the whole window.
+    ///
+    /// For every new sample, the progress predicted by the speeds measured over a quarter, a half,
+    /// three quarters and the whole window is compared with the actual progress, and the span
+    /// with the lowest recent error is used from then on. Steady jobs end up with the whole
+    /// window, which filters the noise best, while jobs whose speed drifts end up with a shorter
+    /// span, which follows the drift.
+    pub fn enable_calibration(&mut self) {
+        self.calibration = Some(Calibration::new(self.samples.limit()));
+    }
+
+    /// Returns the number of recent intervals between samples the speed is measured over. It's
+    /// the whole window unless the calibration is enabled with `enable_calibration`.
+    pub fn get_effective_window(&self) -> usize {
+        match self.calibration {
+            Some(calibration) => calibration.get_span(),
+            None => self.samples.limit().saturating_sub(1),
+        }
+    }
+
+    // Called with every new sample before it's added to the window, to compare the predictions
+    // of the candidate spans with it.
+    pub(crate) fn calibrate(&mut self, sample: &Sample) {
+        let mut calibration = match self.calibration {
+            Some(calibration) => calibration,
+            None => return,
+        };
+
+        let newest = match self.samples.back() {
+            Some(newest) => *newest,
+            None => return,
+        };
+
+        let limit = self.samples.limit();
+        let elapsed = sample.timestamp.saturating_sub(newest.timestamp) as f64;
+
+        for (index, error) in calibration.errors.iter_mut().enumerate() {
+            let speed = match self.reference_over(candidate(limit, index)) {
+                Some(reference) => sample_speed(&reference, &newest),
+                None => continue,
+            };
+
+            let predicted = newest.current as f64 + speed * elapsed;
+            let actual = (predicted - sample.current as f64).abs();
+            *error = *error * (1.0f64 - ERROR_WEIGHT) + actual * ERROR_WEIGHT;
+        }
+
+        let mut best = CANDIDATES - 1;
+
+        for index in 0..CANDIDATES {
+            if calibration.errors[index] < calibration.errors[best] {
+                best = index;
+            }
+        }
+
+        calibration.span = candidate(limit, best);
+        self.calibration = Some(calibration);
+    }
+}
@@ -0,0 +1,137 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// A queue of raw progress values, which can be filled from an interrupt handler and fed to a
+/// `TimeContext` from the main loop.
+///
+/// The queue is split into its two halves with `split`: a `Recorder`, which only stores the raw
+/// count and the timer tick (no floating point math, no allocation, no locking, so it's safe to
+/// use e.g. in a DMA-complete interrupt), and a `Reader`, which feeds the recorded values to a
+/// `TimeContext` where the estimates are computed. Up to `N` values can be pending; when the queue
+/// is full, new values are dropped until the reader catches up. The queue can only be split once,
+/// so it can be placed in a `static` and split without `unsafe`.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{Capture, TestTimer, TimeContext};
+///   # fn display(_remaining: i64) {}
+///   # let mut ctx = TimeContext::new(TestTimer::new());
+///   # let total_blocks = 100;
+///   static CAPTURE: Capture<16> = Capture::new();
+///
+///   let (recorder, mut reader) = CAPTURE.split().expect("already split");
+///   // Move `recorder` to the interrupt handler, which calls:
+///   //     recorder.record(blocks_done, TIM2.cnt.read().bits());
+///   # let mut recorder = recorder;
+///   # recorder.record(10, 1000);
+///
+///   loop {
+///       reader.drain_into(&mut ctx, total_blocks);
+///       display(ctx.get_remaining_time());
+///   #   break;
+///   }
+/// ```
+pub struct Capture<const N: usize> {
+    slots: [UnsafeCell<(u64, u64)>; N],
+    head: AtomicUsize,    // Number of values ever written; advanced by the recorder.
+    tail: AtomicUsize,    // Number of values ever read; advanced by the reader.
+    taken: AtomicBool,    // The queue was split.
+}
+
+// Every slot is accessed either by the recorder or by the reader, as decided by `head` and `tail`,
+// and there's only one of each.
+unsafe impl<const N: usize> Sync for Capture<N> {}
+
+/// The recording half of a `Capture`, meant to be used from an interrupt handler.
+pub struct Recorder<'a, const N: usize> {
+    capture: &'a Capture<N>,
+}
+
+/// The reading half of a `Capture`, meant to be used from the main loop.
+pub struct Reader<'a, const N: usize> {
+    capture: &'a Capture<N>,
+}
+
+impl<const N: usize> Capture<N> {
+    /// Creates an empty queue. It's a `const fn`, so the queue can be placed in a `static`.
+    pub const fn new() -> Capture<N> {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const EMPTY: UnsafeCell<(u64, u64)> = UnsafeCell::new((0u64, 0u64));
+
+        Capture {
+            slots: [EMPTY; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            taken: AtomicBool::new(false),
+        }
+    }
+
+    /// Splits the queue into its recording and reading halves. Returns `None` if it was already
+    /// split, so there's never more than one recorder and one reader.
+    pub fn split(&self) -> Option<(Recorder<'_, N>, Reader<'_, N>)> {
+        if self.taken.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+
+        Some((Recorder { capture: self }, Reader { capture: self }))
+    }
+}
+
+impl<const N: usize> Default for Capture<N> {
+    fn default() -> Capture<N> { Capture::new() }
+}
+
+impl<'a, const N: usize> Recorder<'a, N> {
+    /// Records the raw progress value `count`, reached at `tick` (in units of time used by the
+    /// `TimeContext` the values are fed to). Returns false if the queue is full and the value was
+    /// dropped.
+    pub fn record(&mut self, count: u64, tick: u64) -> bool {
+        let capture = self.capture;
+        let head = capture.head.load(Ordering::Relaxed);
+        let tail = capture.tail.load(Ordering::Acquire);
+
+        if N == 0 || head.wrapping_sub(tail) >= N {
+            return false;
+        }
+
+        // The slot is not visible to the reader until `head` is advanced.
+        unsafe { *capture.slots[head % N].get() = (count, tick); }
+        capture.head.store(head.wrapping_add(1), Ordering::Release);
+        true
+    }
+}
+
+impl<'a, const N: usize> Reader<'a, N> {
+    /// Takes the oldest recorded `(count, tick)` pair out of the queue.
+    pub fn pop(&mut self) -> Option<(u64, u64)> {
+        let capture = self.capture;
+        let tail = capture.tail.load(Ordering::Relaxed);
+        let head = capture.head.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        // The slot is not reused by the recorder until `tail` is advanced.
+        let value = unsafe { *capture.slots[tail % N].get() };
+        capture.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    /// Feeds all recorded values to `ctx`, with `update_eta_fast`, as progress out of `max_prog`.
+    /// Returns the number of values fed.
+    pub fn drain_into<T, W>(&mut self, ctx: &mut TimeContext<T, W>, max_prog: u64) -> usize
+        where T: GetTimestamp, W: Window {
+        let mut fed = 0;
+
+        while let Some((count, tick)) = self.pop() {
+            ctx.update_eta_fast(count, max_prog, tick);
+            fed += 1;
+        }
+
+        fed
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::{GetTimestamp, TimeContext};
+
+/// A message sent by a producer to a `ChannelContext`.
+#[derive(Copy, Clone, Debug)]
+pub enum ProgressEvent {
+    /// Sets the absolute progress: `Set(cur_prog, max_prog)`.
+    Set(u64, u64),
+
+    /// Advances the current progress by the given amount of units.
+    Advance(u64),
+}
+
+/// A `TimeContext` which is fed through a channel of `ProgressEvent`s.
+///
+/// Producers hold a `Sender` obtained from the `sender` method. Sending an event never blocks and
+/// never performs any estimation work; the events are drained, and the estimate recalculated,
+/// only when the consumer calls `update`.
+pub struct ChannelContext<T> {
+    ctx: TimeContext<T>,
+    tx: Sender<ProgressEvent>,
+    rx: Receiver<ProgressEvent>,
+    cur: u64,             // Progress value accumulated from the received events.
+    max: u64,             // Maximum progress value.
+}
+
+impl<T: GetTimestamp> ChannelContext<T> {
+    /// Creates a new `ChannelContext` which will use a user-supplied `GetTimestamp` struct.
+    ///
+    /// `max_prog` is the initial maximum progress value; it can be changed later by sending a
+    /// `ProgressEvent::Set` event.
+    pub fn new(timefunc: T, max_prog: u64) -> ChannelContext<T> {
+        let (tx, rx) = channel();
+
+        ChannelContext {
+            ctx: TimeContext::new(timefunc),
+            tx,
+            rx,
+            cur: 0u64,
+            max: max_prog,
+        }
+    }
+
+    /// Returns a new `Sender` which can be moved to a producer thread.
+    pub fn sender(&self) -> Sender<ProgressEvent> {
+        self.tx.clone()
+    }
+
+    /// Drains all pending events and updates the underlying `TimeContext`.
+    ///
+    /// The ETA is recalculated only once, using the state after the last received event. If no
+    /// events were pending, the context is left untouched.
+    pub fn update(&mut self) {
+        let mut received = false;
+
+        while let Ok(event) = self.rx.try_recv() {
+            match event {
+                ProgressEvent::Set(cur_prog, max_prog) => {
+                    self.cur = cur_prog;
+                    self.max = max_prog;
+                }
+                ProgressEvent::Advance(n) => {
+                    self.cur = self.cur.saturating_add(n);
+                }
+            }
+
+            received = true;
+        }
+
+        if received {
+            self.ctx.update_eta(self.cur, self.max);
+        }
+    }
+
+    /// Gets a reference to the underlying `TimeContext`.
+    pub fn get_context(&self) -> &TimeContext<T> { &self.ctx }
+
+    /// Gets a mutable reference to the underlying `TimeContext`.
+    pub fn get_context_mut(&mut self) -> &mut TimeContext<T> { &mut self.ctx }
+}
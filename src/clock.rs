@@ -0,0 +1,37 @@
+use std::time::Instant;
+
+use crate::GetTimestamp;
+
+/// A `GetTimestamp` implementation backed by the standard library's monotonic clock.
+///
+/// The timestamps are milliseconds elapsed since the timer was created, so the remaining time
+/// returned by a `TimeContext` using this timer is expressed in milliseconds as well.
+///
+/// The standard library has no clock on `wasm32-unknown-unknown`, so this timer panics there; use
+/// `PerformanceTimer` from the `wasm` feature instead.
+#[derive(Copy, Clone, Debug)]
+pub struct SystemTimer {
+    start: Instant,
+}
+
+impl SystemTimer {
+    /// Creates a new timer, starting at 0.
+    pub fn new() -> SystemTimer {
+        SystemTimer { start: Instant::now() }
+    }
+}
+
+impl Default for SystemTimer {
+    fn default() -> SystemTimer {
+        SystemTimer::new()
+    }
+}
+
+impl GetTimestamp for SystemTimer {
+    /// Returns the number of milliseconds elapsed since the timer was created.
+    fn get_timestamp(&self) -> u64 {
+        let elapsed = self.start.elapsed();
+        let millis = elapsed.subsec_millis() as u64;
+        elapsed.as_secs().saturating_mul(1000).saturating_add(millis)
+    }
+}
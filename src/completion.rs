@@ -0,0 +1,143 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use crate::Observer;
+
+/// Describes how a tracked operation ended.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Outcome {
+    /// The progress reached 100%.
+    Finished,
+
+    /// The operation was cancelled, or abandoned (e.g. its `TimeContext` was dropped) before
+    /// reaching 100%.
+    Cancelled,
+}
+
+struct State {
+    outcome: Option<Outcome>,
+    waker: Option<Waker>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    cond: Condvar,
+}
+
+impl Shared {
+    fn resolve(&self, outcome: Outcome) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.outcome.is_some() {
+            return;
+        }
+
+        state.outcome = Some(outcome);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+
+        self.cond.notify_all();
+    }
+}
+
+/// A handle which resolves when the tracked operation ends.
+///
+/// It's returned by `TimeContext::completion`. It can be waited for synchronously with `wait`, or
+/// awaited, since it implements `Future`; the latter allows supervising tasks to `select!` on the
+/// completion alongside timeouts.
+pub struct Completion {
+    shared: Arc<Shared>,
+}
+
+impl Completion {
+    /// Returns the outcome, if the operation already ended.
+    pub fn try_get(&self) -> Option<Outcome> {
+        self.shared.state.lock().unwrap_or_else(|e| e.into_inner()).outcome
+    }
+
+    /// Blocks the current thread until the operation ends.
+    pub fn wait(&self) -> Outcome {
+        let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        loop {
+            if let Some(outcome) = state.outcome {
+                return outcome;
+            }
+
+            state = self.shared.cond.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Blocks the current thread until the operation ends, or `timeout` passes. Returns `None` on
+    /// timeout.
+    pub fn wait_timeout(&self, timeout: Duration) -> Option<Outcome> {
+        let state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (state, _) = self.shared.cond
+            .wait_timeout_while(state, timeout, |s| s.outcome.is_none())
+            .unwrap_or_else(|e| e.into_inner());
+
+        state.outcome
+    }
+}
+
+impl Future for Completion {
+    type Output = Outcome;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Outcome> {
+        let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        match state.outcome {
+            Some(outcome) => Poll::Ready(outcome),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// The observer side of a `Completion`, registered in the context. If it's dropped before the
+// progress reaches 100%, the operation is considered to be cancelled.
+pub struct CompletionObserver {
+    shared: Arc<Shared>,
+    done: bool,
+}
+
+impl CompletionObserver {
+    pub fn new() -> (CompletionObserver, Completion) {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State { outcome: None, waker: None }),
+            cond: Condvar::new(),
+        });
+
+        (CompletionObserver { shared: shared.clone(), done: false }, Completion { shared })
+    }
+
+    pub fn resolve(&mut self, outcome: Outcome) {
+        self.done = true;
+        self.shared.resolve(outcome);
+    }
+}
+
+impl Observer for CompletionObserver {
+    fn on_complete(&mut self) {
+        self.resolve(Outcome::Finished);
+    }
+
+    fn on_cancel(&mut self) {
+        self.resolve(Outcome::Cancelled);
+    }
+
+    fn is_closed(&self) -> bool { self.done }
+}
+
+impl Drop for CompletionObserver {
+    fn drop(&mut self) {
+        if !self.done {
+            self.shared.resolve(Outcome::Cancelled);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+
+use crate::{clamp_offset, clamp_time, GetTimestamp, History, ScheduleStatus, TimeContext, Window};
+
+/// An expected progress-vs-time curve of an operation, e.g. from planning or from a previous run.
+///
+/// Each point is a time since the start of the operation, in units of time used by the
+/// `GetTimestamp` struct, and the progress expected by then, in percent; the progress between the
+/// points is interpolated linearly. A job with predictable phases, like a slow setup followed by
+/// a fast copy, is described by the shape of the curve, which pure measurement only learns as it
+/// goes.
+///
+/// It's set with `TimeContext::set_expected_curve`.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{ProgressCurve, SystemTimer, TimeContext};
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   // 10 s of setup without progress, then an even copy taking 50 s.
+///   let curve = ProgressCurve::new(vec![(10_000, 0.0), (60_000, 100.0)]);
+///   ctx.set_expected_curve(curve);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProgressCurve {
+    points: Vec<(u64, f64)>,
+                          // Times and expected percents, both ascending, starting at the origin.
+}
+
+impl ProgressCurve {
+    /// Creates a curve through `points`, which are sorted by time. The curve starts at 0% at time
+    /// 0, and the progress never decreases: a point lower than an earlier one is raised to it.
+    pub fn new(mut points: Vec<(u64, f64)>) -> ProgressCurve {
+        points.retain(|p| !p.1.is_nan());
+        points.sort_by_key(|p| p.0);
+
+        let mut curve = Vec::with_capacity(points.len() + 1);
+        curve.push((0u64, 0f64));
+
+        for (time, percent) in points {
+            let highest = curve.last().map_or(0f64, |p: &(u64, f64)| p.1);
+            let point = (time, percent.clamp(highest, 100.0f64));
+
+            match curve.last_mut() {
+                Some(last) if last.0 == time => *last = point,
+                _ => curve.push(point),
+            }
+        }
+
+        ProgressCurve { points: curve }
+    }
+
+    /// Creates a curve from the history of a previous run (see `TimeContext::enable_history`),
+    /// which had `total` work units.
+    pub fn from_history(history: &History, total: u64) -> ProgressCurve {
+        let points = history.get_points();
+        let first = match points.first() {
+            Some(first) => *first,
+            None => return ProgressCurve::new(Vec::new()),
+        };
+
+        let total = if total > 0 { total } else { 1 };
+
+        ProgressCurve::new(points.iter().map(|p| {
+            let percent = p.current.saturating_sub(first.current) as f64 * 100.0f64 / total as f64;
+            (p.timestamp.saturating_sub(first.timestamp), percent)
+        }).collect())
+    }
+
+    /// Returns the points of the curve, including the origin.
+    pub fn get_points(&self) -> &[(u64, f64)] { &self.points }
+
+    /// Returns the progress expected `elapsed` units of time after the start, in percent.
+    pub fn get_percent_at(&self, elapsed: u64) -> f64 {
+        for pair in self.points.windows(2) {
+            let ((t0, p0), (t1, p1)) = (pair[0], pair[1]);
+
+            if elapsed < t1 {
+                let fraction = elapsed.saturating_sub(t0) as f64 / (t1 - t0) as f64;
+                return p0 + (p1 - p0) * fraction;
+            }
+        }
+
+        self.points.last().map_or(0f64, |p| p.1)
+    }
+
+    /// Returns the earliest time after the start by which `percent` is expected to be reached,
+    /// or `None` if the curve never reaches it.
+    pub fn get_time_at(&self, percent: f64) -> Option<f64> {
+        if percent <= 0f64 {
+            return Some(0f64);
+        }
+
+        for pair in self.points.windows(2) {
+            let ((t0, p0), (t1, p1)) = (pair[0], pair[1]);
+
+            if percent <= p1 && p1 > p0 {
+                let fraction = ((percent - p0) / (p1 - p0)).max(0f64);
+                return Some(t0 as f64 + (t1 - t0) as f64 * fraction);
+            }
+        }
+
+        None
+    }
+
+    // Returns the time after the start at which `percent` is expected, as close to `elapsed` as
+    // possible: the curve stays at a percent for a while if it has a flat part, e.g. a setup
+    // phase, and the operation is on schedule anywhere within it. Returns `None` if the curve
+    // never reaches `percent`.
+    fn get_time_near(&self, percent: f64, elapsed: f64) -> Option<f64> {
+        let earliest = self.get_time_at(percent)?;
+
+        // The latest time is where the curve rises above `percent`, or the end of the curve.
+        let latest = self.points.windows(2)
+            .find(|pair| pair[1].1 > percent)
+            .map(|pair| {
+                let ((t0, p0), (t1, p1)) = (pair[0], pair[1]);
+                t0 as f64 + (t1 - t0) as f64 * ((percent - p0) / (p1 - p0)).max(0f64)
+            })
+            .unwrap_or_else(|| self.points.last().map_or(0f64, |p| p.0 as f64));
+
+        Some(elapsed.clamp(earliest, latest.max(earliest)))
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Sets the expected progress curve of the operation. See `ProgressCurve`,
+    /// `get_curve_status` and `get_curve_remaining_time`.
+    pub fn set_expected_curve(&mut self, curve: ProgressCurve) {
+        self.curve = Some(curve);
+    }
+
+    /// Returns how far ahead of or behind the expected curve the operation is, as of the latest
+    /// update: `time` is how much earlier than expected the current progress was reached (0 if
+    /// the curve expects the current progress for a while, and the operation is within it), and
+    /// `percent` is the difference between the current and the expected progress. Returns `None`
+    /// before the first update, or if no curve is set.
+    pub fn get_curve_status(&self) -> Option<ScheduleStatus> {
+        let (curve, origin, now) = (self.curve.as_ref()?, self.origin?, self.called?);
+        let elapsed = now.saturating_sub(origin.timestamp);
+        let percent = self.get_percent();
+
+        let time = match curve.get_time_near(percent, elapsed as f64) {
+            Some(expected) => expected - elapsed as f64,
+            // Beyond the end of the curve, the operation is ahead by the time left.
+            None => curve.get_points().last().map_or(0f64, |p| p.0 as f64) - elapsed as f64,
+        };
+
+        Some(ScheduleStatus {
+            time: clamp_offset(time),
+            percent: percent - curve.get_percent_at(elapsed),
+        })
+    }
+
+    /// Returns the remaining time estimated from the expected curve: the time the curve expects
+    /// from the current progress to 100%, scaled by how much faster or slower than expected the
+    /// operation was so far. Returns 0 if it can't be estimated: before the first update, if no
+    /// curve is set, or if the curve doesn't reach 100%.
+    pub fn get_curve_remaining_time(&self) -> i64 {
+        let (curve, origin, now) = match (self.curve.as_ref(), self.origin, self.called) {
+            (Some(curve), Some(origin), Some(now)) => (curve, origin, now),
+            _ => return 0,
+        };
+
+        let percent = self.get_percent();
+        let elapsed = now.saturating_sub(origin.timestamp) as f64;
+        let (expected, end) = match (curve.get_time_near(percent, elapsed),
+                                     curve.get_time_at(100.0f64)) {
+            (Some(expected), Some(end)) => (expected, end),
+            _ => return 0,
+        };
+
+        let pace = if expected > 0f64 { elapsed / expected } else { 1f64 };
+
+        clamp_time((end - expected) * pace)
+    }
+}
@@ -0,0 +1,74 @@
+/// Describes how many `update_eta` calls of a `TimeContext` do real work.
+///
+/// Skipped calls only decrement a counter; they don't read the clock. The call which reaches
+/// the maximum progress value is never skipped, so the completion is always noticed.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Decimation {
+    /// Every call does real work. This is the default.
+    Off,
+
+    /// Only every Nth call does real work.
+    Every(u64),
+
+    /// About one call per the given number of units of time does real work. The number of calls
+    /// skipped in between is tuned automatically, from the rate of the calls.
+    Budget(u64),
+}
+
+// The state of the decimation policy of a `TimeContext`.
+#[derive(Copy, Clone, Debug)]
+pub struct Decimator {
+    policy: Decimation,
+    skip: u64,            // Calls left to skip.
+    stride: u64,          // Calls per real update, tuned by `Budget`.
+    last: Option<u64>,    // Timestamp of the last real update.
+}
+
+impl Decimator {
+    pub fn new(policy: Decimation) -> Decimator {
+        Decimator {
+            policy,
+            skip: 0u64,
+            stride: 1u64,
+            last: None,
+        }
+    }
+
+    pub fn get_policy(&self) -> Decimation { self.policy }
+
+    // Returns true if the current call should be skipped.
+    pub fn skip(&mut self, finished: bool) -> bool {
+        if self.skip == 0 || finished {
+            return false;
+        }
+
+        self.skip -= 1;
+        true
+    }
+
+    // Called for every real update, to set the number of calls to skip until the next one. The
+    // `Budget` policy doubles the stride when the real updates are too close to each other, and
+    // halves it when they're too far apart.
+    pub fn record(&mut self, now: u64) {
+        match self.policy {
+            Decimation::Off => {}
+            Decimation::Every(n) => self.skip = n.saturating_sub(1),
+            Decimation::Budget(interval) => {
+                if let Some(last) = self.last {
+                    let elapsed = now.saturating_sub(last);
+
+                    if elapsed < interval {
+                        self.stride = self.stride.saturating_mul(2);
+                    } else if elapsed > interval.saturating_mul(2) && self.stride > 1 {
+                        self.stride /= 2;
+                    }
+                }
+
+                self.last = Some(now);
+                self.skip = self.stride - 1;
+            }
+        }
+    }
+}
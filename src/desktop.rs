@@ -0,0 +1,130 @@
+use std::format;
+use std::string::{String, ToString};
+use std::time::Duration;
+use std::vec::Vec;
+
+use notify_rust::Notification;
+
+use crate::{Observer, Snapshot, Status};
+
+/// An `Observer` which raises desktop notifications, through `notify-rust`, when the operation
+/// finishes, when it stalls, and when the remaining time drops below the given thresholds.
+///
+/// Notifications are shown from `update_eta`, which can then take a few milliseconds longer, but
+/// only when one of the above happens. Failures to show a notification, e.g. because there's no
+/// notification daemon, are ignored.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{DesktopNotifier, SystemTimer, TimeContext};
+///   # use std::time::Duration;
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   let mut notifier = DesktopNotifier::new("Backup", Duration::from_millis(1));
+///   notifier.add_threshold(Duration::from_secs(60));
+///
+///   ctx.add_observer(Box::new(notifier));
+/// ```
+pub struct DesktopNotifier {
+    title: String,        // Summary of every notification.
+    unit: Duration,       // Unit of time of the `GetTimestamp` struct.
+    complete: bool,       // Notify on completion.
+    stall: bool,          // Notify on stall.
+    thresholds: Vec<(Duration, bool)>,
+                          // Thresholds of the remaining time, and whether they were crossed.
+    last: Option<Duration>,
+                          // The remaining time of the previous sample.
+}
+
+impl DesktopNotifier {
+    /// Creates a notifier for an operation named `title`, which notifies on completion and on
+    /// stall. `unit` is the `Duration` of a single unit of time of the `GetTimestamp` struct
+    /// (e.g. `Duration::from_millis(1)` for `SystemTimer`).
+    pub fn new(title: &str, unit: Duration) -> DesktopNotifier {
+        DesktopNotifier {
+            title: title.to_string(),
+            unit,
+            complete: true,
+            stall: true,
+            thresholds: Vec::new(),
+            last: None,
+        }
+    }
+
+    /// Enables or disables the notification on completion.
+    pub fn set_notify_complete(&mut self, enabled: bool) {
+        self.complete = enabled;
+    }
+
+    /// Enables or disables the notification on stall. Stalls are only detected if the context
+    /// has a stall timeout; see `TimeContext::set_stall_timeout`.
+    pub fn set_notify_stall(&mut self, enabled: bool) {
+        self.stall = enabled;
+    }
+
+    /// Adds a notification for when the remaining time drops below `remaining`. It's raised once,
+    /// and not at all if the first estimate is already below it.
+    pub fn add_threshold(&mut self, remaining: Duration) {
+        self.thresholds.push((remaining, self.last.is_some_and(|last| last <= remaining)));
+    }
+
+    fn show(&self, body: &str) {
+        let _ = Notification::new().summary(&self.title).body(body).show();
+    }
+}
+
+impl Observer for DesktopNotifier {
+    fn on_progress(&mut self, snapshot: &Snapshot) {
+        if snapshot.status != Status::Running || snapshot.remaining <= 0 {
+            return;
+        }
+
+        let remaining = self.unit.saturating_mul(snapshot.remaining.min(u32::MAX as i64) as u32);
+        let first = self.last.is_none();
+        let mut crossed = None;
+
+        for (threshold, passed) in self.thresholds.iter_mut() {
+            if !*passed && remaining <= *threshold {
+                *passed = true;
+
+                // The smallest threshold tells the most when the ETA drops below several at once.
+                if !first && crossed.is_none_or(|t| *threshold < t) {
+                    crossed = Some(*threshold);
+                }
+            }
+        }
+
+        self.last = Some(remaining);
+
+        if let Some(threshold) = crossed {
+            self.show(&format!("Less than {} left ({:.0}% done)", format_duration(threshold),
+                               snapshot.progress));
+        }
+    }
+
+    fn on_stall(&mut self) {
+        if self.stall {
+            self.show("No progress for a while");
+        }
+    }
+
+    fn on_complete(&mut self) {
+        if self.complete {
+            self.show("Finished");
+        }
+    }
+}
+
+// Formats `duration` for humans, with at most two units, e.g. "1 h 5 min", "3 min", or "45 s".
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, min, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    match (h, min, s) {
+        (0, 0, s) => format!("{} s", s),
+        (0, min, 0) => format!("{} min", min),
+        (0, min, s) => format!("{} min {} s", min, s),
+        (h, 0, _) => format!("{} h", h),
+        (h, min, _) => format!("{} h {} min", h, min),
+    }
+}
@@ -0,0 +1,129 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use http::header::{HeaderMap, CONTENT_LENGTH};
+
+use crate::{EtaHandle, GetTimestamp, TimeContext};
+
+/// A stream adapter which counts the bytes of an HTTP response body and updates a context.
+///
+/// It's created by `track_body`. The body is expected to be a stream of chunks, like the one
+/// returned by `reqwest::Response::bytes_stream`. When the size of the body is known, every
+/// chunk updates the context. When it's not (there's no `Content-Length` header, e.g. because
+/// of chunked transfer encoding), the context only measures the throughput (see
+/// `TimeContext::update_count`) until the size is set with `set_total`, or the body ends.
+pub struct EtaBody<S, T> {
+    stream: S,
+    handle: EtaHandle<T>,
+    count: u64,           // Bytes yielded so far.
+    total: Option<u64>,   // Expected number of bytes, if known.
+}
+
+impl<S, T: GetTimestamp> EtaBody<S, T> {
+    /// Returns a handle which can be used to read the live ETA while the body is consumed.
+    pub fn handle(&self) -> EtaHandle<T> {
+        self.handle.clone()
+    }
+
+    /// Returns the number of bytes yielded so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the expected size of the body, if it's known.
+    pub fn get_total(&self) -> Option<u64> { self.total }
+
+    /// Sets the expected size of the body, e.g. when it's reported by other means than the
+    /// `Content-Length` header. The throughput measured so far is kept.
+    pub fn set_total(&mut self, total: u64) {
+        self.total = Some(total);
+    }
+}
+
+impl<S, B, E, T> Stream for EtaBody<S, T>
+    where S: Stream<Item = Result<B, E>> + Unpin, B: AsRef<[u8]>, T: GetTimestamp
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<S::Item>> {
+        let this = &mut *self;
+        let item = Pin::new(&mut this.stream).poll_next(cx);
+
+        match item {
+            Poll::Ready(Some(Ok(ref chunk))) => {
+                this.count = this.count.saturating_add(chunk.as_ref().len() as u64);
+
+                match this.total {
+                    Some(total) => this.handle.update_eta(this.count, total),
+                    None => this.handle.update_count(this.count),
+                }
+            }
+            Poll::Ready(None) => {
+                // The server may send less than announced; the body is complete anyway.
+                this.handle.update_eta(this.count, this.count);
+            }
+            Poll::Ready(Some(Err(_))) | Poll::Pending => {}
+        }
+
+        item
+    }
+}
+
+/// Returns the size of a response body announced by its `Content-Length` header, if any.
+pub fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers.get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+}
+
+/// Wraps the body stream of an HTTP response with the given `headers` into an `EtaBody`, and
+/// returns it together with a handle to read the live ETA.
+///
+/// # Example
+///
+/// ```rust
+///   # use futures::StreamExt;
+///   # use gaeta::{track_body, SystemTimer};
+///   # use std::io::Write;
+///   # mod reqwest {
+///   #     pub struct Response(pub http::HeaderMap);
+///   #     impl Response {
+///   #         pub fn headers(&self) -> &http::HeaderMap { &self.0 }
+///   #         pub fn bytes_stream(self) -> impl futures::Stream<Item = std::io::Result<Vec<u8>>> {
+///   #             futures::stream::iter(vec![Ok(vec![0u8; 16])])
+///   #         }
+///   #     }
+///   #     pub async fn get(_url: &str) -> std::io::Result<Response> {
+///   #         Ok(Response(http::HeaderMap::new()))
+///   #     }
+///   # }
+///   # async fn download(url: &str, file: &mut Vec<u8>) -> std::io::Result<()> {
+///   let response = reqwest::get(url).await?;
+///   let headers = response.headers().clone();
+///   let (mut body, eta) = track_body(&headers, response.bytes_stream(), SystemTimer::new());
+///
+///   while let Some(chunk) = body.next().await {
+///       file.write_all(&chunk?)?;
+///       // `eta.get_remaining_time()` can be read from any task.
+///   }
+///   # drop(eta);
+///   # Ok(())
+///   # }
+///   # futures::executor::block_on(download("http://example.com/", &mut Vec::new())).unwrap();
+/// ```
+///
+/// The body stream must be `Unpin`; use `Box::pin` for streams which aren't.
+pub fn track_body<S, T>(headers: &HeaderMap, body: S, timefunc: T) -> (EtaBody<S, T>, EtaHandle<T>)
+    where T: GetTimestamp
+{
+    let handle = EtaHandle::new(TimeContext::new(timefunc));
+    let body = EtaBody {
+        stream: body,
+        handle: handle.clone(),
+        count: 0u64,
+        total: content_length(headers),
+    };
+
+    (body, handle)
+}
@@ -0,0 +1,152 @@
+use crate::{GetTimestamp, Snapshot, TimeContext, MAX_REMAINING_TIME};
+
+/// One of the two metrics tracked by a `DualContext`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Metric {
+    /// The amount of data, e.g. bytes transferred.
+    Bytes,
+
+    /// The number of items, e.g. files processed.
+    Items,
+}
+
+/// A copy of the values calculated by a `DualContext` at some point in time.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DualSnapshot {
+    /// The snapshot of the bytes metric.
+    pub bytes: Snapshot,
+
+    /// The snapshot of the items metric.
+    pub items: Snapshot,
+
+    /// Currently measured speed, in bytes per unit of time.
+    pub bytes_per_unit: f64,
+
+    /// Currently measured speed, in items per unit of time.
+    pub items_per_unit: f64,
+
+    /// Remaining time, estimated from `predictor`.
+    pub remaining: i64,
+
+    /// The metric the remaining time is estimated from.
+    pub predictor: Metric,
+}
+
+/// An estimator which tracks two metrics of one job, the amount of data and the number of items,
+/// like a backup tool counting both bytes and files.
+///
+/// Both metrics are measured, and both rates are reported, but the remaining time is estimated
+/// from the better predictor: the metric whose estimates of the finish time were the most stable
+/// so far. That's usually the bytes, but e.g. a job dominated by per-file overhead (many tiny
+/// files, remote metadata operations) is better predicted by the items.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{DualContext, SystemTimer};
+///   # fn copy(_file: &[u8]) -> std::io::Result<()> { Ok(()) }
+///   # fn main() -> std::io::Result<()> {
+///   # let files = vec![vec![0u8; 10], vec![0u8; 20]];
+///   # let total_bytes = 30;
+///   # let mut bytes_done = 0;
+///   let mut ctx = DualContext::new(SystemTimer::new());
+///
+///   for (files_done, file) in files.iter().enumerate() {
+///       copy(file)?;
+///       bytes_done += file.len() as u64;
+///       ctx.update(bytes_done, total_bytes, files_done as u64 + 1, files.len() as u64);
+///   }
+///   # Ok(())
+///   # }
+/// ```
+pub struct DualContext<T> {
+    bytes: TimeContext<T>,
+    items: TimeContext<T>,
+    drift: [f64; 2],      // Average change of the predicted finish time, per metric.
+    finish: [Option<u64>; 2],
+                          // The previous predicted finish time, per metric.
+}
+
+impl<T: GetTimestamp + Clone> DualContext<T> {
+    /// Creates a `DualContext` which will use a user-supplied `GetTimestamp` struct.
+    pub fn new(timefunc: T) -> DualContext<T> {
+        DualContext {
+            bytes: TimeContext::new(timefunc.clone()),
+            items: TimeContext::new(timefunc),
+            drift: [0f64; 2],
+            finish: [None; 2],
+        }
+    }
+
+    /// Updates both metrics, with the same timestamp. See `TimeContext::update_eta`.
+    pub fn update(&mut self, bytes: u64, max_bytes: u64, items: u64, max_items: u64) {
+        let ts = self.bytes.get_timefunc().get_timestamp();
+        self.bytes.update_eta_fast(bytes, max_bytes, ts);
+        self.items.update_eta_fast(items, max_items, ts);
+
+        let remaining = [self.bytes.get_remaining_time(), self.items.get_remaining_time()];
+
+        for (index, remaining) in remaining.iter().enumerate() {
+            if *remaining <= 0 || *remaining >= MAX_REMAINING_TIME {
+                continue;
+            }
+
+            let finish = ts.saturating_add(*remaining as u64);
+
+            if let Some(previous) = self.finish[index] {
+                // An exponential moving average, so a bad start is eventually forgotten.
+                let change = (finish as f64 - previous as f64).abs();
+                self.drift[index] = self.drift[index] * 0.9f64 + change * 0.1f64;
+            }
+
+            self.finish[index] = Some(finish);
+        }
+    }
+
+    /// Returns the metric the remaining time is currently estimated from.
+    pub fn get_predictor(&self) -> Metric {
+        let has_estimate = |ctx: &TimeContext<T>| ctx.get_remaining_time() > 0;
+
+        match (has_estimate(&self.bytes), has_estimate(&self.items)) {
+            (false, true) => Metric::Items,
+            (true, true) if self.drift[1] < self.drift[0] => Metric::Items,
+            _ => Metric::Bytes,
+        }
+    }
+
+    /// Returns the remaining time (ETA), estimated from the better predictor. See
+    /// `TimeContext::get_remaining_time`.
+    pub fn get_remaining_time(&self) -> i64 {
+        self.get_context(self.get_predictor()).get_remaining_time()
+    }
+
+    /// Returns a `DualSnapshot` of the values calculated by this `DualContext` instance.
+    pub fn snapshot(&self) -> DualSnapshot {
+        DualSnapshot {
+            bytes: self.bytes.snapshot(),
+            items: self.items.snapshot(),
+            bytes_per_unit: self.bytes.calc_throughput(),
+            items_per_unit: self.items.calc_throughput(),
+            remaining: self.get_remaining_time(),
+            predictor: self.get_predictor(),
+        }
+    }
+
+    /// Gets a reference to the `TimeContext` tracking `metric`.
+    pub fn get_context(&self, metric: Metric) -> &TimeContext<T> {
+        match metric {
+            Metric::Bytes => &self.bytes,
+            Metric::Items => &self.items,
+        }
+    }
+
+    /// Gets a mutable reference to the `TimeContext` tracking `metric`, e.g. to add observers.
+    pub fn get_context_mut(&mut self, metric: Metric) -> &mut TimeContext<T> {
+        match metric {
+            Metric::Bytes => &mut self.bytes,
+            Metric::Items => &mut self.items,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+use crate::{Snapshot, Status};
+
+// Largest request head read by `serve`; the request line comes first, so the rest is skipped.
+#[cfg(feature = "std")]
+const MAX_HEAD: usize = 8192;
+
+impl Snapshot {
+    /// Formats the snapshot as a JSON object:
+    ///
+    /// ```text
+    ///   {"progress":42.5,"speed":0.012,"remaining":47916,"status":"running"}
+    /// ```
+    ///
+    /// Values which aren't finite numbers are written as `null`.
+    pub fn to_json(&self) -> String {
+        let number = |value: f64| {
+            if value.is_finite() { format!("{}", value) } else { String::from("null") }
+        };
+
+        let status = match self.status {
+            Status::Running => "running",
+            Status::Finished => "finished",
+            Status::Cancelled => "cancelled",
+        };
+
+        format!("{{\"progress\":{},\"speed\":{},\"remaining\":{},\"status\":\"{}\"}}",
+            number(self.progress), number(self.speed), self.remaining, status)
+    }
+}
+
+/// A minimal HTTP handler which serves a `Snapshot` as JSON on a single path, e.g. `/progress`.
+///
+/// It doesn't depend on any server: `respond` turns the bytes of a request into the bytes of a
+/// response, so it can be plugged into anything which gives access to the raw connection, even
+/// without `std`. `serve` handles a whole connection of a `std::net::TcpListener`, and with the
+/// `http` feature, `respond_http` works with the `http` crate types used by most frameworks.
+///
+/// `GET` and `HEAD` requests for the path (with any query string) get the snapshot, other paths
+/// get `404 Not Found`, and other methods `405 Method Not Allowed`. The response is never cached.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{EtaHandle, ProgressEndpoint, SystemTimer, TimeContext};
+///   # use std::net::TcpListener;
+///   # fn main() -> std::io::Result<()> {
+///   # let handle = EtaHandle::new(TimeContext::new(SystemTimer::new()));
+///   let endpoint = ProgressEndpoint::new("/progress");
+///   let listener = TcpListener::bind("127.0.0.1:8080")?;
+///
+///   for stream in listener.incoming() {
+///       let snapshot = handle.snapshot();
+///       let _ = endpoint.serve(&mut stream?, &snapshot);
+///   }
+///   # Ok(())
+///   # }
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProgressEndpoint {
+    path: String,
+}
+
+impl ProgressEndpoint {
+    /// Creates a handler serving the snapshot on `path`.
+    pub fn new(path: &str) -> ProgressEndpoint {
+        ProgressEndpoint {
+            path: path.to_string(),
+        }
+    }
+
+    /// Returns the path the snapshot is served on.
+    pub fn get_path(&self) -> &str { &self.path }
+
+    /// Returns the raw HTTP/1.1 response to the raw `request`, which needs to contain at least
+    /// the request line. The response closes the connection.
+    pub fn respond(&self, request: &[u8], snapshot: &Snapshot) -> Vec<u8> {
+        let line = request.split(|b| *b == b'\n').next().unwrap_or(&[]);
+        let line = core::str::from_utf8(line).unwrap_or("");
+        let mut parts = line.split_whitespace();
+
+        let (code, head, body) = match (parts.next(), parts.next()) {
+            (Some(method), Some(target)) => {
+                let (code, body) = self.route(method, target, snapshot);
+                (code, method == "HEAD", body)
+            }
+            _ => (400, false, String::from("bad request\n")),
+        };
+
+        let content_type = if code == 200 { "application/json" } else { "text/plain" };
+        let mut response = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+             Cache-Control: no-store\r\nConnection: close\r\n",
+            code, reason(code), content_type, body.len());
+
+        if code == 405 {
+            response.push_str("Allow: GET, HEAD\r\n");
+        }
+
+        response.push_str("\r\n");
+        if !head {
+            response.push_str(&body);
+        }
+
+        response.into_bytes()
+    }
+
+    /// Reads a request from `stream`, e.g. an accepted `TcpStream`, and writes the response.
+    #[cfg(feature = "std")]
+    pub fn serve<S: Read + Write>(&self, stream: &mut S, snapshot: &Snapshot) -> io::Result<()> {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+
+        // The whole head is read, so closing the connection doesn't reset it under the client.
+        while request.len() < MAX_HEAD && !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            match stream.read(&mut buf)? {
+                0 => break,
+                n => request.extend_from_slice(&buf[..n]),
+            }
+        }
+
+        stream.write_all(&self.respond(&request, snapshot))?;
+        stream.flush()
+    }
+
+    /// Returns the response to a request of the `http` crate, for frameworks which use its types.
+    #[cfg(feature = "http")]
+    pub fn respond_http<B>(&self, request: &http::Request<B>, snapshot: &Snapshot)
+        -> http::Response<Vec<u8>> {
+        let target = request.uri().path_and_query().map_or("/", |target| target.as_str());
+        let (code, body) = self.route(request.method().as_str(), target, snapshot);
+        let body = if request.method() == http::Method::HEAD { Vec::new() } else { body.into_bytes() };
+
+        let mut response = http::Response::builder()
+            .status(code)
+            .header(http::header::CACHE_CONTROL, "no-store")
+            .header(http::header::CONTENT_TYPE, if code == 200 { "application/json" } else { "text/plain" });
+
+        if code == 405 {
+            response = response.header(http::header::ALLOW, "GET, HEAD");
+        }
+
+        // The headers above are all valid, so building can't fail.
+        response.body(body).unwrap_or_default()
+    }
+
+    // Returns the status code and the body of the response.
+    fn route(&self, method: &str, target: &str, snapshot: &Snapshot) -> (u16, String) {
+        let path = target.split('?').next().unwrap_or("");
+
+        if path != self.path {
+            (404, String::from("not found\n"))
+        } else if method != "GET" && method != "HEAD" {
+            (405, String::from("method not allowed\n"))
+        } else {
+            (200, snapshot.to_json())
+        }
+    }
+}
+
+fn reason(code: u16) -> &'static str {
+    match code {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Method Not Allowed",
+    }
+}
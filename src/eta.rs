@@ -0,0 +1,68 @@
+use core::time::Duration;
+
+use crate::{GetTimestamp, TimeContext, Window, MAX_REMAINING_TIME};
+
+/// A remaining time, which tells apart the cases where no finite value can be given.
+///
+/// It's returned by `TimeContext::get_eta`. Unlike `get_remaining_time`, which uses 0 and
+/// `MAX_REMAINING_TIME` for these cases, it can be converted to a `Duration` safely.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EtaValue {
+    /// There's no estimate yet, e.g. before the progress changed for the first time.
+    Unknown,
+
+    /// The progress doesn't advance, or so slowly that the remaining time is effectively
+    /// infinite.
+    Infinite,
+
+    /// The remaining time, in units of time used by the `GetTimestamp` struct. It's 0 when the
+    /// operation is complete.
+    Remaining(u64),
+}
+
+impl EtaValue {
+    /// Converts the remaining time to a `Duration`, given the `Duration` of a single unit of time
+    /// of the `GetTimestamp` struct (e.g. `Duration::from_millis(1)` for `SystemTimer`).
+    ///
+    /// Returns `None` for the `Unknown` and `Infinite` values, and if the result would overflow
+    /// `Duration`.
+    pub fn to_duration(&self, unit: Duration) -> Option<Duration> {
+        let units = match *self {
+            EtaValue::Remaining(units) => units,
+            _ => return None,
+        };
+
+        let nanos = unit.as_nanos().checked_mul(units as u128)?;
+        let secs = nanos / 1_000_000_000;
+
+        if secs > u64::MAX as u128 {
+            return None;
+        }
+
+        Some(Duration::new(secs as u64, (nanos % 1_000_000_000) as u32))
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Returns the remaining time as an `EtaValue`, which tells apart an unknown and an
+    /// infinite remaining time from a finite one. See `get_remaining_time`.
+    pub fn get_eta(&self) -> EtaValue {
+        if self.origin.is_none() || !self.known {
+            return EtaValue::Unknown;
+        }
+
+        if self.get_percent() >= 100.0f64 {
+            EtaValue::Remaining(0u64)
+        } else if self.remaining >= MAX_REMAINING_TIME {
+            EtaValue::Infinite
+        } else if self.remaining > 0 {
+            EtaValue::Remaining(self.remaining as u64)
+        } else if self.samples.is_empty() || self.curspeed.is_nan() {
+            EtaValue::Unknown
+        } else {
+            // There's work left, but the speed measured so far is zero or negative.
+            EtaValue::Infinite
+        }
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(feature = "std")]
+use std::sync::mpsc::Sender;
+
+use crate::Snapshot;
+
+/// A notification about a state transition of a `TimeContext`.
+///
+/// Events are delivered to the receivers returned by `TimeContext::subscribe`.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Event {
+    /// The first update was received.
+    Started,
+
+    /// A new sample was accepted.
+    Progressed {
+        /// Values calculated after the sample was accepted.
+        snapshot: Snapshot,
+    },
+
+    /// The progress didn't change for longer than the stall timeout. See
+    /// `TimeContext::set_stall_timeout`. It's sent once per stall; a `Progressed` event follows
+    /// when the progress changes again.
+    Stalled,
+
+    /// The projected completion time slipped past the deadline. See `TimeContext::set_deadline`.
+    /// It's sent once per deadline.
+    DeadlineMissed {
+        /// The projected completion time, as a timestamp of the `GetTimestamp` struct.
+        projected: u64,
+    },
+
+    /// The speed stayed below the minimum acceptable speed for longer than the grace period. See
+    /// `TimeContext::set_speed_floor`.
+    SlowedDown {
+        /// The measured speed, in work units per unit of time.
+        speed: f64,
+    },
+
+    /// The progress reached 100%.
+    Finished,
+
+    /// The operation was cancelled with `TimeContext::cancel`.
+    Cancelled,
+}
+
+/// A trait for objects which want to be notified about state transitions of a `TimeContext`.
+///
+/// Observers are registered with `TimeContext::add_observer`, and their methods are invoked from
+/// `update_eta` at the right moments, so callers don't need to poll the context to detect the
+/// transitions themselves. All methods have empty default implementations, so an observer only
+/// needs to implement the ones it's interested in.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{Observer, SystemTimer, TimeContext};
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   struct Logger;
+///
+///   impl Observer for Logger {
+///       fn on_stall(&mut self) {
+///           println!("no progress for a while...");
+///       }
+///
+///       fn on_complete(&mut self) {
+///           println!("done!");
+///       }
+///   }
+///
+///   ctx.add_observer(Box::new(Logger));
+/// ```
+pub trait Observer {
+    /// Called after the first update was received.
+    fn on_start(&mut self) {}
+
+    /// Called after a new sample was accepted.
+    fn on_progress(&mut self, _snapshot: &Snapshot) {}
+
+    /// Called once when the progress didn't change for longer than the stall timeout.
+    fn on_stall(&mut self) {}
+
+    /// Called once when the projected completion time slips past the deadline, with the
+    /// projected completion time.
+    fn on_deadline_missed(&mut self, _projected: u64) {}
+
+    /// Called once per period of low speed, with the measured speed.
+    fn on_slow(&mut self, _speed: f64) {}
+
+    /// Called once when the progress reaches 100%.
+    fn on_complete(&mut self) {}
+
+    /// Called once when the operation is cancelled with `TimeContext::cancel`.
+    fn on_cancel(&mut self) {}
+
+    /// Returns `true` if this observer doesn't want to receive any more notifications. Closed
+    /// observers are removed by the context.
+    fn is_closed(&self) -> bool { false }
+}
+
+// Forwards the notifications to a channel created by `TimeContext::subscribe`.
+#[cfg(feature = "std")]
+pub struct Subscriber {
+    tx: Sender<Event>,
+    closed: bool,         // The receiver was dropped.
+}
+
+#[cfg(feature = "std")]
+impl Subscriber {
+    pub fn new(tx: Sender<Event>) -> Subscriber {
+        Subscriber { tx, closed: false }
+    }
+
+    fn send(&mut self, event: Event) {
+        if self.tx.send(event).is_err() {
+            self.closed = true;
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Observer for Subscriber {
+    fn on_start(&mut self) {
+        self.send(Event::Started);
+    }
+
+    fn on_progress(&mut self, snapshot: &Snapshot) {
+        self.send(Event::Progressed { snapshot: *snapshot });
+    }
+
+    fn on_stall(&mut self) {
+        self.send(Event::Stalled);
+    }
+
+    fn on_deadline_missed(&mut self, projected: u64) {
+        self.send(Event::DeadlineMissed { projected });
+    }
+
+    fn on_slow(&mut self, speed: f64) {
+        self.send(Event::SlowedDown { speed });
+    }
+
+    fn on_complete(&mut self) {
+        self.send(Event::Finished);
+    }
+
+    fn on_cancel(&mut self) {
+        self.send(Event::Cancelled);
+    }
+
+    fn is_closed(&self) -> bool { self.closed }
+}
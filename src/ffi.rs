@@ -0,0 +1,114 @@
+//! C interface to the estimator.
+//!
+//! The functions in this module are exported with unmangled names, so C and C++ programs can
+//! link against the static or dynamic library built from this crate, using the declarations
+//! from `include/gaeta.h`. Available only with the `ffi` feature. The library can be built with
+//! `cargo rustc --release --features ffi --crate-type staticlib` (or `cdylib`).
+
+use alloc::boxed::Box;
+use std::os::raw::c_void;
+use std::ptr;
+
+use crate::{GetTimestamp, SystemTimer, TimeContext};
+
+/// A clock callback supplied by C code. It receives the user pointer passed to
+/// `gaeta_create_with_clock`, and returns the current time in any unit.
+pub type GaetaClockFn = extern "C" fn(user: *mut c_void) -> u64;
+
+enum Clock {
+    System(SystemTimer),
+    User(GaetaClockFn, *mut c_void),
+}
+
+impl GetTimestamp for Clock {
+    fn get_timestamp(&self) -> u64 {
+        match *self {
+            Clock::System(ref timer) => timer.get_timestamp(),
+            Clock::User(f, user) => f(user),
+        }
+    }
+}
+
+/// An opaque estimator handle used by the C interface.
+pub struct GaetaContext {
+    ctx: TimeContext<Clock>,
+}
+
+/// Creates a new estimator, which measures time in milliseconds using the system's monotonic
+/// clock. The returned pointer must be released with `gaeta_destroy`.
+#[no_mangle]
+pub extern "C" fn gaeta_create() -> *mut GaetaContext {
+    let ctx = TimeContext::new(Clock::System(SystemTimer::new()));
+    Box::into_raw(Box::new(GaetaContext { ctx }))
+}
+
+/// Creates a new estimator, which reads the time by calling `clock` with `user` as its argument.
+/// The returned pointer must be released with `gaeta_destroy`. Returns `NULL` if `clock` is
+/// `NULL`.
+#[no_mangle]
+pub extern "C" fn gaeta_create_with_clock(clock: Option<GaetaClockFn>, user: *mut c_void)
+    -> *mut GaetaContext {
+    match clock {
+        Some(clock) => {
+            let ctx = TimeContext::new(Clock::User(clock, user));
+            Box::into_raw(Box::new(GaetaContext { ctx }))
+        }
+        None => ptr::null_mut(),
+    }
+}
+
+/// Updates the estimator with the current progress. See `TimeContext::update_eta`.
+///
+/// # Safety
+///
+/// `ctx` must be `NULL` or a pointer returned by one of the `gaeta_create` functions, which was
+/// not destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gaeta_update(ctx: *mut GaetaContext, cur_prog: u64, max_prog: u64) {
+    if let Some(ctx) = ctx.as_mut() {
+        ctx.ctx.update_eta(cur_prog, max_prog);
+    }
+}
+
+/// Returns the remaining time, in the units of the clock, or 0 if `ctx` is `NULL`. See
+/// `TimeContext::get_remaining_time`.
+///
+/// # Safety
+///
+/// `ctx` must be `NULL` or a pointer returned by one of the `gaeta_create` functions, which was
+/// not destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gaeta_remaining(ctx: *const GaetaContext) -> i64 {
+    match ctx.as_ref() {
+        Some(ctx) => ctx.ctx.get_remaining_time(),
+        None => 0,
+    }
+}
+
+/// Returns the current speed, in percent per unit of time, or 0 if `ctx` is `NULL`. See
+/// `TimeContext::calc_speed_per_unit`.
+///
+/// # Safety
+///
+/// `ctx` must be `NULL` or a pointer returned by one of the `gaeta_create` functions, which was
+/// not destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gaeta_speed(ctx: *const GaetaContext) -> f64 {
+    match ctx.as_ref() {
+        Some(ctx) => ctx.ctx.calc_speed_per_unit(),
+        None => 0f64,
+    }
+}
+
+/// Releases an estimator. Does nothing if `ctx` is `NULL`.
+///
+/// # Safety
+///
+/// `ctx` must be `NULL` or a pointer returned by one of the `gaeta_create` functions, which was
+/// not destroyed yet. It must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn gaeta_destroy(ctx: *mut GaetaContext) {
+    if !ctx.is_null() {
+        drop(Box::from_raw(ctx));
+    }
+}
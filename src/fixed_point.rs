@@ -0,0 +1,140 @@
+use crate::{GetTimestamp, MAX_REMAINING_TIME};
+
+/// The progress of a job, in millionths, which corresponds to 100%.
+pub const PROGRESS_SCALE: u64 = 1_000_000;
+
+/// The default number of fractional bits in the speed returned by `FixedPointContext`.
+pub const SPEED_FRACTION_BITS: u32 = 16;
+
+/// An estimator which uses only integer arithmetic.
+///
+/// It works like `TimeContext`, but it never touches floating-point numbers, which makes it
+/// suitable for microcontrollers without an FPU, where every `f64` operation is an expensive
+/// call to a soft-float library. The progress is tracked in millionths of the job (see
+/// `PROGRESS_SCALE`), and the speed is a fixed-point number of millionths per unit of time, with
+/// `F` fractional bits (`SPEED_FRACTION_BITS` by default).
+///
+/// Up to `N` recent samples are kept inline, so the estimator doesn't allocate either. Both are
+/// fixed at compile time, so every configuration is a separate, exactly-sized type, e.g.
+/// `FixedPointContext<Timer, 4, 24>` for a small window and a precise speed of slow jobs. `F` can
+/// be at most 32; larger values are rejected when the context is created.
+#[derive(Copy, Clone, Debug)]
+pub struct FixedPointContext<T, const N: usize = 10, const F: u32 = { SPEED_FRACTION_BITS }> {
+    timefunc: T,          // An interface that provides current time in some unit.
+    curspeed: u64,        // Current speed per time unit, in fixed-point.
+    fts: Option<u64>,     // First timestamp.
+    fprog: Option<u64>,   // First progress, in millionths.
+    cprog: u64,           // Current progress, in millionths.
+    samples: [(u64, u64); N],
+                          // Recent (timestamp, progress) pairs, oldest at `head`.
+    head: usize,          // Index of the oldest sample.
+    len: usize,           // Number of stored samples.
+}
+
+impl<T: GetTimestamp, const N: usize, const F: u32> FixedPointContext<T, N, F> {
+    // Evaluated at compile time for every `F` the context is created with. The progress takes up
+    // to 20 bits, so the shifted value always fits in `u64`.
+    const VALID_FRACTION_BITS: () = assert!(F <= 32, "too many fractional bits");
+
+    /// Creates new `FixedPointContext` which will use a user-supplied `GetTimestamp` struct.
+    pub fn new(timefunc: T) -> FixedPointContext<T, N, F> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_FRACTION_BITS;
+
+        FixedPointContext {
+            timefunc,
+            curspeed: 0u64,
+            fts: None,
+            fprog: None,
+            cprog: 0u64,
+            samples: [(0u64, 0u64); N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Updates the state of this `FixedPointContext` instance. See `TimeContext::update_eta`.
+    pub fn update_eta(&mut self, cur_prog: u64, max_prog: u64) {
+        let ts = self.timefunc.get_timestamp();
+        let prog = scale_progress(cur_prog, max_prog);
+        self.cprog = prog;
+
+        if self.fts.is_none() { self.fts = Some(ts); }
+        if self.fprog.is_none() { self.fprog = Some(prog); }
+
+        if self.len > 0 && self.samples[(self.head + self.len - 1) % N].1 == prog {
+            return;
+        }
+
+        self.push_sample(ts, prog);
+        self.curspeed = self.calc_speed_per_unit();
+    }
+
+    fn push_sample(&mut self, ts: u64, prog: u64) {
+        if N == 0 {
+            return;
+        }
+
+        if self.len == N {
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+        }
+
+        self.samples[(self.head + self.len) % N] = (ts, prog);
+        self.len += 1;
+    }
+
+    /// Returns the current progress, in millionths of the job (see `PROGRESS_SCALE`).
+    pub fn get_progress(&self) -> u64 { self.cprog }
+
+    /// Returns the currently measured speed, in millionths of the job per unit of time, as a
+    /// fixed-point number with `F` fractional bits.
+    ///
+    /// The unit of time is the same as chosen by the `GetTimestamp`'s `get_timestamp` method.
+    pub fn calc_speed_per_unit(&self) -> u64 {
+        if self.len == 0 {
+            return 0u64;
+        }
+
+        // Measured over the window, from the oldest to the newest sample, like in `TimeContext`.
+        let (ts, prog) = self.samples[(self.head + self.len - 1) % N];
+        let (from_ts, from_prog) = match self.samples[self.head] {
+            (oldest_ts, oldest_prog) if oldest_ts < ts => (oldest_ts, oldest_prog),
+            _ => (self.fts.unwrap_or(0u64), self.fprog.unwrap_or(0u64)),
+        };
+
+        let elapsed = ts.saturating_sub(from_ts);
+        let done = prog.saturating_sub(from_prog) << F;
+        done / if elapsed == 0 { 1 } else { elapsed }
+    }
+
+    /// Returns the remaining time (ETA), or 0 if the operation is complete or it can't be
+    /// estimated yet. The result is clamped to `MAX_REMAINING_TIME`, like in `TimeContext`.
+    ///
+    /// The unit of time is the same as chosen by the `GetTimestamp`'s `get_timestamp` method.
+    pub fn get_remaining_time(&self) -> u64 {
+        if self.curspeed == 0 {
+            return 0u64;
+        }
+
+        let remaining = PROGRESS_SCALE.saturating_sub(self.cprog) << F;
+        (remaining / self.curspeed).min(MAX_REMAINING_TIME as u64)
+    }
+
+    /// Gets a reference to the underlying `GetTimestamp` struct.
+    pub fn get_timefunc(&self) -> &T { &self.timefunc }
+
+    /// Gets a mutable reference to the underlying `GetTimestamp` struct.
+    pub fn get_timefunc_mut(&mut self) -> &mut T { &mut self.timefunc }
+}
+
+// Converts `cur / max` to millionths, exactly and without floats. The intermediate product can't
+// overflow `u128`.
+fn scale_progress(cur: u64, max: u64) -> u64 {
+    if max == 0 {
+        return PROGRESS_SCALE;
+    }
+
+    let cur = if cur > max { max } else { cur };
+    (cur as u128 * PROGRESS_SCALE as u128 / max as u128) as u64
+}
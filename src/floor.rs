@@ -0,0 +1,68 @@
+use crate::{sample_speed, GetTimestamp, Sample, Status, TimeContext, Window};
+
+// The state of the low speed alert of a `TimeContext`.
+#[derive(Copy, Clone, Debug)]
+pub struct Floor {
+    rate: f64,            // Minimum acceptable speed, in work units per unit of time.
+    grace: u64,           // Time the speed may stay below the minimum.
+    since: Option<u64>,   // Time the speed fell below the minimum.
+    reported: bool,       // The current period of low speed was reported.
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Sets the minimum acceptable speed, in work units per unit of time. When the measured
+    /// speed stays below `min_rate` for longer than `grace` units of time, it's reported as
+    /// `Event::SlowedDown`, e.g. to detect a degraded network path early in a long transfer.
+    /// It's reported once per period of low speed; the speed has to recover before it can be
+    /// reported again.
+    ///
+    /// The speed is measured as in the estimate, so a stall counts as low speed too. Like stalls,
+    /// it's detected in `update_eta`.
+    pub fn set_speed_floor(&mut self, min_rate: f64, grace: u64) {
+        self.floor = Some(Floor {
+            rate: min_rate,
+            grace,
+            since: None,
+            reported: false,
+        });
+    }
+
+    // Called for every update. Returns the measured speed if it should be reported as too low.
+    pub(crate) fn check_floor(&mut self, now: u64) -> Option<f64> {
+        let mut floor = self.floor?;
+
+        let speed = match (self.reference(), self.lts) {
+            (Some(reference), Some(lts))
+                if self.status == Status::Running && !self.samples.is_empty() =>
+            {
+                let throughput = self.calc_throughput();
+
+                if now > lts {
+                    // Without progress since the newest sample, the speed falls with the stall.
+                    let now = Sample { timestamp: now, current: self.cur };
+                    throughput.min(sample_speed(&reference, &now))
+                } else {
+                    throughput
+                }
+            }
+            _ => return None,
+        };
+
+        let mut report = None;
+
+        if speed < floor.rate {
+            let since = *floor.since.get_or_insert(now);
+
+            if !floor.reported && now.saturating_sub(since) > floor.grace {
+                floor.reported = true;
+                report = Some(speed);
+            }
+        } else {
+            floor.since = None;
+            floor.reported = false;
+        }
+
+        self.floor = Some(floor);
+        report
+    }
+}
@@ -0,0 +1,66 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::{EtaHandle, GetTimestamp, TimeContext};
+
+/// A future which carries its own `TimeContext`.
+///
+/// The wrapped future receives an `EtaHandle` when it's created, and reports its progress by
+/// calling `update_eta` on it while it runs. A supervising task (e.g. a dashboard) can obtain
+/// another handle with the `handle` method and read snapshots of the live ETA.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{EtaFuture, SystemTimer};
+///   # async fn process(_chunk: &u32) {}
+///   # fn spawn<F: std::future::Future>(_job: F) {}
+///   # let chunks = vec![1u32, 2, 3];
+///   let job = EtaFuture::new(SystemTimer::new(), |eta| async move {
+///       for (i, chunk) in chunks.iter().enumerate() {
+///           process(chunk).await;
+///           eta.update_eta(i as u64 + 1, chunks.len() as u64);
+///       }
+///   });
+///
+///   let eta = job.handle();
+///   spawn(job);
+///
+///   // Later, in the dashboard task:
+///   let snapshot = eta.snapshot();
+/// ```
+pub struct EtaFuture<F, T> {
+    future: F,
+    handle: EtaHandle<T>,
+}
+
+impl<F: Future, T: GetTimestamp> EtaFuture<F, T> {
+    /// Creates a new `EtaFuture`. `make` is called with a handle to the embedded context, and
+    /// should return the future which will report its progress through that handle.
+    pub fn new<M>(timefunc: T, make: M) -> EtaFuture<F, T>
+        where M: FnOnce(EtaHandle<T>) -> F {
+        let handle = EtaHandle::new(TimeContext::new(timefunc));
+
+        EtaFuture {
+            future: make(handle.clone()),
+            handle,
+        }
+    }
+
+    /// Returns a handle to the embedded context.
+    pub fn handle(&self) -> EtaHandle<T> {
+        self.handle.clone()
+    }
+}
+
+impl<F: Future, T> Future for EtaFuture<F, T> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<F::Output> {
+        // The wrapped future is structurally pinned: it's never moved out of `self`, and
+        // `EtaFuture` has no `Drop` implementation.
+        let future = unsafe { self.map_unchecked_mut(|s| &mut s.future) };
+        future.poll(cx)
+    }
+}
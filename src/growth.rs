@@ -0,0 +1,65 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::GetProgress;
+
+/// A `GetProgress` source which watches the size of a file growing towards an expected size.
+///
+/// It's meant for jobs done by external programs (encoders, downloaders, archivers) which don't
+/// report their progress, but write their output incrementally. Every `poll_update` reads the
+/// current size of the file; a file which doesn't exist yet counts as empty.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{FileGrowth, SystemTimer, TimeContext};
+///   # use std::process::Command;
+///   # use std::thread;
+///   # use std::time::Duration;
+///   # fn main() -> std::io::Result<()> {
+///   # let args = ["-i", "in.mkv", "out.mkv"];
+///   # let expected_size = 1 << 30;
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   let mut child = Command::new("ffmpeg").args(&args).spawn()?;
+///   let source = FileGrowth::new("out.mkv", expected_size);
+///
+///   while child.try_wait()?.is_none() {
+///       ctx.poll_update(&source);
+///       println!("{} remaining", ctx.get_remaining_time());
+///       thread::sleep(Duration::from_secs(1));
+///   }
+///   # Ok(())
+///   # }
+/// ```
+pub struct FileGrowth {
+    path: PathBuf,
+    expected: u64,        // The final size of the file, in bytes.
+}
+
+impl FileGrowth {
+    /// Starts watching the file at `path`, which is expected to grow to `expected` bytes.
+    pub fn new<P: Into<PathBuf>>(path: P, expected: u64) -> FileGrowth {
+        FileGrowth {
+            path: path.into(),
+            expected,
+        }
+    }
+
+    /// Changes the expected final size, e.g. when a better guess becomes available.
+    pub fn set_expected(&mut self, expected: u64) {
+        self.expected = expected;
+    }
+
+    /// Returns the path of the watched file.
+    pub fn get_path(&self) -> &Path { &self.path }
+
+    /// Returns the expected final size of the file, in bytes.
+    pub fn get_expected(&self) -> u64 { self.expected }
+}
+
+impl GetProgress for FileGrowth {
+    fn get_progress(&self) -> (u64, u64) {
+        let size = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0u64);
+        (size, self.expected)
+    }
+}
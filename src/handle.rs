@@ -0,0 +1,70 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{GetTimestamp, Snapshot, TimeContext};
+
+/// A cheaply clonable, thread-safe handle to a `TimeContext`.
+///
+/// Adapters which update a context on the user's behalf (e.g. stream or I/O wrappers) hand out
+/// an `EtaHandle`, so the live ETA can be read from elsewhere while the operation is running.
+pub struct EtaHandle<T> {
+    inner: Arc<Mutex<TimeContext<T>>>,
+}
+
+impl<T> Clone for EtaHandle<T> {
+    fn clone(&self) -> EtaHandle<T> {
+        EtaHandle { inner: self.inner.clone() }
+    }
+}
+
+impl<T: GetTimestamp> EtaHandle<T> {
+    /// Wraps `ctx` into a new handle.
+    pub fn new(ctx: TimeContext<T>) -> EtaHandle<T> {
+        EtaHandle { inner: Arc::new(Mutex::new(ctx)) }
+    }
+
+    /// Updates the state of the underlying `TimeContext`. See `TimeContext::update_eta`.
+    pub fn update_eta(&self, cur_prog: u64, max_prog: u64) {
+        self.lock().update_eta(cur_prog, max_prog);
+    }
+
+    /// Updates the state of the underlying `TimeContext` while the total is unknown. See
+    /// `TimeContext::update_count`.
+    pub fn update_count(&self, cur_prog: u64) {
+        self.lock().update_count(cur_prog);
+    }
+
+    /// Returns the currently measured speed. See `TimeContext::calc_speed_per_unit`.
+    pub fn calc_speed_per_unit(&self) -> f64 {
+        self.lock().calc_speed_per_unit()
+    }
+
+    /// Returns the remaining time (ETA). See `TimeContext::get_remaining_time`.
+    pub fn get_remaining_time(&self) -> i64 {
+        self.lock().get_remaining_time()
+    }
+
+    /// Cancels the tracked operation. See `TimeContext::cancel`.
+    pub fn cancel(&self) {
+        self.lock().cancel();
+    }
+
+    /// Returns a `Snapshot` of the underlying `TimeContext`.
+    pub fn snapshot(&self) -> Snapshot {
+        self.lock().snapshot()
+    }
+
+    /// Runs `f` with exclusive access to the underlying `TimeContext`.
+    pub fn with<R, F: FnOnce(&mut TimeContext<T>) -> R>(&self, f: F) -> R {
+        f(&mut *self.lock())
+    }
+
+    /// Returns the shared context, e.g. to pass it to `spawn_updater`.
+    pub fn get_shared(&self) -> Arc<Mutex<TimeContext<T>>> {
+        self.inner.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TimeContext<T>> {
+        // A panic in another thread doesn't make the estimator state invalid.
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,135 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// A histogram of the speeds of the intervals between accepted samples over a whole run.
+///
+/// The buckets are defined by their upper bounds, in work units per unit of time: with bounds
+/// `[10, 100]`, there are three buckets, of speeds below 10, from 10 to below 100, and 100 or
+/// more. A job served partly from a cache and partly from a slow device shows up as two separate
+/// peaks, which a single average speed can't tell.
+///
+/// It's enabled with `TimeContext::enable_speed_histogram`.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{SpeedHistogram, TestTimer, TimeContext};
+///   # let mut ctx = TimeContext::new(TestTimer::new());
+///   ctx.enable_speed_histogram(SpeedHistogram::exponential(1.0, 2.0, 20));
+///
+///   // ... run the job, updating `ctx` ...
+///   # for ts in 0..10 {
+///   #     ctx.get_timefunc_mut().set_timestamp(ts * 100);
+///   #     ctx.update_eta(ts * ts, 100);
+///   # }
+///
+///   let histogram = ctx.get_speed_histogram().unwrap();
+///   for (index, count) in histogram.get_counts().iter().enumerate() {
+///       println!("{:?}: {}", histogram.get_range(index), count);
+///   }
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpeedHistogram {
+    bounds: Vec<f64>,     // Upper bounds of all buckets but the last, in ascending order.
+    counts: Vec<u64>,     // Number of intervals per bucket.
+}
+
+impl SpeedHistogram {
+    /// Creates a histogram with buckets bounded by `bounds`. The bounds are sorted, and the
+    /// duplicate ones are removed.
+    pub fn new(mut bounds: Vec<f64>) -> SpeedHistogram {
+        bounds.retain(|b| !b.is_nan());
+        bounds.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        bounds.dedup();
+
+        let counts = vec![0u64; bounds.len() + 1];
+        SpeedHistogram { bounds, counts }
+    }
+
+    /// Creates a histogram with `count` buckets of `width` starting at `start`, plus one bucket of
+    /// the speeds below `start` and one of the speeds above the last bucket.
+    pub fn linear(start: f64, width: f64, count: usize) -> SpeedHistogram {
+        SpeedHistogram::new((0..=count).map(|i| start + width * i as f64).collect())
+    }
+
+    /// Creates a histogram with `count` buckets starting at `start`, each `factor` times wider
+    /// than the previous one, plus one bucket of the speeds below `start` and one of the speeds
+    /// above the last bucket. It covers a wide range of speeds with few buckets.
+    pub fn exponential(start: f64, factor: f64, count: usize) -> SpeedHistogram {
+        let mut bound = start;
+        let mut bounds = Vec::with_capacity(count + 1);
+
+        for _ in 0..=count {
+            bounds.push(bound);
+            bound *= factor;
+        }
+
+        SpeedHistogram::new(bounds)
+    }
+
+    // Called by `TimeContext` with the speed of every accepted interval.
+    pub(crate) fn record(&mut self, speed: f64) {
+        let index = self.bounds.partition_point(|&bound| bound <= speed);
+        self.counts[index] += 1;
+    }
+
+    /// Returns the upper bounds of the buckets, except the last bucket, which has none.
+    pub fn get_bounds(&self) -> &[f64] { &self.bounds }
+
+    /// Returns the number of intervals in every bucket, from the slowest to the fastest bucket.
+    pub fn get_counts(&self) -> &[u64] { &self.counts }
+
+    /// Returns the range of speeds of the bucket `index`, from the lower bound (inclusive) to the
+    /// upper bound (exclusive). The first bucket starts at negative infinity, and the last one
+    /// ends at infinity.
+    pub fn get_range(&self, index: usize) -> (f64, f64) {
+        let lower = if index == 0 { f64::NEG_INFINITY } else { self.bounds[index - 1] };
+        let upper = self.bounds.get(index).copied().unwrap_or(f64::INFINITY);
+        (lower, upper)
+    }
+
+    /// Returns the number of intervals in all buckets.
+    pub fn get_total(&self) -> u64 { self.counts.iter().sum() }
+
+    /// Returns the indices of the buckets which hold more intervals than their neighbours, from
+    /// the slowest to the fastest. More than one peak suggests that the job switches between
+    /// different speeds.
+    pub fn get_peaks(&self) -> Vec<usize> {
+        let counts = &self.counts;
+        let mut peaks = Vec::new();
+        let mut index = 0;
+
+        while index < counts.len() {
+            // A plateau of equal counts is one peak, at its first bucket.
+            let mut end = index;
+            while end + 1 < counts.len() && counts[end + 1] == counts[index] {
+                end += 1;
+            }
+
+            let left = index == 0 || counts[index - 1] < counts[index];
+            let right = end + 1 == counts.len() || counts[end + 1] < counts[index];
+
+            if counts[index] > 0 && left && right {
+                peaks.push(index);
+            }
+
+            index = end + 1;
+        }
+
+        peaks
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Starts counting the speeds of the intervals in the buckets of `histogram`. See
+    /// `SpeedHistogram`.
+    pub fn enable_speed_histogram(&mut self, histogram: SpeedHistogram) {
+        self.histogram = Some(histogram);
+    }
+
+    /// Returns the histogram of the speeds, if it was enabled with `enable_speed_histogram`.
+    pub fn get_speed_histogram(&self) -> Option<&SpeedHistogram> { self.histogram.as_ref() }
+}
@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+
+use crate::{GetTimestamp, Sample, TimeContext, Window};
+
+/// A coarse history of a whole run, kept alongside the fine-grained recent samples of a
+/// `TimeContext`.
+///
+/// At most one point is kept per `resolution` units of time, the newest sample of each period.
+/// When the number of points exceeds the capacity, every other point is dropped and the resolution
+/// is doubled, so the memory used stays bounded no matter how long the run is, while the history
+/// still covers it from the start.
+///
+/// It's enabled with `TimeContext::enable_history`.
+#[derive(Clone, Debug)]
+pub struct History {
+    points: Vec<Sample>,
+    resolution: u64,      // Minimum time between two points.
+    capacity: usize,      // Maximum number of points.
+}
+
+impl History {
+    fn new(resolution: u64, capacity: usize) -> History {
+        let capacity = if capacity > 1 { capacity } else { 2 };
+
+        // One more than the capacity, for the point pushed right before a compaction.
+        History {
+            points: Vec::with_capacity(capacity.saturating_add(1)),
+            resolution: if resolution > 0 { resolution } else { 1 },
+            capacity,
+        }
+    }
+
+    // Called by `TimeContext` for every accepted sample.
+    pub(crate) fn record(&mut self, sample: Sample) {
+        let resolution = self.resolution;
+        let same_period = match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) if self.points.len() > 1 => {
+                let period = |s: &Sample| s.timestamp.saturating_sub(first.timestamp) / resolution;
+                period(last) == period(&sample)
+            }
+            _ => false,
+        };
+
+        if same_period {
+            let last = self.points.len() - 1;
+            self.points[last] = sample;
+            return;
+        }
+
+        self.points.push(sample);
+
+        if self.points.len() > self.capacity {
+            self.compact();
+        }
+    }
+
+    // Halves the number of points, keeping the first and the newest one, and doubles the
+    // resolution.
+    fn compact(&mut self) {
+        let last = self.points.len() - 1;
+        let mut index = 0;
+        self.points.retain(|_| {
+            let keep = index % 2 == 0 || index == last;
+            index += 1;
+            keep
+        });
+
+        self.resolution = self.resolution.saturating_mul(2);
+    }
+
+    /// Returns the points of the history, from the oldest to the newest.
+    pub fn get_points(&self) -> &[Sample] { &self.points }
+
+    /// Returns the current minimum time between two points.
+    pub fn get_resolution(&self) -> u64 { self.resolution }
+
+    /// Returns the time covered by the history.
+    pub fn get_elapsed(&self) -> u64 {
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => last.timestamp.saturating_sub(first.timestamp),
+            _ => 0u64,
+        }
+    }
+
+    /// Returns the average speed of the whole run, in work units per unit of time.
+    pub fn get_average_speed(&self) -> f64 {
+        let elapsed = self.get_elapsed();
+
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) if elapsed > 0 => {
+                (last.current as i128 - first.current as i128) as f64 / elapsed as f64
+            }
+            _ => 0f64,
+        }
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Starts recording a coarse history of the whole run, with at most one point per
+    /// `resolution` units of time, and at most `capacity` points. See `History`.
+    ///
+    /// The recent samples used for the estimate are not affected.
+    pub fn enable_history(&mut self, resolution: u64, capacity: usize) {
+        self.history = Some(History::new(resolution, capacity));
+    }
+
+    /// Returns the coarse history of the run, if it was enabled with `enable_history`.
+    pub fn get_history(&self) -> Option<&History> { self.history.as_ref() }
+}
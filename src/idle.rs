@@ -0,0 +1,70 @@
+use crate::{GetTimestamp, TimeContext, Window};
+
+// The time a `TimeContext` spent below the minimum speed.
+#[derive(Copy, Clone, Debug)]
+pub struct Idle {
+    rate: f64,            // Minimum speed, in work units per unit of time.
+    total: u64,           // Time spent below the minimum speed.
+    sampled: u64,         // `total` as of the newest accepted sample.
+    excluded: bool,       // The idle time is excluded from the average speed.
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Starts measuring the time spent idle: in the intervals between updates without progress, or
+    /// with a speed below `min_rate` work units per unit of time. It's tracked separately from the
+    /// elapsed time, see `get_idle_time`.
+    pub fn set_idle_rate(&mut self, min_rate: f64) {
+        self.idle = Some(Idle {
+            rate: min_rate,
+            total: 0u64,
+            sampled: 0u64,
+            excluded: false,
+        });
+    }
+
+    /// Sets whether the idle time is excluded from the time `calc_average_speed` divides the
+    /// progress by, so the average reflects the speed while the job was actually working. It
+    /// requires the idle time to be measured, see `set_idle_rate`.
+    pub fn set_idle_excluded(&mut self, excluded: bool) {
+        if let Some(idle) = self.idle.as_mut() {
+            idle.excluded = excluded;
+        }
+    }
+
+    /// Returns the total time spent idle so far, or 0 if it's not measured. See `set_idle_rate`.
+    pub fn get_idle_time(&self) -> u64 {
+        self.idle.map_or(0u64, |idle| idle.total)
+    }
+
+    // Called for every update which isn't skipped, before it's applied, with the effective
+    // progress value.
+    pub(crate) fn measure_idle(&mut self, cur_prog: u64, ts: u64) {
+        let (mut idle, previous) = match (self.idle, self.called) {
+            (Some(idle), Some(previous)) if ts > previous => (idle, previous),
+            _ => return,
+        };
+
+        let elapsed = ts - previous;
+        let progress = cur_prog.saturating_sub(self.cur) as f64;
+
+        if progress == 0f64 || progress < idle.rate * elapsed as f64 {
+            idle.total = idle.total.saturating_add(elapsed);
+            self.idle = Some(idle);
+        }
+    }
+
+    // Returns the idle time to subtract from the time since the origin up to the newest sample.
+    pub(crate) fn excluded_idle(&self) -> u64 {
+        match self.idle {
+            Some(idle) if idle.excluded => idle.sampled,
+            _ => 0u64,
+        }
+    }
+
+    // Called after a sample was accepted.
+    pub(crate) fn sample_idle(&mut self) {
+        if let Some(idle) = self.idle.as_mut() {
+            idle.sampled = idle.total;
+        }
+    }
+}
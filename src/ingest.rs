@@ -0,0 +1,164 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// A queue of progress increments, which can be filled from any number of threads and fed to a
+/// `TimeContext` by whichever thread reads the estimates.
+///
+/// Producers call `push` with the time and the amount of work they just finished. It's lock-free:
+/// a few atomic operations, no allocation, and no waiting for the other producers or the reader,
+/// so the hot path of the workers never contends on the mutex of the context. The thread which
+/// displays the progress calls `drain_into` before reading the estimates, which sums up the
+/// increments and feeds the totals to the context. Up to `N` increments can be pending; when the
+/// queue is full, `push` returns false and the caller should retry later or keep the increment
+/// and add it to the next one.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{GetTimestamp, SampleQueue, SystemTimer, TimeContext};
+///   # let timer = SystemTimer::new();
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   # let (bytes_written, total_bytes) = (4096, 1 << 20);
+///   static QUEUE: SampleQueue<256> = SampleQueue::new();
+///
+///   // In any worker thread:
+///   QUEUE.push(timer.get_timestamp(), bytes_written);
+///
+///   // In the rendering thread:
+///   QUEUE.drain_into(&mut ctx, total_bytes);
+///   println!("{} remaining", ctx.get_remaining_time());
+/// ```
+pub struct SampleQueue<const N: usize> {
+    slots: [Slot; N],
+    head: AtomicUsize,    // Number of increments ever claimed by producers.
+    tail: AtomicUsize,    // Number of increments ever claimed by readers.
+    total: AtomicU64,     // Sum of all drained increments.
+    latest: AtomicU64,    // The latest drained timestamp.
+}
+
+// A slot of the queue. Its sequence number tells who may use it: the producer of increment `pos`
+// waits for `lap(pos)`, and the reader waits for `lap(pos) + 1`, where `lap(pos)` is `pos` rounded
+// down to a multiple of `N`.
+struct Slot {
+    seq: AtomicUsize,
+    value: UnsafeCell<(u64, u64)>,
+}
+
+// Every slot is accessed only by the thread which claimed it, as decided by `seq`.
+unsafe impl<const N: usize> Sync for SampleQueue<N> {}
+
+impl<const N: usize> SampleQueue<N> {
+    /// Creates an empty queue. It's a `const fn`, so the queue can be placed in a `static`.
+    pub const fn new() -> SampleQueue<N> {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const EMPTY: Slot = Slot {
+            seq: AtomicUsize::new(0),
+            value: UnsafeCell::new((0u64, 0u64)),
+        };
+
+        SampleQueue {
+            slots: [EMPTY; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            total: AtomicU64::new(0u64),
+            latest: AtomicU64::new(0u64),
+        }
+    }
+
+    /// Records that `delta` units of work were finished at `timestamp` (in units of time used by
+    /// the `TimeContext` the increments are fed to). Returns false if the queue is full and the
+    /// increment was dropped.
+    ///
+    /// It can be called from any thread.
+    pub fn push(&self, timestamp: u64, delta: u64) -> bool {
+        if N == 0 {
+            return false;
+        }
+
+        let mut pos = self.head.load(Ordering::Relaxed);
+
+        let slot = loop {
+            let slot = &self.slots[pos % N];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let lap = pos - pos % N;
+
+            if seq == lap {
+                match self.head.compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed,
+                                                      Ordering::Relaxed) {
+                    Ok(_) => break slot,
+                    Err(current) => pos = current,
+                }
+            } else if seq < lap {
+                // The reader didn't take the increment from the previous lap yet.
+                return false;
+            } else {
+                pos = self.head.load(Ordering::Relaxed);
+            }
+        };
+
+        // The slot is not visible to the readers until `seq` is advanced.
+        unsafe { *slot.value.get() = (timestamp, delta); }
+        slot.seq.store(pos - pos % N + 1, Ordering::Release);
+        true
+    }
+
+    /// Takes the oldest pushed `(timestamp, delta)` pair out of the queue.
+    pub fn pop(&self) -> Option<(u64, u64)> {
+        if N == 0 {
+            return None;
+        }
+
+        let mut pos = self.tail.load(Ordering::Relaxed);
+
+        let slot = loop {
+            let slot = &self.slots[pos % N];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let lap = pos - pos % N;
+
+            if seq == lap + 1 {
+                match self.tail.compare_exchange_weak(pos, pos.wrapping_add(1), Ordering::Relaxed,
+                                                      Ordering::Relaxed) {
+                    Ok(_) => break slot,
+                    Err(current) => pos = current,
+                }
+            } else if seq <= lap {
+                return None;
+            } else {
+                pos = self.tail.load(Ordering::Relaxed);
+            }
+        };
+
+        // The slot is not reused by the producers until `seq` is advanced to the next lap.
+        let value = unsafe { *slot.value.get() };
+        slot.seq.store(pos - pos % N + N, Ordering::Release);
+        Some(value)
+    }
+
+    /// Feeds all pushed increments to `ctx`, with `update_eta_fast`, as the running total out of
+    /// `max_prog`. Returns the number of increments fed.
+    ///
+    /// Producers may push their increments slightly out of order, so a timestamp older than the
+    /// latest one fed is counted as the latest one instead.
+    pub fn drain_into<T, W>(&self, ctx: &mut TimeContext<T, W>, max_prog: u64) -> usize
+        where T: GetTimestamp, W: Window {
+        let mut fed = 0;
+
+        while let Some((timestamp, delta)) = self.pop() {
+            let total = self.total.fetch_add(delta, Ordering::Relaxed).saturating_add(delta);
+            let latest = self.latest.fetch_max(timestamp, Ordering::Relaxed).max(timestamp);
+            ctx.update_eta_fast(total, max_prog, latest);
+            fed += 1;
+        }
+
+        fed
+    }
+
+    /// Returns the sum of all increments fed to a context by `drain_into`.
+    pub fn get_total(&self) -> u64 { self.total.load(Ordering::Relaxed) }
+}
+
+impl<const N: usize> Default for SampleQueue<N> {
+    fn default() -> SampleQueue<N> { SampleQueue::new() }
+}
@@ -0,0 +1,165 @@
+use std::io::{self, Read, Write};
+use std::vec;
+
+use crate::{GetTimestamp, TimeContext};
+
+/// A reader which counts the bytes read through it and updates an internal `TimeContext`.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{EtaReader, SystemTimer};
+///   # use std::fs::File;
+///   # use std::io;
+///   # fn main() -> io::Result<()> {
+///   # let file = File::open("input.bin")?;
+///   # let mut output = io::sink();
+///   let size = file.metadata()?.len();
+///   let mut reader = EtaReader::new(file, size, SystemTimer::new());
+///   io::copy(&mut reader, &mut output)?;
+///   # Ok(())
+///   # }
+/// ```
+///
+/// While the copy is running, the ETA can be read with `get_context().get_remaining_time()`
+/// (e.g. from a `Write` implementation wrapping `output`, or between `read` calls).
+pub struct EtaReader<R, T> {
+    inner: R,
+    ctx: TimeContext<T>,
+    count: u64,           // Bytes read so far.
+    total: u64,           // Expected number of bytes.
+}
+
+/// A writer which counts the bytes written through it and updates an internal `TimeContext`.
+pub struct EtaWriter<W, T> {
+    inner: W,
+    ctx: TimeContext<T>,
+    count: u64,           // Bytes written so far.
+    total: u64,           // Expected number of bytes.
+}
+
+macro_rules! impl_common {
+    ($name:ident, $inner:ident) => {
+        impl<$inner, T: GetTimestamp> $name<$inner, T> {
+            /// Wraps `inner`, which is expected to transfer `total` bytes.
+            pub fn new(inner: $inner, total: u64, timefunc: T) -> $name<$inner, T> {
+                $name {
+                    inner,
+                    ctx: TimeContext::new(timefunc),
+                    count: 0u64,
+                    total,
+                }
+            }
+
+            /// Returns the number of bytes transferred so far.
+            pub fn count(&self) -> u64 {
+                self.count
+            }
+
+            /// Gets a reference to the internal `TimeContext`.
+            pub fn get_context(&self) -> &TimeContext<T> { &self.ctx }
+
+            /// Gets a reference to the underlying I/O object.
+            pub fn get_ref(&self) -> &$inner { &self.inner }
+
+            /// Gets a mutable reference to the underlying I/O object.
+            pub fn get_mut(&mut self) -> &mut $inner { &mut self.inner }
+
+            /// Unwraps this struct, returning the underlying I/O object.
+            pub fn into_inner(self) -> $inner { self.inner }
+
+            fn advance(&mut self, n: usize) {
+                if n > 0 {
+                    self.count = self.count.saturating_add(n as u64);
+                    self.ctx.update_eta(self.count, self.total);
+                }
+            }
+        }
+    }
+}
+
+impl_common!(EtaReader, R);
+impl_common!(EtaWriter, W);
+
+impl<R: Read, T: GetTimestamp> Read for EtaReader<R, T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.advance(n);
+        Ok(n)
+    }
+}
+
+impl<W: Write, T: GetTimestamp> Write for EtaWriter<W, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.advance(n);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Size of the buffer used by `copy_metered`.
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Copies everything from `reader` to `writer`, like `io::copy`, updating `ctx` with the number
+/// of bytes copied so far out of `total`, and calling `report` after every chunk. Returns the
+/// number of bytes copied.
+///
+/// It's the core of a `pv`-like tool, or of a pipeline stage which forwards the output of a
+/// subprocess. `report` is called often, so it should be cheap, or rate-limit itself; for the
+/// typical notifications, an observer added with `add_throttled_observer` is a better fit.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{copy_metered, SystemTimer, TimeContext};
+///   # use std::fs::File;
+///   # use std::process::{Command, Stdio};
+///   # fn main() -> std::io::Result<()> {
+///   # let mut child = Command::new("tar").args(["-c", "."]).stdout(Stdio::piped()).spawn()?;
+///   # let mut file = File::create("out.tar")?;
+///   # let size = 1 << 30;
+///   let mut stdout = child.stdout.take().unwrap();
+///   let mut ctx = TimeContext::new(SystemTimer::new());
+///   copy_metered(&mut stdout, &mut file, size, &mut ctx, |ctx| {
+///       eprint!("\r{} ms remaining", ctx.get_remaining_time());
+///   })?;
+///   # Ok(())
+///   # }
+/// ```
+pub fn copy_metered<R, W, T, F>(reader: &mut R, writer: &mut W, total: u64,
+                                ctx: &mut TimeContext<T>, mut report: F) -> io::Result<u64>
+    where R: Read + ?Sized, W: Write + ?Sized, T: GetTimestamp, F: FnMut(&TimeContext<T>)
+{
+    let mut buf = vec![0u8; COPY_BUFFER_SIZE];
+    let mut count = 0u64;
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        writer.write_all(&buf[..n])?;
+        count = count.saturating_add(n as u64);
+        ctx.update_eta(count, total);
+        report(ctx);
+    }
+
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Copies the standard input to the standard output with `copy_metered`.
+pub fn meter_stdio<T, F>(total: u64, ctx: &mut TimeContext<T>, report: F) -> io::Result<u64>
+    where T: GetTimestamp, F: FnMut(&TimeContext<T>)
+{
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    copy_metered(&mut stdin.lock(), &mut stdout.lock(), total, ctx, report)
+}
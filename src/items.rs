@@ -0,0 +1,86 @@
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// Statistics of the durations of discrete items of an operation, e.g. files of a batch.
+///
+/// Items are marked with `TimeContext::item_started` and `TimeContext::item_finished`, and they
+/// are expected to be processed one after another. Either call is enough on its own: starting an
+/// item finishes the one in progress, and an item finished without being started is measured from
+/// the end of the previous one, or from the first update. All durations are in units of time used
+/// by the `GetTimestamp` struct.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ItemStats {
+    count: u64,           // Number of finished items.
+    total: u64,           // Sum of the durations of the finished items.
+    min: Option<u64>,
+    max: Option<u64>,
+    started: Option<u64>, // Start of the item in progress.
+    boundary: Option<u64>,
+                          // End of the previous item.
+}
+
+impl ItemStats {
+    fn start(&mut self, ts: u64) {
+        if self.started.is_some() {
+            self.finish(ts, None);
+        }
+
+        self.started = Some(ts);
+    }
+
+    // Finishes the item in progress, or the one since `fallback` if there was no previous item.
+    fn finish(&mut self, ts: u64, fallback: Option<u64>) {
+        let start = match self.started.take().or(self.boundary).or(fallback) {
+            Some(start) => start,
+            None => {
+                // Nothing to measure the first item from.
+                self.boundary = Some(ts);
+                return;
+            }
+        };
+
+        let duration = ts.saturating_sub(start);
+        self.count += 1;
+        self.total = self.total.saturating_add(duration);
+        self.min = Some(self.min.map_or(duration, |min| min.min(duration)));
+        self.max = Some(self.max.map_or(duration, |max| max.max(duration)));
+        self.boundary = Some(ts);
+    }
+
+    /// Returns the number of finished items.
+    pub fn get_count(&self) -> u64 { self.count }
+
+    /// Returns the total duration of the finished items.
+    pub fn get_total_duration(&self) -> u64 { self.total }
+
+    /// Returns the average duration of an item, or 0 before any item is finished.
+    pub fn get_average(&self) -> f64 {
+        if self.count == 0 { 0f64 } else { self.total as f64 / self.count as f64 }
+    }
+
+    /// Returns the duration of the fastest item.
+    pub fn get_min(&self) -> Option<u64> { self.min }
+
+    /// Returns the duration of the slowest item.
+    pub fn get_max(&self) -> Option<u64> { self.max }
+
+    /// Returns `true` if an item was started and not finished yet.
+    pub fn is_in_progress(&self) -> bool { self.started.is_some() }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Marks the start of an item. See `ItemStats`.
+    pub fn item_started(&mut self) {
+        let ts = self.now();
+        self.items.start(ts);
+    }
+
+    /// Marks the end of the current item. See `ItemStats`.
+    pub fn item_finished(&mut self) {
+        let ts = self.now();
+        self.items.finish(ts, self.origin.map(|origin| origin.timestamp));
+    }
+
+    /// Returns the statistics of the durations of the items.
+    pub fn get_item_stats(&self) -> &ItemStats { &self.items }
+}
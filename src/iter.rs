@@ -0,0 +1,74 @@
+use crate::{GetTimestamp, TimeContext};
+
+/// An iterator adapter which counts yielded items against a known total and updates an internal
+/// `TimeContext`.
+///
+/// It's created by the `track_eta` method of the `EtaIteratorExt` trait.
+pub struct EtaIter<I, T> {
+    iter: I,
+    ctx: TimeContext<T>,
+    count: u64,           // Number of items yielded so far.
+    total: u64,           // Expected number of items.
+}
+
+impl<I, T: GetTimestamp> EtaIter<I, T> {
+    /// Returns the number of items yielded so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets a reference to the internal `TimeContext`.
+    pub fn get_context(&self) -> &TimeContext<T> { &self.ctx }
+}
+
+impl<I: Iterator, T: GetTimestamp> Iterator for EtaIter<I, T> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.iter.next();
+
+        if item.is_some() {
+            self.count = self.count.saturating_add(1);
+            self.ctx.update_eta(self.count, self.total);
+        }
+
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// An extension trait which adds ETA tracking to any iterator.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{EtaIteratorExt, SystemTimer};
+///   # fn process(_item: &u32) {}
+///   # let work = vec![1u32, 2, 3];
+///   for item in work.iter().track_eta(work.len() as u64, SystemTimer::new()) {
+///       process(item);
+///   }
+///
+///   // Or, to read the ETA inside the loop body:
+///   let mut items = work.iter().track_eta(work.len() as u64, SystemTimer::new());
+///   while let Some(item) = items.next() {
+///       process(item);
+///       println!("{} remaining", items.get_context().get_remaining_time());
+///   }
+/// ```
+pub trait EtaIteratorExt: Iterator + Sized {
+    /// Wraps this iterator, which is expected to yield `total` items, into an `EtaIter`.
+    fn track_eta<T: GetTimestamp>(self, total: u64, timefunc: T) -> EtaIter<Self, T> {
+        EtaIter {
+            iter: self,
+            ctx: TimeContext::new(timefunc),
+            count: 0u64,
+            total,
+        }
+    }
+}
+
+impl<I: Iterator> EtaIteratorExt for I {}
@@ -1,19 +1,321 @@
-extern crate collections;
+//! ETA estimation for long-running operations.
+//!
+//! The core estimator (`TimeContext`) only needs the `alloc` crate, so it works under `no_std`,
+//! e.g. in firmware showing the progress of a flash erase on a small display. Everything which
+//! needs the standard library (threads, channels, I/O wrappers, the built-in `SystemTimer`) is
+//! behind the `std` feature, which is enabled by default.
+//!
+//! With the `defmt` feature, snapshots and the other small value types implement `defmt::Format`,
+//! so they can be logged over RTT without pulling in the `core::fmt` machinery.
+//!
+//! With the `arbitrary` feature, the input types implement `arbitrary::Arbitrary`, and `Scenario`
+//! describes a random sequence of updates, so estimator invariants can be property-tested or
+//! fuzzed.
 
-use collections::RingBuf;
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "futures")]
+extern crate futures;
+#[cfg(feature = "http")]
+extern crate http;
+#[cfg(feature = "desktop")]
+extern crate notify_rust;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{channel, Receiver};
+
+use crate::bias::Bias;
+use crate::calibrate::Calibration;
+use crate::decimation::Decimator;
+use crate::floor::Floor;
+use crate::idle::Idle;
+
+pub use aggregate::{Aggregate, Mode};
+#[cfg(any(feature = "tokio", feature = "futures"))]
+pub use async_io::{EtaAsyncReader, EtaAsyncWriter};
+#[cfg(target_has_atomic = "64")]
+pub use atomic::{AtomicProgress, Unit, Worker};
+#[cfg(target_has_atomic = "ptr")]
+pub use capture::{Capture, Reader, Recorder};
+#[cfg(feature = "std")]
+pub use channel::{ChannelContext, ProgressEvent};
+#[cfg(feature = "std")]
+pub use clock::SystemTimer;
+#[cfg(feature = "std")]
+pub use completion::{Completion, Outcome};
+pub use curve::ProgressCurve;
+pub use decimation::Decimation;
+#[cfg(feature = "desktop")]
+pub use desktop::DesktopNotifier;
+#[cfg(feature = "http")]
+pub use download::{content_length, track_body, EtaBody};
+pub use dual::{DualContext, DualSnapshot, Metric};
+pub use endpoint::ProgressEndpoint;
+pub use eta::EtaValue;
+pub use events::{Event, Observer};
+pub use fixed_point::{FixedPointContext, PROGRESS_SCALE, SPEED_FRACTION_BITS};
+#[cfg(feature = "std")]
+pub use future::EtaFuture;
+#[cfg(feature = "std")]
+pub use handle::EtaHandle;
+#[cfg(feature = "std")]
+pub use growth::FileGrowth;
+pub use histogram::SpeedHistogram;
+pub use history::History;
+#[cfg(target_has_atomic = "64")]
+pub use ingest::SampleQueue;
+#[cfg(feature = "std")]
+pub use io::{copy_metered, meter_stdio, EtaReader, EtaWriter};
+pub use items::ItemStats;
+pub use iter::{EtaIter, EtaIteratorExt};
+pub use milestones::Milestones;
+pub use notifier::EtaNotifier;
+#[cfg(target_has_atomic = "64")]
+pub use pool::{Shard, WorkerPool};
+pub use preset::Preset;
+#[cfg(all(feature = "procfs", target_os = "linux"))]
+pub use procfs::FdProgress;
+#[cfg(target_has_atomic = "64")]
+pub use published::SnapshotCell;
+pub use quantile::RateQuantiles;
+pub use record::{LogEntry, SessionLog};
+pub use rows::{RowProgress, RowReport};
+#[cfg(feature = "arbitrary")]
+pub use scenario::{Scenario, Step};
+pub use schedule::{Schedule, ScheduleStatus};
+#[cfg(all(feature = "shm", unix, target_has_atomic = "64"))]
+pub use shm::SharedProgress;
+pub use single::F32Context;
+pub use smooth::SmoothEta;
+pub use stages::Stages;
+#[cfg(feature = "std")]
+pub use stages::StageHistory;
+pub use stall::{Stall, StallLog};
+pub use state::ContextState;
+#[cfg(all(feature = "status", unix))]
+pub use status::{spawn_status_server, StatusServer};
+#[cfg(feature = "std")]
+pub use store::{FileStore, MemoryStore, RunStats, RunStore, RunSummary};
+#[cfg(feature = "futures")]
+pub use stream::{EtaStream, EtaStreamExt};
+#[cfg(all(feature = "systemd", target_os = "linux"))]
+pub use systemd::SystemdNotifier;
+#[cfg(feature = "std")]
+pub use throttle::Throttled;
+pub use timeline::TimelineSpan;
+#[cfg(feature = "tokio")]
+pub use tokio_updater::spawn_tokio_updater;
+pub use trace::{TraceGenerator, Workload};
+pub use transcode::{TranscodeContext, TranscodeSnapshot};
+pub use tree::{TaskSnapshot, Tracker};
+#[cfg(feature = "std")]
+pub use updater::{spawn_updater, Updater};
+#[cfg(feature = "wasm")]
+pub use wasm::PerformanceTimer;
+pub use weighted::WeightedItems;
+pub use widen::{Narrow, WideTimer};
+pub use window::{DefaultWindow, FixedWindow, HeapWindow, Window};
+
+#[macro_use]
+mod macros;
+
+mod aggregate;
+#[cfg(any(feature = "tokio", feature = "futures"))]
+mod async_io;
+#[cfg(target_has_atomic = "64")]
+mod atomic;
+mod bias;
+mod calibrate;
+#[cfg(target_has_atomic = "ptr")]
+mod capture;
+#[cfg(feature = "std")]
+mod channel;
+#[cfg(feature = "std")]
+mod clock;
+#[cfg(feature = "std")]
+mod completion;
+mod curve;
+mod decimation;
+#[cfg(feature = "desktop")]
+mod desktop;
+#[cfg(feature = "http")]
+mod download;
+mod dual;
+mod endpoint;
+mod eta;
+mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fixed_point;
+mod floor;
+#[cfg(feature = "std")]
+mod future;
+#[cfg(feature = "std")]
+mod handle;
+#[cfg(feature = "std")]
+mod growth;
+mod histogram;
+mod history;
+mod idle;
+#[cfg(target_has_atomic = "64")]
+mod ingest;
+#[cfg(feature = "std")]
+mod io;
+mod items;
+mod iter;
+mod milestones;
+mod notifier;
+#[cfg(target_has_atomic = "64")]
+mod pool;
+mod preset;
+#[cfg(all(feature = "procfs", target_os = "linux"))]
+mod procfs;
+#[cfg(target_has_atomic = "64")]
+mod published;
+mod quantile;
+mod record;
+mod rows;
+#[cfg(feature = "arbitrary")]
+mod scenario;
+mod schedule;
+#[cfg(all(feature = "shm", unix, target_has_atomic = "64"))]
+mod shm;
+mod single;
+mod smooth;
+mod stages;
+mod stall;
+mod state;
+#[cfg(all(feature = "status", unix))]
+mod status;
+#[cfg(feature = "std")]
+mod store;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(all(feature = "systemd", target_os = "linux"))]
+mod systemd;
+#[cfg(feature = "std")]
+mod throttle;
+mod timeline;
+#[cfg(feature = "tokio")]
+mod tokio_updater;
+mod trace;
+mod transcode;
+mod tree;
+#[cfg(feature = "std")]
+mod updater;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod weighted;
+mod widen;
+mod window;
+
+// Used by the exported macros, so they work in `no_std` crates too.
+#[doc(hidden)]
+pub mod __private {
+    pub use core::iter::{ExactSizeIterator, IntoIterator};
+}
 
 impl<T: GetTimestamp> TimeContext<T> {
 
     /// Creates new `TimeContext` which will use a user-supplied `GetTimestamp` struct.
-    #[stable]
     pub fn new(timefunc: T) -> TimeContext<T> {
+        TimeContext::with_window(timefunc)
+    }
+
+    /// Creates a `TimeContext` for a job which is resumed after a restart (e.g. an interrupted
+    /// upload), which will use a user-supplied `GetTimestamp` struct.
+    ///
+    /// `prior_elapsed` is the time the job ran before, in units of time used by the
+    /// `GetTimestamp` struct, and `prior_prog` is the progress it made, out of `max_prog`. The
+    /// context starts with the average speed of the previous run, instead of pretending the
+    /// job starts from scratch; further updates refine it as usual.
+    pub fn resumed(prior_elapsed: u64, prior_prog: u64, max_prog: u64, timefunc: T)
+        -> TimeContext<T> {
+        let mut ctx = TimeContext::new(timefunc);
+        let prior_prog = if prior_prog > max_prog { max_prog } else { prior_prog };
+
+        let now = ctx.timefunc.get_timestamp();
+        ctx.shift = prior_elapsed.saturating_sub(now);
+
+        let now = ctx.now();
+        ctx.origin = Some(Sample {
+            timestamp: now.saturating_sub(prior_elapsed),
+            current: 0u64,
+        });
+        ctx.cur = prior_prog;
+        ctx.max = max_prog;
+        ctx.lts = Some(now);
+        ctx.push_sample(Sample {
+            timestamp: now,
+            current: prior_prog,
+        });
+
+        ctx.refresh(now);
+        ctx
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+
+    /// Creates new `TimeContext` which will use a user-supplied `GetTimestamp` struct, and keep
+    /// its samples in the storage `W`, e.g. `FixedTimeContext::<_, 16>::with_window(timer)`.
+    pub fn with_window(timefunc: T) -> TimeContext<T, W> {
         TimeContext {
-            timefunc: timefunc,
+            timefunc,
             curspeed: 0f64,
-            cprog: 0f64,
-            fprog: None,
-            fts: None,
-            samples: RingBuf::with_capacity(10),
+            remaining: 0,
+            cur: 0u64,
+            max: 0u64,
+            origin: None,
+            samples: W::new(),
+            observers: Vec::new(),
+            stall_timeout: None,
+            monotone: None,
+            lts: None,
+            stalled: false,
+            status: Status::Running,
+            shift: 0u64,
+            history: None,
+            decimator: Decimator::new(Decimation::Off),
+            discard: 0,
+            anchor: None,
+            called: None,
+            epsilon: 0u64,
+            transfer: None,
+            known: true,
+            cap: None,
+            calibration: None,
+            recording: None,
+            quantiles: None,
+            histogram: None,
+            stall_log: None,
+            idle: None,
+            bias: None,
+            deadline: None,
+            missed: false,
+            floor: None,
+            schedule: None,
+            curve: None,
+            finished_at: None,
+            predicted_end: None,
+            items: ItemStats::default(),
         }
     }
 
@@ -26,45 +328,467 @@ impl<T: GetTimestamp> TimeContext<T> {
     /// This method should be called periodically by your busy loop function in order to
     /// property calculate ETA. Internally it will call `GetTimestamp`'s `get_timestamp` method
     /// in order to read current time.
-    #[unstable]
+    ///
+    /// If `cur_prog` is lower than in the previous call (e.g. a failed chunk is retried), the
+    /// measurement restarts from the new value: the speed measured so far is kept until new
+    /// samples are available, and the remaining time grows by the lost work, instead of the
+    /// negative delta being averaged into the speed.
+    ///
+    /// Calling it more often than the clock ticks is cheap: a call with the same timestamp and
+    /// the same values as the previous one returns right after reading the clock, without
+    /// recalculating anything or calling the observers.
+    ///
+    /// This method never allocates: all the storage it needs is reserved up front, by the
+    /// constructor and by `enable_history` and `enable_stall_log`, so it can be called from
+    /// realtime threads which forbid heap use. The only exceptions are the log of
+    /// `enable_recording`, which grows with every update, and the registered observers: they're
+    /// called from here, so they need to follow the same rule; the observer registered by
+    /// `subscribe`, for one, allocates when sending events.
     pub fn update_eta(&mut self, cur_prog: u64, max_prog: u64) {
-        if let None = self.fts {
-            self.fts = Some(self.timefunc.get_timestamp());
+        if self.status == Status::Cancelled || self.decimator.skip(cur_prog >= max_prog) {
+            return;
         }
 
-        self.update_history(cur_prog, max_prog);
-        self.curspeed = self.calc_speed_per_unit();
+        let ts = self.now();
+        self.decimator.record(ts);
+        self.known = true;
+        self.update_at(cur_prog, max_prog, ts);
     }
 
-    fn update_history(&mut self, cur_prog: u64, max_prog: u64) {
-        let prog = self.get_progress(cur_prog, max_prog);
-        self.cprog = prog;
+    /// Updates the state of this `TimeContext` instance while the maximum progress value is not
+    /// known yet, e.g. while a server didn't report the size of a download, or while the files to
+    /// process are still being enumerated.
+    ///
+    /// In this mode only the throughput is measured (see `calc_throughput`): there's no progress
+    /// percentage and no remaining time, and `get_eta` returns `EtaValue::Unknown`. As soon as
+    /// the total is known, `update_eta` can be called instead; the samples recorded so far are
+    /// kept, so the first estimate is based on the whole window, not on a fresh start.
+    pub fn update_count(&mut self, cur_prog: u64) {
+        if self.status == Status::Cancelled || self.decimator.skip(false) {
+            return;
+        }
+
+        let ts = self.now();
+        self.decimator.record(ts);
+        self.known = false;
+        self.update_at(cur_prog, 0u64, ts);
+    }
 
-        if let None = self.fprog {
-            self.fprog = Some(prog);
+    /// Updates the state of this `TimeContext` instance, like `update_eta`, using `timestamp`
+    /// as the current time instead of calling the `GetTimestamp` struct.
+    ///
+    /// It's meant for per-frame calls from a game loop, which already knows the frame time. Its
+    /// cost is bounded: it doesn't read the clock, it ignores the decimation policy, and it does a
+    /// constant amount of work, except for a pass over the history, if enabled, when it's
+    /// compacted. Registered observers are called as usual, so their cost adds to it.
+    pub fn update_eta_fast(&mut self, cur_prog: u64, max_prog: u64, timestamp: u64) {
+        if self.status == Status::Cancelled {
+            return;
         }
 
-        let vhist = &mut self.samples;
-        let ts = self.timefunc.get_timestamp();
+        let ts = timestamp.saturating_add(self.shift);
+        self.known = true;
+        self.update_at(cur_prog, max_prog, ts);
+    }
 
-        let last_progress = if let Some(tv) = vhist.get(vhist.len() - 1) {
-            tv.progress
-        } else {
-            0f64
+    fn update_at(&mut self, cur_prog: u64, max_prog: u64, ts: u64) {
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push(LogEntry {
+                timestamp: ts,
+                current: cur_prog,
+                total: if self.known { Some(max_prog) } else { None },
+            });
+        }
+
+        let cur_prog = match self.transfer {
+            Some(transfer) if self.known => transfer(cur_prog, max_prog),
+            _ => cur_prog,
         };
 
-        if last_progress == prog - self.fprog.unwrap() {
+        // A repeated call within the same tick and without any progress can't change anything.
+        if self.called == Some(ts) && cur_prog == self.cur && max_prog == self.max {
             return;
         }
 
-        if vhist.len() > 9 {
-            vhist.pop_front();
+        self.measure_idle(cur_prog, ts);
+        self.called = Some(ts);
+
+        if self.origin.is_some() && cur_prog < self.cur {
+            self.rebase(ts, cur_prog);
         }
 
-        vhist.push_back(Sample {
+        let started = self.origin.is_none();
+        let origin = *self.origin.get_or_insert(Sample {
             timestamp: ts,
-            progress: prog
+            current: cur_prog,
         });
+
+        let changed = cur_prog != self.cur || max_prog != self.max;
+        self.max = max_prog;
+        let accepted = self.update_history(origin, ts, cur_prog);
+
+        if accepted || changed {
+            self.refresh(ts);
+
+            if accepted {
+                self.correct_bias(ts);
+            }
+        } else {
+            // Without progress only the remaining time changes, growing with the stall.
+            let estimate = self.estimate_remaining(ts);
+            self.show_remaining(estimate);
+        }
+
+        self.predict_end(ts);
+        self.notify(started, accepted, ts);
+    }
+
+    fn notify(&mut self, started: bool, accepted: bool, now: u64) {
+        if started {
+            for o in self.observers.iter_mut() { o.on_start(); }
+        }
+
+        if let Some(log) = self.stall_log.as_mut() {
+            log.update(now, accepted);
+        }
+
+        if accepted {
+            self.stalled = false;
+
+            if !self.observers.is_empty() {
+                let snapshot = self.snapshot();
+                for o in self.observers.iter_mut() { o.on_progress(&snapshot); }
+            }
+        } else if let (Some(timeout), Some(lts)) = (self.stall_timeout, self.lts) {
+            if !self.stalled && self.status == Status::Running && now.saturating_sub(lts) > timeout {
+                self.stalled = true;
+
+                if let Some(log) = self.stall_log.as_mut() {
+                    log.start(lts, now);
+                }
+
+                for o in self.observers.iter_mut() { o.on_stall(); }
+            }
+        }
+
+        // Checked on every update, as the first one (which is not a sample) can be complete too.
+        if self.status == Status::Running && self.get_percent() >= 100.0f64 {
+            self.status = Status::Finished;
+            self.finished_at = Some(now);
+            for o in self.observers.iter_mut() { o.on_complete(); }
+        }
+
+        if let Some(deadline) = self.deadline {
+            // The deadline is given on the timeline of the `GetTimestamp` struct, without the shift.
+            let projected = now.saturating_sub(self.shift).saturating_add(self.remaining as u64);
+
+            if !self.missed && self.status == Status::Running && self.remaining > 0
+                && projected > deadline
+            {
+                self.missed = true;
+                for o in self.observers.iter_mut() { o.on_deadline_missed(projected); }
+            }
+        }
+
+        if let Some(speed) = self.check_floor(now) {
+            for o in self.observers.iter_mut() { o.on_slow(speed); }
+        }
+
+        self.observers.retain(|o| !o.is_closed());
+    }
+
+    /// Registers an `Observer`, whose methods will be invoked when the state of this
+    /// `TimeContext` changes.
+    ///
+    /// Observers must be `Send` and `Sync`, so they don't take away the thread safety of the
+    /// context. They're not copied when the context is cloned.
+    pub fn add_observer(&mut self, observer: Box<dyn Observer + Send + Sync>) {
+        self.observers.push(observer);
+    }
+
+    /// Registers an `Observer` which receives at most `max_per_sec` progress notifications per
+    /// second. See `Throttled`.
+    #[cfg(feature = "std")]
+    pub fn add_throttled_observer<O>(&mut self, observer: O, max_per_sec: u32)
+        where O: Observer + Send + Sync + 'static {
+        self.add_observer(Box::new(Throttled::new(observer, max_per_sec)));
+    }
+
+    /// Cancels the tracked operation.
+    ///
+    /// The context is frozen in the `Status::Cancelled` state: further updates are ignored, and
+    /// snapshots keep reporting the values measured up to this point, so "aborted at 60%" can be
+    /// told apart from "still running at 60%". Observers are notified with `on_cancel`. Does
+    /// nothing if the operation is already finished or cancelled.
+    pub fn cancel(&mut self) {
+        if self.status != Status::Running {
+            return;
+        }
+
+        self.status = Status::Cancelled;
+        for o in self.observers.iter_mut() { o.on_cancel(); }
+        self.observers.retain(|o| !o.is_closed());
+    }
+
+    /// Returns the status of the tracked operation.
+    pub fn get_status(&self) -> Status { self.status }
+
+    /// Returns a `Completion` handle, which resolves when the progress reaches 100%.
+    ///
+    /// If the operation is cancelled, or this `TimeContext` is dropped before that, the handle
+    /// resolves with `Outcome::Cancelled`.
+    #[cfg(feature = "std")]
+    pub fn completion(&mut self) -> Completion {
+        let (mut observer, completion) = completion::CompletionObserver::new();
+
+        if self.status == Status::Finished {
+            observer.resolve(Outcome::Finished);
+        } else if self.status == Status::Cancelled {
+            observer.resolve(Outcome::Cancelled);
+        } else {
+            self.add_observer(Box::new(observer));
+        }
+
+        completion
+    }
+
+    /// Returns a receiver of `Event`s describing state transitions of this `TimeContext`.
+    ///
+    /// Events are sent from `update_eta`, so the receiver can be moved to another thread (e.g. a
+    /// GUI main thread) without sharing the context itself. Any number of receivers can be
+    /// created; dropping one simply unsubscribes it.
+    #[cfg(feature = "std")]
+    pub fn subscribe(&mut self) -> Receiver<Event> {
+        let (tx, rx) = channel();
+        self.add_observer(Box::new(events::Subscriber::new(tx)));
+        rx
+    }
+
+    /// Sets the time after which lack of progress is reported as `Event::Stalled`, in units of
+    /// time used by the `GetTimestamp` struct. By default stalls are not reported.
+    ///
+    /// Stalls are detected in `update_eta`, so it needs to be called even if the progress value
+    /// doesn't change.
+    pub fn set_stall_timeout(&mut self, timeout: u64) {
+        self.stall_timeout = Some(timeout);
+    }
+
+    /// Sets the time the operation should finish by, as a timestamp of the `GetTimestamp` struct.
+    /// The first time the projected completion time (the current time plus the remaining time)
+    /// is later than `deadline`, it's reported as `Event::DeadlineMissed`, so the caller can react
+    /// while there's still time, e.g. by adding workers. It's reported once per deadline.
+    ///
+    /// Like stalls, it's detected in `update_eta`.
+    pub fn set_deadline(&mut self, deadline: u64) {
+        self.deadline = Some(deadline);
+        self.missed = false;
+    }
+
+    /// Makes the remaining time never increase, unless the estimate exceeds the previously
+    /// reported value by more than `margin` units of time. By default the remaining time follows
+    /// the estimate in both directions.
+    ///
+    /// This is meant for displays like installer dialogs, where an ETA which jumps back up looks
+    /// broken, even though it's more accurate. Small increases are absorbed: the reported value
+    /// stays the same until the estimate falls below it again.
+    pub fn set_monotone_margin(&mut self, margin: u64) {
+        self.monotone = Some(margin);
+    }
+
+    /// Sets the decimation policy, which makes only some of the `update_eta` calls do real work.
+    /// See `Decimation`.
+    ///
+    /// This is useful when `update_eta` is called from a very hot loop, e.g. once per processed
+    /// byte, where reading the clock on every call would be a measurable cost.
+    pub fn set_decimation(&mut self, policy: Decimation) {
+        self.decimator = Decimator::new(policy);
+    }
+
+    /// Returns the decimation policy.
+    pub fn get_decimation(&self) -> Decimation { self.decimator.get_policy() }
+
+    /// Sets the smallest progress which counts as new: an update is accepted as a sample only if
+    /// the progress advanced by more than `epsilon` units since the last accepted one. By default
+    /// it's 0, so any change counts.
+    ///
+    /// With very fine-grained progress (e.g. billions of bytes updated from many callbacks) most
+    /// updates differ by a few units only, and accepting all of them fills the window with
+    /// samples which are too close to each other to measure the speed reliably. The progress of
+    /// the ignored updates isn't lost: it's included in the next accepted sample.
+    pub fn set_progress_epsilon(&mut self, epsilon: u64) {
+        self.epsilon = epsilon;
+    }
+
+    /// Sets a function which maps the raw progress value passed to `update_eta` (and the other
+    /// update methods) to the effective progress, out of the same maximum value. By default the
+    /// raw value is used as is.
+    ///
+    /// The estimate assumes that the progress is roughly linear in time. When it's known not to
+    /// be, e.g. when exhausting a search space where later levels take exponentially longer, or
+    /// when the last part of the job is known to be slower, the function can map it to a domain
+    /// where it is. Everything reported by the context (e.g. `get_current` and `snapshot`)
+    /// refers to the effective progress. The function should be monotone, and map the maximum
+    /// value to itself, so the completion is still recognized.
+    ///
+    /// ```rust
+    ///   # use gaeta::{SystemTimer, TimeContext};
+    ///   # let mut ctx = TimeContext::new(SystemTimer::new());
+    ///   // The last 10% of the work takes as long as the first 90%.
+    ///   ctx.set_transfer_function(|cur, max| {
+    ///       let knee = max / 10 * 9;
+    ///       if cur <= knee { cur / 9 * 5 } else { max / 2 + (cur - knee) * 5 }
+    ///   });
+    /// ```
+    pub fn set_transfer_function(&mut self, transfer: fn(u64, u64) -> u64) {
+        self.transfer = Some(transfer);
+    }
+
+    /// Declares the highest possible speed of the job, in work units per unit of time, e.g. the
+    /// limit of a throttled upload or of a rate-limited API. By default there's no cap.
+    ///
+    /// The speed used for the estimate never exceeds the cap, and once the measured speed gets
+    /// close to it (within 10%), the cap itself is used, as it's a better predictor of the
+    /// steady state than the measurement, which may include short bursts above it.
+    pub fn set_rate_cap(&mut self, cap: f64) {
+        self.cap = if cap > 0f64 { Some(cap) } else { None };
+    }
+
+    /// Excludes the first `intervals` intervals between accepted samples from the speed
+    /// measurement. By default none are excluded.
+    ///
+    /// The first intervals often include setup latency (opening files, connecting, filling caches)
+    /// before any real work is done, which makes the first estimates too pessimistic. Unlike a
+    /// time-based warm-up, this counts progress changes, so it adapts to both fast and slow jobs.
+    /// It should be called before the first update.
+    pub fn set_discard_intervals(&mut self, intervals: usize) {
+        self.discard = intervals;
+    }
+
+    /// Reads the current progress from `source` and updates the state of this `TimeContext`
+    /// instance, as if `update_eta` was called with the returned values.
+    pub fn poll_update<P: GetProgress>(&mut self, source: &P) {
+        let (cur_prog, max_prog) = source.get_progress();
+        self.update_eta(cur_prog, max_prog);
+    }
+
+    // Reads the current time, including the shift applied by `from_state`.
+    fn now(&self) -> u64 {
+        self.timefunc.get_timestamp().saturating_add(self.shift)
+    }
+
+    fn update_history(&mut self, origin: Sample, ts: u64, cur_prog: u64) -> bool {
+        self.cur = cur_prog;
+
+        let last = *self.samples.back().or(self.anchor.as_ref()).unwrap_or(&origin);
+
+        if cur_prog.saturating_sub(last.current) <= self.epsilon {
+            return false;
+        }
+
+        let sample = Sample {
+            timestamp: ts,
+            current: cur_prog
+        };
+
+        if self.discard > 0 {
+            // The interval up to this sample is excluded from the speed: the sample only becomes
+            // the point the next interval is measured from.
+            self.discard -= 1;
+            self.anchor = Some(sample);
+        } else {
+            self.calibrate(&sample);
+
+            if ts > last.timestamp {
+                let speed = sample_speed(&last, &sample);
+
+                if let Some(quantiles) = self.quantiles.as_mut() {
+                    quantiles.record(speed);
+                }
+
+                if let Some(histogram) = self.histogram.as_mut() {
+                    histogram.record(speed);
+                }
+            }
+
+            self.push_sample(sample);
+            self.sample_idle();
+        }
+
+        self.lts = Some(ts);
+        true
+    }
+
+    // Restarts the measurement from `cur_prog` after the progress went backwards. The samples
+    // would give negative deltas, so they're all dropped; the speed is kept by `measure_speed`
+    // until a new sample is accepted.
+    fn rebase(&mut self, ts: u64, cur_prog: u64) {
+        while self.samples.pop_front().is_some() {}
+        self.anchor = None;
+
+        self.origin = Some(Sample {
+            timestamp: ts,
+            current: cur_prog,
+        });
+    }
+
+    // Adds a sample to the window, evicting the oldest one if it's full. The origin must already
+    // be set.
+    fn push_sample(&mut self, sample: Sample) {
+        if self.origin.is_none() {
+            return;
+        }
+
+        if self.samples.len() >= self.samples.limit() {
+            self.samples.pop_front();
+        }
+
+        if self.samples.limit() > 0 {
+            self.samples.push_back(sample);
+        }
+
+        if let Some(ref mut history) = self.history {
+            history.record(sample);
+        }
+    }
+
+    // The sample the speed is measured from: the oldest one in the window (or in its part used
+    // by the calibration), or the last discarded one or the origin if the window doesn't span any
+    // time yet.
+    fn reference(&self) -> Option<Sample> {
+        match self.calibration {
+            Some(calibration) => self.reference_over(calibration.get_span()),
+            None => self.reference_over(usize::MAX),
+        }
+    }
+
+    // Like `reference`, for a window of the `span` most recent intervals.
+    fn reference_over(&self, span: usize) -> Option<Sample> {
+        let oldest = self.samples.len().saturating_sub(span.saturating_add(1));
+
+        match (self.samples.get(oldest), self.samples.back()) {
+            (Some(oldest), Some(newest)) if newest.timestamp > oldest.timestamp => Some(*oldest),
+            _ => self.anchor.or(self.origin),
+        }
+    }
+
+    // Recomputes the cached speed and remaining time. Called by the updating methods, so the
+    // accessors are cheap and return the same values until the next update.
+    fn refresh(&mut self, now: u64) {
+        self.curspeed = self.measure_speed();
+
+        let estimate = self.estimate_remaining(now);
+        self.show_remaining(estimate);
+    }
+
+    // Stores the remaining time returned by the accessors, applying the monotone mode.
+    fn show_remaining(&mut self, estimate: i64) {
+        self.remaining = match self.monotone {
+            // An unknown (0) remaining time may always be replaced by an estimate.
+            Some(margin) if self.remaining > 0 && estimate > self.remaining => {
+                if (estimate - self.remaining) as u64 > margin { estimate } else { self.remaining }
+            }
+            _ => estimate,
+        };
     }
 
     /// Returns the currently measured speed.
@@ -73,56 +797,181 @@ impl<T: GetTimestamp> TimeContext<T> {
     /// `GetTimestamp` returns time units measured in seconds, the result of `calc_speed_per_unit`
     /// can be interpreted as: `x%` for every second.
     ///
-    /// The unit of time is the same as chosen by the `GetTimestamp`'s `get_timestamp` method.
-    #[unstable]
-    pub fn calc_speed_per_unit(&self) -> f64 {
-        if self.samples.len() == 0 {
-            return 0f64;
+    /// The speed is measured over the window of recent samples, from the oldest to the newest one,
+    /// so it reflects the recent behavior of the operation rather than the average since the start.
+    ///
+    /// The unit of time is the same as chosen by the `GetTimestamp`'s `get_timestamp` method. The
+    /// value is computed by `update_eta`, so calling this method is cheap.
+    pub fn calc_speed_per_unit(&self) -> f64 { self.curspeed }
+
+    /// Returns the currently measured speed in work units per unit of time, which, unlike
+    /// `calc_speed_per_unit`, doesn't depend on the maximum progress value, so it's available
+    /// also while it's unknown (see `update_count`).
+    pub fn calc_throughput(&self) -> f64 {
+        match (self.reference(), self.samples.back()) {
+            (Some(reference), Some(newest)) => sample_speed(&reference, newest),
+            _ => 0f64,
+        }
+    }
+
+    /// Returns the average speed of the whole run so far, in work units per unit of time, or 0
+    /// before there's at least one sample after the first one. The idle time can be excluded, see
+    /// `set_idle_excluded`.
+    pub fn calc_average_speed(&self) -> f64 {
+        match self.measure_run() {
+            Some((elapsed, units)) => units as f64 / if elapsed == 0 { 1 } else { elapsed } as f64,
+            None => 0f64,
         }
+    }
 
-        let mut speed_sum = 0f64;
-        for vitem in self.samples.iter() {
-            let timestamp = vitem.timestamp - self.fts.unwrap_or(0u64);
-            let percent = vitem.progress - self.fprog.unwrap_or(0f64);
+    // Returns the time from the origin to the newest sample, without the excluded idle time, and
+    // the progress made in it.
+    pub(crate) fn measure_run(&self) -> Option<(u64, u64)> {
+        let (origin, newest) = (self.origin?, *self.samples.back()?);
+        let elapsed = newest.timestamp.saturating_sub(origin.timestamp)
+            .saturating_sub(self.excluded_idle());
 
-            let speed_sample = percent as f64 / if timestamp == 0 { 1 } else { timestamp } as f64;
-            speed_sum = speed_sum + speed_sample;
+        Some((elapsed, newest.current.saturating_sub(origin.current)))
+    }
+
+    fn measure_speed(&self) -> f64 {
+        if self.samples.is_empty() {
+            return self.curspeed;
+        }
+
+        // The samples are in work units, so the speed is converted to percent only here, using
+        // the current total.
+        if self.max == 0 || !self.known {
+            return 0f64;
         }
 
-        let speed = speed_sum / self.samples.len() as f64;
-        speed
+        let throughput = match self.cap {
+            // Measurements near the cap mean the job runs at it, and bursts above it (e.g. a
+            // token bucket being emptied) don't last.
+            Some(cap) if self.calc_throughput() >= cap * RATE_CAP_THRESHOLD => cap,
+            _ => self.calc_throughput(),
+        };
+
+        throughput * 100.0f64 / self.max as f64
     }
 
     fn get_progress(&self, cur: u64, max: u64) -> f64 {
-        ((cur as f64) * 100.0f64 / (max as f64))
+        // There's nothing to do in a job of size 0, so it's complete.
+        if max == 0 {
+            return 100.0f64;
+        }
+
+        // `f64` has only 53 bits of mantissa, so for huge values `cur` and `max` would be rounded
+        // before the division. The integer part is computed exactly instead, and only the
+        // fraction, which is below 1%, is subject to rounding.
+        let scaled = cur as u128 * 100;
+        let whole = scaled / max as u128;
+        let rest = scaled % max as u128;
+
+        let progress = whole as f64 + rest as f64 / max as f64;
+
+        // A fraction just below 1 can be rounded up, which mustn't make an unfinished job look
+        // complete; the largest value below 100 is used instead.
+        if cur < max && progress >= 100.0f64 {
+            return f64::from_bits(100.0f64.to_bits() - 1);
+        }
+
+        progress
+    }
+
+    fn get_percent(&self) -> f64 {
+        // Before the first update there's no job of size 0 yet, just a job which didn't start.
+        if !self.known || self.origin.is_none() {
+            return 0f64;
+        }
+
+        self.get_progress(self.cur, self.max)
     }
 
+    /// Returns the progress value passed to the last `update_eta` call, in work units.
+    pub fn get_current(&self) -> u64 { self.cur }
+
+    /// Returns the maximum progress value passed to the last `update_eta` call, in work units.
+    pub fn get_total(&self) -> u64 { self.max }
+
     /// Returns the remaining time (ETA).
     ///
-    /// The unit of time is the same as chosen by the `GetTimestamp`'s `get_timestamp` method.
-    #[unstable]
-    pub fn get_remaining_time(&self) -> int {
-        if let None = self.fts { return 0; }
-        if let None = self.fprog { return 0; }
+    /// The unit of time is the same as chosen by the `GetTimestamp`'s `get_timestamp` method. The
+    /// value is computed by `update_eta`, so calling this method is cheap.
+    ///
+    /// While the progress doesn't change, the time since the last change is taken into account:
+    /// the estimate is based on the average speed including the stall, if it's lower than the
+    /// measured one, so the remaining time grows instead of staying frozen.
+    ///
+    /// The result is always between 0 and `MAX_REMAINING_TIME`. It's 0 when the operation is
+    /// complete (including progress values over 100%), and when there's no estimate: before the
+    /// first update, or while the measured speed is zero, negative or not a number. Estimates
+    /// larger than `MAX_REMAINING_TIME`, caused by a near-zero speed, are clamped to it.
+    pub fn get_remaining_time(&self) -> i64 { self.remaining }
 
-        let remaining_prc = 100.0f64 - (self.cprog - self.fprog.unwrap_or(0f64));
-        let whole_work = remaining_prc / self.curspeed;
+    /// Returns the remaining time at `now` (a timestamp of the `GetTimestamp` struct), counted
+    /// down from the value computed by the last update, without recalculating the estimate.
+    ///
+    /// It's meant for displays refreshed more often than the context is updated, e.g. once per
+    /// frame, which would otherwise show the same value for several frames and then jump. See
+    /// also `SmoothEta`. The result stops at 0, and stays there until the next update.
+    pub fn get_remaining_time_at(&self, now: u64) -> i64 {
+        let elapsed = match self.called {
+            Some(ts) => now.saturating_add(self.shift).saturating_sub(ts),
+            None => 0u64,
+        };
 
-        if whole_work >= 0f64 {
-            whole_work as int
-        } else {
-            0i
+        // An effectively infinite estimate doesn't count down.
+        if self.remaining >= MAX_REMAINING_TIME {
+            return self.remaining;
+        }
+
+        (self.remaining - elapsed.min(MAX_REMAINING_TIME as u64) as i64).max(0)
+    }
+
+    fn estimate_remaining(&self, now: u64) -> i64 {
+        let origin = match self.origin {
+            Some(origin) => origin,
+            None => return 0,
+        };
+
+        let speed = match self.lts {
+            // Right after a rebase there are no samples, and the reference is the new origin, so
+            // the stall would measure no speed at all; the kept speed is used until a new sample.
+            Some(lts) if now > lts && !self.samples.is_empty() => {
+                let reference = self.reference().unwrap_or(origin);
+                let stalled = sample_speed(&reference, &Sample { timestamp: now, current: self.cur });
+                self.curspeed.min(stalled * 100.0f64 / self.max as f64)
+            }
+            _ => self.curspeed,
+        };
+
+        let remaining_prc = 100.0f64 - self.get_percent();
+
+        if remaining_prc.is_nan() || remaining_prc <= 0f64 || speed.is_nan() || speed <= 0f64 {
+            return 0;
+        }
+
+        let whole_work = remaining_prc / speed * self.get_bias_factor();
+        clamp_time(whole_work)
+    }
+
+    /// Returns a `Snapshot` of the values calculated by this `TimeContext` instance.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            progress: self.get_percent(),
+            speed: self.curspeed,
+            remaining: self.remaining,
+            status: self.status,
         }
     }
 
     /// Gets a reference to the underlying `GetTimestamp` struct, which was set by the `new`
     /// constructor.
-    #[experimental]
     pub fn get_timefunc(&self) -> &T { &self.timefunc }
 
     /// Gets a mutable reference to the underlying `GetTimestamp` struct, which was set by the
     /// `new` constructor.
-    #[experimental]
     pub fn get_timefunc_mut(&mut self) -> &mut T { &mut self.timefunc }
 }
 
@@ -131,34 +980,124 @@ impl<T: GetTimestamp> TimeContext<T> {
 /// # Example of a valid implementation of `GetTimestamp` trait
 ///
 /// ```rust
-///   extern crate time;
+///   # use gaeta::GetTimestamp;
+///   use std::time::Instant;
 ///
-///   struct SystemTimer;
+///   struct MonotonicTimer {
+///       start: Instant,
+///   }
 ///
-///   impl SystemTimer {
-///       fn new() -> SystemTimer { SystemTimer }
+///   impl MonotonicTimer {
+///       fn new() -> MonotonicTimer { MonotonicTimer { start: Instant::now() } }
 ///   }
 ///
 ///   // Trait implementation.
-///   impl GetTimestamp for SystemTimer {
+///   impl GetTimestamp for MonotonicTimer {
 ///       fn get_timestamp(&self) -> u64 {
-///           // Converts nanosecond to a millisecond.
+///           // Converts the elapsed time to milliseconds.
 ///
-///           time::precise_time_ns() / 1_000_000
+///           self.start.elapsed().as_millis() as u64
 ///       }
 ///   }
 ///
 /// ```
 ///
 /// This example implementation chooses a millisecond to be the unit of time used by the library.
-#[experimental]
 pub trait GetTimestamp {
     fn get_timestamp(&self) -> u64;
 }
 
-struct Sample {
+/// A trait that describes a callback mechanism for `gaeta` to read the current progress.
+///
+/// It's the counterpart of `GetTimestamp`: instead of pushing the progress with `update_eta`, the
+/// user can let the `TimeContext` pull it with `poll_update`. This is useful when the progress
+/// is an external gauge, like the size of a file being written by another process, or the number
+/// of rows in a database table.
+///
+/// The trait is implemented for closures returning a `(cur_prog, max_prog)` pair, so simple
+/// sources don't need a dedicated struct.
+///
+/// # Example of a valid implementation of `GetProgress` trait
+///
+/// ```rust
+///   # use gaeta::GetProgress;
+///   use std::fs;
+///   use std::path::PathBuf;
+///
+///   struct FileSize {
+///       path: PathBuf,
+///       expected: u64,
+///   }
+///
+///   impl GetProgress for FileSize {
+///       fn get_progress(&self) -> (u64, u64) {
+///           let size = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+///           (size, self.expected)
+///       }
+///   }
+/// ```
+pub trait GetProgress {
+    /// Returns the current `(cur_prog, max_prog)` pair.
+    fn get_progress(&self) -> (u64, u64);
+}
+
+impl<F: Fn() -> (u64, u64)> GetProgress for F {
+    fn get_progress(&self) -> (u64, u64) {
+        (*self)()
+    }
+}
+
+/// A progress value recorded by a `TimeContext` at some point in time, in work units.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Sample {
     timestamp: u64,
-    progress: f64,
+    current: u64,
+}
+
+impl Sample {
+    /// Returns the time at which the sample was recorded.
+    pub fn get_timestamp(&self) -> u64 { self.timestamp }
+
+    /// Returns the recorded progress value, in work units.
+    pub fn get_current(&self) -> u64 { self.current }
+}
+
+/// A copy of the values calculated by a `TimeContext` at some point in time.
+///
+/// Snapshots are cheap to copy, so they can be sent to other threads (e.g. the UI thread) without
+/// sharing the context itself.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Snapshot {
+    /// Current progress, in percent.
+    pub progress: f64,
+
+    /// Currently measured speed, in percent per unit of time. See `calc_speed_per_unit`.
+    pub speed: f64,
+
+    /// Remaining time. See `get_remaining_time`.
+    pub remaining: i64,
+
+    /// Status of the tracked operation.
+    pub status: Status,
+}
+
+/// Describes the status of an operation tracked by a `TimeContext`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Status {
+    /// The operation is in progress (or wasn't started yet).
+    Running,
+
+    /// The progress reached 100%.
+    Finished,
+
+    /// The operation was cancelled with `TimeContext::cancel`.
+    Cancelled,
 }
 
 /// Wraps ETA calculation methods into one struct.
@@ -171,34 +1110,213 @@ struct Sample {
 ///
 /// This way the `gaeta` library doesn't create any dependencies by itself. The user also can
 /// choose the best way to get the timestamp.
-#[experimental]
-pub struct TimeContext<T> {
+///
+/// A `TimeContext` is `Send` and `Sync` whenever its `GetTimestamp` struct is, so it can be moved
+/// into a worker thread or shared behind a `Mutex` (see `EtaHandle`). It's also `Clone` when the
+/// `GetTimestamp` struct is; a clone has the same measurements, but no observers, which makes it
+/// suitable for what-if calculations that shouldn't trigger any notifications.
+///
+/// The recent samples are kept in a `Window`, by default the 10 most recent ones, inline in the
+/// context. See `FixedTimeContext` for other sizes.
+pub struct TimeContext<T, W = DefaultWindow> {
     timefunc: T,          // An interface that provides current time in some unit.
     curspeed: f64,        // Current speed per time unit.
-    fts: Option<u64>,     // First timestamp.
-    fprog: Option<f64>,   // First progress.
-    cprog: f64,           // Current progress.
-    samples: RingBuf<Sample>,
+    remaining: i64,       // Current remaining time.
+    origin: Option<Sample>,
+                          // First timestamp and progress; `None` until the first update.
+    cur: u64,             // Current progress, in work units.
+    max: u64,             // Current maximum progress, in work units.
+    samples: W,
                           // A small table of recent time samples, to make the result more smooth.
+    observers: Vec<Box<dyn Observer + Send + Sync>>,
+                          // Objects notified about state transitions.
+    stall_timeout: Option<u64>,
+                          // Time without progress after which a stall is reported.
+    monotone: Option<u64>,
+                          // Margin of the monotone mode, if enabled.
+    lts: Option<u64>,     // Timestamp of the last accepted sample.
+    stalled: bool,        // A stall was reported and the progress didn't change since.
+    status: Status,       // Running, finished or cancelled.
+    shift: u64,           // Added to every timestamp; see `from_state`.
+    history: Option<History>,
+                          // Coarse history of the whole run, if enabled.
+    decimator: Decimator, // Decides which updates are skipped.
+    discard: usize,       // Accepted samples still to be excluded from the speed window.
+    anchor: Option<Sample>,
+                          // The last excluded sample.
+    called: Option<u64>,  // Timestamp of the last update which wasn't skipped.
+    epsilon: u64,         // Progress below or at which a sample isn't new.
+    transfer: Option<fn(u64, u64) -> u64>,
+                          // Maps the raw progress to the effective progress.
+    known: bool,          // The maximum progress value is known.
+    cap: Option<f64>,     // The highest possible speed, in work units per unit of time.
+    calibration: Option<Calibration>,
+                          // State of the choice of the window span, if enabled.
+    recording: Option<SessionLog>,
+                          // Log of all updates, if enabled.
+    quantiles: Option<RateQuantiles>,
+                          // Percentiles of the speed over the run, if enabled.
+    histogram: Option<SpeedHistogram>,
+                          // Distribution of the speed over the run, if enabled.
+    stall_log: Option<StallLog>,
+                          // All stalls of the run, if enabled.
+    idle: Option<Idle>,   // Time spent below the minimum speed, if measured.
+    bias: Option<Bias>,   // State of the correction of the estimate, if enabled.
+    deadline: Option<u64>,
+                          // Time the operation should finish by.
+    missed: bool,         // The deadline miss was reported.
+    floor: Option<Floor>, // State of the low speed alert, if enabled.
+    schedule: Option<Schedule>,
+                          // The plan the operation is compared with.
+    curve: Option<ProgressCurve>,
+                          // The expected progress-vs-time curve.
+    finished_at: Option<u64>,
+                          // Timestamp at which the progress reached 100%.
+    predicted_end: Option<u64>,
+                          // Completion time predicted by the first estimate.
+    items: ItemStats,     // Durations of the marked items.
+}
+
+/// The largest remaining time returned by the estimators, in units of time used by the
+/// `GetTimestamp` struct. It's the largest value of `i32`, so the results fit in 32 bits (e.g. in
+/// the C API) and are the same on every platform.
+pub const MAX_REMAINING_TIME: i64 = 0x7fff_ffff;
+
+// The fraction of the rate cap above which the cap is used as the speed.
+const RATE_CAP_THRESHOLD: f64 = 0.9;
+
+// Converts a time calculated in floating point to `i64`. Negative and NaN values (e.g. from a zero
+// or negative speed) are clamped to 0, and values too large to `MAX_REMAINING_TIME`.
+fn clamp_time(time: f64) -> i64 {
+    if time >= MAX_REMAINING_TIME as f64 {
+        MAX_REMAINING_TIME
+    } else if time > 0f64 {
+        time as i64
+    } else {
+        0i64
+    }
+}
+
+// Converts a time which may be negative, e.g. the time ahead of a schedule, to an integer,
+// limited to `MAX_REMAINING_TIME` in both directions.
+fn clamp_offset(time: f64) -> i64 {
+    let limit = MAX_REMAINING_TIME as f64;
+    time.clamp(-limit, limit) as i64
+}
+
+// Speed between two samples, in work units per unit of time.
+fn sample_speed(from: &Sample, to: &Sample) -> f64 {
+    let timestamp = to.timestamp.saturating_sub(from.timestamp);
+    let units = (to.current as i128 - from.current as i128) as f64;
+
+    units / if timestamp == 0 { 1 } else { timestamp } as f64
+}
+
+/// A `TimeContext` which keeps up to `N` recent samples inline, in a `FixedWindow`.
+///
+/// Like the default `TimeContext`, it doesn't allocate unless observers are registered or the
+/// updates are recorded (see `TimeContext::update_eta`), so it can be used in embedded or realtime
+/// code, e.g. on the stack of an interrupt handler's task.
+pub type FixedTimeContext<T, const N: usize> = TimeContext<T, FixedWindow<N>>;
+
+impl<T: Clone, W: Clone> Clone for TimeContext<T, W> {
+    fn clone(&self) -> TimeContext<T, W> {
+        TimeContext {
+            timefunc: self.timefunc.clone(),
+            curspeed: self.curspeed,
+            remaining: self.remaining,
+            origin: self.origin,
+            cur: self.cur,
+            max: self.max,
+            samples: self.samples.clone(),
+            observers: Vec::new(),
+            stall_timeout: self.stall_timeout,
+            monotone: self.monotone,
+            lts: self.lts,
+            stalled: self.stalled,
+            status: self.status,
+            shift: self.shift,
+            history: self.history.clone(),
+            decimator: self.decimator,
+            discard: self.discard,
+            anchor: self.anchor,
+            called: self.called,
+            epsilon: self.epsilon,
+            transfer: self.transfer,
+            known: self.known,
+            cap: self.cap,
+            calibration: self.calibration,
+            recording: self.recording.clone(),
+            quantiles: self.quantiles,
+            histogram: self.histogram.clone(),
+            stall_log: self.stall_log.clone(),
+            idle: self.idle,
+            bias: self.bias,
+            deadline: self.deadline,
+            missed: self.missed,
+            floor: self.floor,
+            schedule: self.schedule,
+            curve: self.curve.clone(),
+            finished_at: self.finished_at,
+            predicted_end: self.predicted_end,
+            items: self.items,
+        }
+    }
+}
+
+/// Creates a `TimeContext` using the default value of its `GetTimestamp` struct, e.g.
+/// `let ctx: TimeContext<SystemTimer> = TimeContext::default();`.
+impl<T: GetTimestamp + Default> Default for TimeContext<T> {
+    fn default() -> TimeContext<T> {
+        TimeContext::new(T::default())
+    }
 }
 
+// The `GetTimestamp` struct and observers are not shown, so `T` doesn't need to be `Debug`.
+impl<T: GetTimestamp, W: Window + fmt::Debug> fmt::Debug for TimeContext<T, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TimeContext")
+            .field("first_timestamp", &self.origin.map(|o| o.timestamp))
+            .field("first_progress", &self.origin.map(|o| o.current))
+            .field("last_timestamp", &self.lts)
+            .field("progress", &self.cur)
+            .field("total", &self.max)
+            .field("speed", &self.curspeed)
+            .field("remaining", &self.remaining)
+            .field("status", &self.status)
+            .field("stalled", &self.stalled)
+            .field("stall_timeout", &self.stall_timeout)
+            .field("monotone_margin", &self.monotone)
+            .field("observers", &self.observers.len())
+            .field("samples", &self.samples)
+            .finish()
+    }
+}
+
+// Compile-time check of the thread safety promised in the documentation of `TimeContext`.
+#[cfg(feature = "std")]
+#[allow(dead_code)]
+fn assert_thread_safety() {
+    fn is_send_sync<S: Send + Sync>() {}
+
+    is_send_sync::<TimeContext<TestTimer>>();
+    is_send_sync::<Snapshot>();
+    is_send_sync::<EtaHandle<TestTimer>>();
+}
 
 /// A trait that describes a testing callback mechanism for `gaeta` to read a value that symbolizes
 /// passing time.
 ///
 /// It is used in unit testing.
-#[stable]
-#[deriving(Copy)]
+#[derive(Copy, Clone, Debug)]
 pub struct TestTimer {
     cur_ts: u64,
 }
 
 // For testing purposes.
 
-#[stable]
 impl TestTimer {
     /// Creates a new instance of this struct.
-    #[stable]
     pub fn new() -> TestTimer {
         TestTimer {
             cur_ts: 0u64,
@@ -206,16 +1324,20 @@ impl TestTimer {
     }
 
     /// Sets the value to be returned by `get_timestamp` method.
-    #[stable]
     pub fn set_timestamp(&mut self, ts: u64) {
         self.cur_ts = ts;
     }
 }
 
-#[stable]
 impl GetTimestamp for TestTimer {
     /// Returns the value that was set by `set_timestamp` method.
     fn get_timestamp(&self) -> u64 {
         self.cur_ts
     }
 }
+
+impl Default for TestTimer {
+    fn default() -> TestTimer {
+        TestTimer::new()
+    }
+}
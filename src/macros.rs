@@ -0,0 +1,42 @@
+/// Runs a loop over a collection (or a range) while updating a `TimeContext`.
+///
+/// The collection must provide an exact size (i.e. its iterator must implement
+/// `ExactSizeIterator`), which is used as the maximum progress value. The context is updated at
+/// the start of an iteration, at most 1000 times per loop, and once more after the loop ends.
+/// It's available inside the body, so it can be used for rendering.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{eta_for, SystemTimer, TimeContext};
+///   # fn process(_file: &str) {}
+///   # let files = ["a", "b", "c"];
+///   let mut ctx = TimeContext::new(SystemTimer::new());
+///
+///   eta_for!(ctx, file in files.iter() => {
+///       process(file);
+///       println!("{} remaining", ctx.get_remaining_time());
+///   });
+/// ```
+///
+/// `break` and `continue` work inside the body as in a regular `for` loop.
+#[macro_export]
+macro_rules! eta_for {
+    ($ctx:ident, $pat:pat in $iter:expr => $body:block) => {{
+        let iter = $crate::__private::IntoIterator::into_iter($iter);
+        let total = $crate::__private::ExactSizeIterator::len(&iter) as u64;
+        let step = if total / 1000 > 0 { total / 1000 } else { 1u64 };
+        let mut started = 0u64;
+
+        for $pat in iter {
+            if started % step == 0 {
+                $ctx.update_eta(started, total);
+            }
+            started += 1;
+
+            $body
+        }
+
+        $ctx.update_eta(started, total);
+    }};
+}
@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+
+use crate::{Observer, Snapshot};
+
+/// An `Observer` which invokes a callback once when the progress crosses configured thresholds.
+///
+/// The callback receives the crossed threshold (in percent) and the snapshot of the update which
+/// crossed it. If a single update crosses several thresholds, the callback is invoked for each of
+/// them, in ascending order.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{Milestones, SystemTimer, TimeContext};
+///   # fn start_prefetch() {}
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   ctx.add_observer(Box::new(Milestones::every(10.0, |percent, _| {
+///       println!("checkpoint: {}%", percent);
+///   })));
+///
+///   ctx.add_observer(Box::new(Milestones::at(&[50.0, 90.0], |percent, _| {
+///       if percent >= 90.0 { start_prefetch(); }
+///   })));
+/// ```
+pub struct Milestones<F> {
+    thresholds: Vec<f64>, // Sorted thresholds, in percent.
+    next: usize,          // Index of the first threshold which wasn't crossed yet.
+    callback: F,
+}
+
+impl<F: FnMut(f64, &Snapshot)> Milestones<F> {
+    /// Creates milestones at the given thresholds, in percent.
+    pub fn at(thresholds: &[f64], callback: F) -> Milestones<F> {
+        let mut thresholds: Vec<f64> = thresholds.iter().copied()
+            .filter(|t| !t.is_nan())
+            .collect();
+
+        thresholds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        thresholds.dedup();
+
+        Milestones {
+            thresholds,
+            next: 0,
+            callback,
+        }
+    }
+
+    /// Creates milestones at every multiple of `step` percent, up to and including 100%.
+    pub fn every(step: f64, callback: F) -> Milestones<F> {
+        let mut thresholds = Vec::new();
+
+        if step > 0f64 {
+            let mut i = 1u64;
+            while step * i as f64 <= 100.0f64 {
+                thresholds.push(step * i as f64);
+                i += 1;
+            }
+        }
+
+        Milestones::at(&thresholds, callback)
+    }
+}
+
+impl<F: FnMut(f64, &Snapshot)> Observer for Milestones<F> {
+    fn on_progress(&mut self, snapshot: &Snapshot) {
+        while self.next < self.thresholds.len() && snapshot.progress >= self.thresholds[self.next] {
+            (self.callback)(self.thresholds[self.next], snapshot);
+            self.next += 1;
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.next >= self.thresholds.len()
+    }
+}
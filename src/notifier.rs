@@ -0,0 +1,81 @@
+use crate::{Observer, Snapshot};
+
+/// An `Observer` which invokes a callback only when the ETA changes noticeably.
+///
+/// The callback receives the remaining time and the snapshot which triggered it. It's invoked for
+/// the first accepted sample, and then only when the remaining time differs from the last
+/// notified one by more than `min_change`, or when it crosses a boundary set with
+/// `set_boundary` (e.g. a minute). This keeps notification systems like desktop toasts or chat
+/// bots from being spammed by every sample.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{EtaNotifier, SystemTimer, TimeContext};
+///   # fn send_toast(_text: String) {}
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   // Assuming a millisecond clock: notify when the ETA changes by more than 30 seconds, or
+///   // when the displayed number of minutes changes.
+///   let mut notifier = EtaNotifier::new(30_000, |remaining, _| {
+///       send_toast(format!("{} minutes left", remaining / 60_000));
+///   });
+///   notifier.set_boundary(60_000);
+///
+///   ctx.add_observer(Box::new(notifier));
+/// ```
+pub struct EtaNotifier<F> {
+    min_change: u64,      // Minimal change of the ETA which triggers a notification.
+    boundary: Option<u64>,
+                          // Crossing a multiple of this value triggers a notification.
+    last: Option<i64>,    // Last notified remaining time.
+    callback: F,
+}
+
+impl<F: FnMut(i64, &Snapshot)> EtaNotifier<F> {
+    /// Creates a notifier which fires when the ETA changes by more than `min_change` units of
+    /// time.
+    pub fn new(min_change: u64, callback: F) -> EtaNotifier<F> {
+        EtaNotifier {
+            min_change,
+            boundary: None,
+            last: None,
+            callback,
+        }
+    }
+
+    /// Makes the notifier also fire whenever the ETA crosses a multiple of `boundary` units of
+    /// time, regardless of the size of the change.
+    pub fn set_boundary(&mut self, boundary: u64) {
+        self.boundary = if boundary > 0 { Some(boundary) } else { None };
+    }
+
+    fn should_fire(&self, remaining: i64) -> bool {
+        let last = match self.last {
+            None => return true,
+            Some(last) => last,
+        };
+
+        let change = if remaining > last {
+            remaining.saturating_sub(last)
+        } else {
+            last.saturating_sub(remaining)
+        };
+        if change as u64 > self.min_change {
+            return true;
+        }
+
+        match self.boundary {
+            Some(b) => remaining / b as i64 != last / b as i64,
+            None => false,
+        }
+    }
+}
+
+impl<F: FnMut(i64, &Snapshot)> Observer for EtaNotifier<F> {
+    fn on_progress(&mut self, snapshot: &Snapshot) {
+        if self.should_fire(snapshot.remaining) {
+            self.last = Some(snapshot.remaining);
+            (self.callback)(snapshot.remaining, snapshot);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+use crate::GetProgress;
+
+// A counter of one worker, on its own cache line, so workers don't slow each other down.
+#[repr(align(64))]
+struct Slot {
+    count: AtomicU64,     // Progress made by all workers which used this slot.
+    used: AtomicBool,     // A worker holds this slot.
+}
+
+/// A progress counter for work-stealing executors, where workers join and leave dynamically.
+///
+/// Every worker holds a `Shard` with its own counter, so reporting progress never contends with
+/// other workers, even for tiny items. The overall progress is the sum of the counters of all
+/// workers which ever reported, including the ones which left, so it never goes backwards when
+/// the parallelism changes. A `TimeContext` sampling the pool (with `poll_update`) therefore
+/// keeps an overall rate which is correct whichever workers are currently active.
+///
+/// At most `N` workers can hold a shard at the same time; a slot is reused when its worker
+/// leaves.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{Shard, SystemTimer, TimeContext, WorkerPool};
+///   # use std::sync::{Arc, Mutex};
+///   # struct Queue(Mutex<Vec<u32>>);
+///   # impl Queue {
+///   #     fn steal(&self) -> Option<u32> { self.0.lock().unwrap().pop() }
+///   # }
+///   # fn run(_task: u32) {}
+///   # let tasks = vec![1u32, 2, 3];
+///   # let queue = Queue(Mutex::new(tasks.clone()));
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   let pool = Arc::new(WorkerPool::<64>::new(tasks.len() as u64));
+///
+///   // In every worker, for as long as it runs:
+///   let shard = Shard::new(pool.clone()).expect("too many workers");
+///   while let Some(task) = queue.steal() {
+///       run(task);
+///       shard.inc(1);
+///   }
+///
+///   // In the rendering thread:
+///   ctx.poll_update(&pool);
+///   println!("{} workers, {} remaining", pool.get_active_workers(), ctx.get_remaining_time());
+/// ```
+pub struct WorkerPool<const N: usize = 64> {
+    slots: [Slot; N],
+    max: AtomicU64,       // Maximum progress value.
+    active: AtomicUsize,  // Number of shards currently held.
+}
+
+impl<const N: usize> WorkerPool<N> {
+    /// Creates a pool without workers, which reaches 100% when the sum of the progress of its
+    /// workers gets to `max_prog`.
+    pub fn new(max_prog: u64) -> WorkerPool<N> {
+        WorkerPool {
+            slots: core::array::from_fn(|_| Slot {
+                count: AtomicU64::new(0u64),
+                used: AtomicBool::new(false),
+            }),
+            max: AtomicU64::new(max_prog),
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    /// Adds a worker to the pool, returning its shard, or `None` if `N` workers are already
+    /// active. To move the shard into a thread which outlives the pool's borrow, create it with
+    /// `Shard::new` from an `Arc<WorkerPool>` instead.
+    pub fn join(&self) -> Option<Shard<&WorkerPool<N>, N>> {
+        Shard::new(self)
+    }
+
+    /// Changes the value that symbolizes 100%, e.g. when stolen tasks spawn new ones.
+    pub fn set_max(&self, max_prog: u64) {
+        self.max.store(max_prog, Ordering::Relaxed);
+    }
+
+    /// Returns the number of workers which currently hold a shard.
+    pub fn get_active_workers(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Returns the current `(cur_prog, max_prog)` pair, where `cur_prog` is the progress of all
+    /// workers, including the ones which left.
+    pub fn get(&self) -> (u64, u64) {
+        let cur = self.slots.iter()
+            .fold(0u64, |sum, slot| sum.saturating_add(slot.count.load(Ordering::Relaxed)));
+
+        (cur, self.max.load(Ordering::Relaxed))
+    }
+
+    // Claims a free slot.
+    fn claim(&self) -> Option<usize> {
+        let index = self.slots.iter().position(|slot| {
+            slot.used.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok()
+        })?;
+
+        self.active.fetch_add(1, Ordering::Relaxed);
+        Some(index)
+    }
+
+    fn release(&self, index: usize) {
+        self.active.fetch_sub(1, Ordering::Relaxed);
+        self.slots[index].used.store(false, Ordering::Release);
+    }
+}
+
+/// The share of one worker in a `WorkerPool`.
+///
+/// The worker leaves the pool when the shard is dropped; its progress stays counted.
+pub struct Shard<P: Borrow<WorkerPool<N>>, const N: usize = 64> {
+    pool: P,
+    index: usize,         // Index of the slot of this worker.
+}
+
+impl<P: Borrow<WorkerPool<N>>, const N: usize> Shard<P, N> {
+    /// Adds a worker to `pool`, which can be a reference or an `Arc`. Returns `None` if `N`
+    /// workers are already active.
+    pub fn new(pool: P) -> Option<Shard<P, N>> {
+        let index = pool.borrow().claim()?;
+        Some(Shard { pool, index })
+    }
+
+    /// Advances the progress of this worker by `n` units.
+    pub fn inc(&self, n: u64) {
+        self.pool.borrow().slots[self.index].count.fetch_add(n, Ordering::Relaxed);
+    }
+}
+
+impl<P: Borrow<WorkerPool<N>>, const N: usize> Drop for Shard<P, N> {
+    fn drop(&mut self) {
+        self.pool.borrow().release(self.index);
+    }
+}
+
+impl<const N: usize> GetProgress for WorkerPool<N> {
+    fn get_progress(&self) -> (u64, u64) {
+        self.get()
+    }
+}
+
+impl<const N: usize> GetProgress for Arc<WorkerPool<N>> {
+    fn get_progress(&self) -> (u64, u64) {
+        self.get()
+    }
+}
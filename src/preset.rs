@@ -0,0 +1,63 @@
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// A bundle of settings tuned for a common kind of job.
+///
+/// A preset is applied with `TimeContext::apply_preset`. The size of the window is part of the
+/// type of the context, so it can't be changed by a preset; `get_window_size` tells the size the
+/// preset was tuned for, to be used with `FixedTimeContext`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Preset {
+    /// Network transfers: the speed fluctuates with the network, and the first interval includes
+    /// connecting. A long window, the first interval discarded, small increases of the remaining
+    /// time absorbed, and a stall reported after 5 seconds without data.
+    Download,
+
+    /// Computations with a steady speed: a short window which follows changes quickly, and a
+    /// stall reported after 30 seconds without progress.
+    CpuBound,
+
+    /// Jobs which progress in bursts separated by pauses, like batch uploads or flushes: the
+    /// longest window, larger increases of the remaining time absorbed, and a stall reported only
+    /// after a minute without progress.
+    Bursty,
+}
+
+impl Preset {
+    /// Returns the number of samples the preset was tuned for.
+    pub fn get_window_size(&self) -> usize {
+        match *self {
+            Preset::Download => 20,
+            Preset::CpuBound => 10,
+            Preset::Bursty => 40,
+        }
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Applies the settings of `preset`, replacing the stall timeout, the monotone margin and the
+    /// number of discarded intervals. `units_per_second` is the number of units of time of the
+    /// `GetTimestamp` struct in a second (e.g. 1000 for `SystemTimer`), as the presets are
+    /// defined in seconds. It should be called before the first update.
+    pub fn apply_preset(&mut self, preset: Preset, units_per_second: u64) {
+        let seconds = |n: u64| n.saturating_mul(units_per_second);
+
+        match preset {
+            Preset::Download => {
+                self.stall_timeout = Some(seconds(5));
+                self.monotone = Some(seconds(2));
+                self.discard = 1;
+            }
+            Preset::CpuBound => {
+                self.stall_timeout = Some(seconds(30));
+                self.monotone = None;
+                self.discard = 0;
+            }
+            Preset::Bursty => {
+                self.stall_timeout = Some(seconds(60));
+                self.monotone = Some(seconds(10));
+                self.discard = 0;
+            }
+        }
+    }
+}
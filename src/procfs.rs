@@ -0,0 +1,95 @@
+use std::cell::Cell;
+use std::format;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::GetProgress;
+
+/// A `GetProgress` source which watches a file descriptor of another process, on Linux.
+///
+/// The current position is read from `/proc/<pid>/fdinfo/<fd>`, and the size from the metadata
+/// of the file the descriptor points to (like `fstat`), so a `TimeContext` can estimate how long
+/// someone else's `cp` or `dd` will take, without any cooperation from it. Reading another user's
+/// process requires the same permissions as `ptrace`.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{FdProgress, SystemTimer, TimeContext};
+///   # use std::thread;
+///   # use std::time::Duration;
+///   # fn main() -> std::io::Result<()> {
+///   # let pid = std::process::id();
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   // Find the descriptor with `ls -l /proc/<pid>/fd` first.
+///   let source = FdProgress::new(pid, 3)?;
+///
+///   while !source.is_closed() {
+///       ctx.poll_update(&source);
+///       println!("{} remaining", ctx.get_remaining_time());
+///       thread::sleep(Duration::from_secs(1));
+///   }
+///   # Ok(())
+///   # }
+/// ```
+pub struct FdProgress {
+    fdinfo: PathBuf,      // `/proc/<pid>/fdinfo/<fd>`.
+    fd: PathBuf,          // `/proc/<pid>/fd/<fd>`.
+    last: Cell<(u64, u64)>,
+                          // The last position and size which could be read.
+    closed: Cell<bool>,   // The descriptor or the process is gone.
+}
+
+impl FdProgress {
+    /// Starts watching the descriptor `fd` of the process `pid`. Fails if it can't be read, e.g.
+    /// because the process doesn't exist, or because of missing permissions.
+    pub fn new(pid: u32, fd: u32) -> io::Result<FdProgress> {
+        let source = FdProgress {
+            fdinfo: PathBuf::from(format!("/proc/{}/fdinfo/{}", pid, fd)),
+            fd: PathBuf::from(format!("/proc/{}/fd/{}", pid, fd)),
+            last: Cell::new((0u64, 0u64)),
+            closed: Cell::new(false),
+        };
+
+        let progress = source.read()?;
+        source.last.set(progress);
+        Ok(source)
+    }
+
+    /// Reads the current position and size of the watched file.
+    ///
+    /// The size is 0 for descriptors which don't have one, like pipes. Block devices (e.g. the
+    /// input of `dd`) report their size through `ioctl` only, which can't be done from here.
+    pub fn read(&self) -> io::Result<(u64, u64)> {
+        let info = fs::read_to_string(&self.fdinfo)?;
+        let pos = info.lines()
+            .find_map(|line| line.strip_prefix("pos:"))
+            .and_then(|pos| pos.trim().parse::<u64>().ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no position in fdinfo"))?;
+
+        let size = fs::metadata(&self.fd)?.len();
+        Ok((pos, size))
+    }
+
+    /// Returns true if the last read failed, because the descriptor was closed or the process
+    /// exited. It usually means that the operation is finished.
+    pub fn is_closed(&self) -> bool { self.closed.get() }
+}
+
+impl GetProgress for FdProgress {
+    // If the descriptor can't be read anymore, the last read values are returned, so the context
+    // keeps its estimate.
+    fn get_progress(&self) -> (u64, u64) {
+        match self.read() {
+            Ok(progress) => {
+                self.last.set(progress);
+                progress
+            }
+            Err(_) => {
+                self.closed.set(true);
+                self.last.get()
+            }
+        }
+    }
+}
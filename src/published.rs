@@ -0,0 +1,126 @@
+use core::hint;
+use core::sync::atomic::{fence, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+
+use crate::{Snapshot, Status};
+
+/// The latest `Snapshot` of a `TimeContext`, published by the worker thread for render threads.
+///
+/// Readers never block the worker, and never see a snapshot which was half-written: `load` just
+/// retries when it overlaps with `publish` (like a seqlock). Publishing is cheap, a handful of
+/// atomic stores, so it can be done after every update. It's a `const fn`, so the cell can be
+/// placed in a `static`.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{SnapshotCell, SystemTimer, TimeContext};
+///   # fn draw_progress_bar(_progress: f64) {}
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   # let (done, total) = (10, 100);
+///   static LATEST: SnapshotCell = SnapshotCell::new();
+///
+///   // In the worker thread:
+///   ctx.update_eta(done, total);
+///   LATEST.publish(ctx.snapshot());
+///
+///   // In the render thread, every frame:
+///   draw_progress_bar(LATEST.load().progress);
+/// ```
+pub struct SnapshotCell {
+    seq: AtomicUsize,     // Odd while a snapshot is being written.
+    progress: AtomicU64,  // Bits of the `f64` progress.
+    speed: AtomicU64,     // Bits of the `f64` speed.
+    remaining: AtomicU64,
+    status: AtomicU8,
+}
+
+impl SnapshotCell {
+    /// Creates a cell holding the snapshot of a context which wasn't updated yet.
+    pub const fn new() -> SnapshotCell {
+        SnapshotCell {
+            seq: AtomicUsize::new(0),
+            progress: AtomicU64::new(0u64),
+            speed: AtomicU64::new(0u64),
+            remaining: AtomicU64::new(0u64),
+            status: AtomicU8::new(0),
+        }
+    }
+
+    /// Replaces the published snapshot with `snapshot`.
+    ///
+    /// Publishing from several threads at once is safe, but they're serialized: a thread which
+    /// publishes while another one does spins until it's done.
+    pub fn publish(&self, snapshot: Snapshot) {
+        let mut seq = self.seq.load(Ordering::Relaxed);
+
+        loop {
+            if seq % 2 == 1 {
+                hint::spin_loop();
+                seq = self.seq.load(Ordering::Relaxed);
+                continue;
+            }
+
+            match self.seq.compare_exchange_weak(seq, seq.wrapping_add(1), Ordering::Acquire,
+                                                 Ordering::Relaxed) {
+                Ok(_) => break,
+                Err(current) => seq = current,
+            }
+        }
+
+        // Readers which see any of the stores below also see the odd sequence number.
+        fence(Ordering::Release);
+
+        self.progress.store(snapshot.progress.to_bits(), Ordering::Relaxed);
+        self.speed.store(snapshot.speed.to_bits(), Ordering::Relaxed);
+        self.remaining.store(snapshot.remaining as u64, Ordering::Relaxed);
+        self.status.store(encode_status(snapshot.status), Ordering::Relaxed);
+
+        self.seq.store(seq.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Returns the latest published snapshot.
+    pub fn load(&self) -> Snapshot {
+        loop {
+            let before = self.seq.load(Ordering::Acquire);
+
+            if before % 2 == 1 {
+                hint::spin_loop();
+                continue;
+            }
+
+            let snapshot = Snapshot {
+                progress: f64::from_bits(self.progress.load(Ordering::Relaxed)),
+                speed: f64::from_bits(self.speed.load(Ordering::Relaxed)),
+                remaining: self.remaining.load(Ordering::Relaxed) as i64,
+                status: decode_status(self.status.load(Ordering::Relaxed)),
+            };
+
+            // The loads above can't be moved past the check of the sequence number.
+            fence(Ordering::Acquire);
+
+            if self.seq.load(Ordering::Relaxed) == before {
+                return snapshot;
+            }
+        }
+    }
+}
+
+impl Default for SnapshotCell {
+    fn default() -> SnapshotCell { SnapshotCell::new() }
+}
+
+fn encode_status(status: Status) -> u8 {
+    match status {
+        Status::Running => 0,
+        Status::Finished => 1,
+        Status::Cancelled => 2,
+    }
+}
+
+fn decode_status(status: u8) -> Status {
+    match status {
+        1 => Status::Finished,
+        2 => Status::Cancelled,
+        _ => Status::Running,
+    }
+}
@@ -0,0 +1,178 @@
+use core::cmp::Ordering;
+
+use crate::{GetTimestamp, TimeContext, Window};
+
+// A streaming estimator of one quantile, with the P² algorithm (Jain and Chlamtac, 1985): five
+// markers track the minimum, the maximum, the quantile and two points halfway to it, and are moved
+// with a piecewise-parabolic interpolation as observations arrive. It uses constant memory, and
+// the first five observations are kept exactly.
+#[derive(Copy, Clone, Debug)]
+struct Estimator {
+    quantile: f64,
+    heights: [f64; 5],    // Values at the markers.
+    positions: [f64; 5],  // Actual positions of the markers, 1-based.
+    desired: [f64; 5],    // Desired positions of the markers.
+    count: u64,           // Number of observations.
+}
+
+impl Estimator {
+    fn new(quantile: f64) -> Estimator {
+        Estimator {
+            quantile,
+            heights: [0f64; 5],
+            positions: [1f64, 2f64, 3f64, 4f64, 5f64],
+            desired: [1f64, 1f64 + 2f64 * quantile, 1f64 + 4f64 * quantile, 3f64 + 2f64 * quantile,
+                      5f64],
+            count: 0u64,
+        }
+    }
+
+    fn increments(&self) -> [f64; 5] {
+        let p = self.quantile;
+        [0f64, p / 2f64, p, (1f64 + p) / 2f64, 1f64]
+    }
+
+    fn add(&mut self, value: f64) {
+        if self.count < 5 {
+            self.heights[self.count as usize] = value;
+            self.count += 1;
+
+            if self.count == 5 {
+                sort(&mut self.heights);
+            }
+
+            return;
+        }
+
+        self.count += 1;
+
+        let cell = if value < self.heights[0] {
+            self.heights[0] = value;
+            0
+        } else if value >= self.heights[4] {
+            self.heights[4] = value;
+            3
+        } else {
+            (1..5).find(|&i| value < self.heights[i]).unwrap_or(4) - 1
+        };
+
+        for position in self.positions[cell + 1..].iter_mut() {
+            *position += 1f64;
+        }
+
+        let increments = self.increments();
+
+        for (desired, increment) in self.desired.iter_mut().zip(increments.iter()) {
+            *desired += increment;
+        }
+
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            let ahead = self.positions[i + 1] - self.positions[i] > 1f64;
+            let behind = self.positions[i - 1] - self.positions[i] < -1f64;
+
+            if (offset >= 1f64 && ahead) || (offset <= -1f64 && behind) {
+                let step = if offset > 0f64 { 1f64 } else { -1f64 };
+                let height = self.parabolic(i, step);
+
+                self.heights[i] = if self.heights[i - 1] < height && height < self.heights[i + 1] {
+                    height
+                } else {
+                    self.linear(i, step)
+                };
+
+                self.positions[i] += step;
+            }
+        }
+    }
+
+    fn parabolic(&self, i: usize, step: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+
+        q[i] + step / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + step) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+               + (n[i + 1] - n[i] - step) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, step: f64) -> f64 {
+        let j = if step > 0f64 { i + 1 } else { i - 1 };
+        let (q, n) = (&self.heights, &self.positions);
+
+        q[i] + step * (q[j] - q[i]) / (n[j] - n[i])
+    }
+
+    fn get(&self) -> f64 {
+        match self.count {
+            0 => 0f64,
+            1..=4 => {
+                let mut values = [0f64; 5];
+                let values = &mut values[..self.count as usize];
+                values.copy_from_slice(&self.heights[..self.count as usize]);
+                sort(values);
+
+                let index = self.quantile * (self.count - 1) as f64 + 0.5f64;
+                values[index as usize]
+            }
+            _ => self.heights[2],
+        }
+    }
+}
+
+fn sort(values: &mut [f64]) {
+    values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+}
+
+/// Percentiles of the speed over a whole run: the median, the 95th and the 99th percentile.
+///
+/// The speed of every interval between two accepted samples is fed to streaming quantile
+/// estimators, which use a small constant amount of memory no matter how long the run is, so a
+/// post-run report can tell how much the speed varied, not only its average. The values are
+/// estimates; they're exact for up to five intervals.
+///
+/// It's enabled with `TimeContext::enable_rate_quantiles`.
+#[derive(Copy, Clone, Debug)]
+pub struct RateQuantiles {
+    p50: Estimator,
+    p95: Estimator,
+    p99: Estimator,
+}
+
+impl RateQuantiles {
+    fn new() -> RateQuantiles {
+        RateQuantiles {
+            p50: Estimator::new(0.5f64),
+            p95: Estimator::new(0.95f64),
+            p99: Estimator::new(0.99f64),
+        }
+    }
+
+    // Called by `TimeContext` with the speed of every accepted interval.
+    pub(crate) fn record(&mut self, speed: f64) {
+        self.p50.add(speed);
+        self.p95.add(speed);
+        self.p99.add(speed);
+    }
+
+    /// Returns the number of intervals measured.
+    pub fn get_count(&self) -> u64 { self.p50.count }
+
+    /// Returns the median speed, in work units per unit of time, or 0 before any interval was
+    /// measured.
+    pub fn get_p50(&self) -> f64 { self.p50.get() }
+
+    /// Returns the speed which 95% of the intervals were slower than or as fast as.
+    pub fn get_p95(&self) -> f64 { self.p95.get() }
+
+    /// Returns the speed which 99% of the intervals were slower than or as fast as.
+    pub fn get_p99(&self) -> f64 { self.p99.get() }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Starts tracking the percentiles of the speed over the whole run. See `RateQuantiles`.
+    pub fn enable_rate_quantiles(&mut self) {
+        self.quantiles = Some(RateQuantiles::new());
+    }
+
+    /// Returns the percentiles of the speed, if they were enabled with `enable_rate_quantiles`.
+    pub fn get_rate_quantiles(&self) -> Option<&RateQuantiles> { self.quantiles.as_ref() }
+}
@@ -0,0 +1,185 @@
+use alloc::vec::Vec;
+
+use crate::{GetTimestamp, Status, TimeContext, Window};
+
+// The first byte of an encoded log, changed whenever the encoding changes.
+const FORMAT_VERSION: u8 = 1;
+
+// How the maximum progress value of an entry is encoded.
+const MAX_SAME: u64 = 0;
+const MAX_UNKNOWN: u64 = 1;
+const MAX_EXPLICIT: u64 = 2;
+
+/// One update recorded in a `SessionLog`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LogEntry {
+    /// The time of the update, as used by the context.
+    pub timestamp: u64,
+
+    /// The raw progress value.
+    pub current: u64,
+
+    /// The maximum progress value, or `None` for updates made with `update_count`.
+    pub total: Option<u64>,
+}
+
+impl LogEntry {
+    // Repeats the update on `ctx`.
+    pub(crate) fn feed<T: GetTimestamp, W: Window>(&self, ctx: &mut TimeContext<T, W>) {
+        ctx.known = self.total.is_some();
+        ctx.update_at(self.current, self.total.unwrap_or(0u64), self.timestamp);
+    }
+}
+
+/// A log of all updates fed to a `TimeContext`, which can be replayed through another context.
+///
+/// It's recorded when enabled with `TimeContext::enable_recording`, and it's meant for
+/// reproducing bad estimates: the log of a run where "the ETA was wrong here" can be saved with
+/// `to_bytes`, attached to a bug report, and replayed with `replay` through any configuration of
+/// the estimator. The encoding stores differences between consecutive entries as variable-length
+/// integers, so a log takes a few bytes per update.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SessionLog {
+    entries: Vec<LogEntry>,
+}
+
+impl SessionLog {
+    /// Creates an empty log.
+    pub fn new() -> SessionLog {
+        SessionLog { entries: Vec::new() }
+    }
+
+    /// Appends an update to the log.
+    pub fn push(&mut self, entry: LogEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Returns the recorded updates, from the oldest to the newest.
+    pub fn get_entries(&self) -> &[LogEntry] { &self.entries }
+
+    /// Feeds all recorded updates to `ctx`, with the recorded timestamps, as if they happened
+    /// again. Observers of `ctx` are notified as usual.
+    pub fn replay<T: GetTimestamp, W: Window>(&self, ctx: &mut TimeContext<T, W>) {
+        for entry in self.entries.iter() {
+            if ctx.status == Status::Cancelled {
+                return;
+            }
+
+            entry.feed(ctx);
+        }
+    }
+
+    /// Encodes the log into a compact binary form, which can be decoded with `from_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + self.entries.len() * 4);
+        bytes.push(FORMAT_VERSION);
+        write_varint(&mut bytes, self.entries.len() as u64);
+
+        let mut previous = LogEntry { timestamp: 0u64, current: 0u64, total: Some(0u64) };
+
+        for entry in self.entries.iter() {
+            write_varint(&mut bytes, zigzag(entry.timestamp.wrapping_sub(previous.timestamp)));
+            write_varint(&mut bytes, zigzag(entry.current.wrapping_sub(previous.current)));
+
+            match entry.total {
+                total if total == previous.total => write_varint(&mut bytes, MAX_SAME),
+                None => write_varint(&mut bytes, MAX_UNKNOWN),
+                Some(total) => {
+                    write_varint(&mut bytes, MAX_EXPLICIT);
+                    write_varint(&mut bytes, total);
+                }
+            }
+
+            previous = *entry;
+        }
+
+        bytes
+    }
+
+    /// Decodes a log encoded with `to_bytes`. Returns `None` if `bytes` is not a valid log.
+    pub fn from_bytes(bytes: &[u8]) -> Option<SessionLog> {
+        let (&version, mut rest) = bytes.split_first()?;
+
+        if version != FORMAT_VERSION {
+            return None;
+        }
+
+        let count = read_varint(&mut rest)?;
+        // Every entry takes at least 3 bytes, so a corrupted count can't cause a huge allocation.
+        let mut entries = Vec::with_capacity((count as usize).min(rest.len() / 3));
+        let mut previous = LogEntry { timestamp: 0u64, current: 0u64, total: Some(0u64) };
+
+        for _ in 0..count {
+            let timestamp = previous.timestamp.wrapping_add(unzigzag(read_varint(&mut rest)?));
+            let current = previous.current.wrapping_add(unzigzag(read_varint(&mut rest)?));
+            let total = match read_varint(&mut rest)? {
+                MAX_SAME => previous.total,
+                MAX_UNKNOWN => None,
+                MAX_EXPLICIT => Some(read_varint(&mut rest)?),
+                _ => return None,
+            };
+
+            previous = LogEntry { timestamp, current, total };
+            entries.push(previous);
+        }
+
+        if !rest.is_empty() {
+            return None;
+        }
+
+        Some(SessionLog { entries })
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Starts recording all updates into a `SessionLog`. See `SessionLog`.
+    ///
+    /// The log grows with every update, so unlike the rest of the context, the updating methods
+    /// allocate while it's enabled.
+    pub fn enable_recording(&mut self) {
+        self.recording = Some(SessionLog::new());
+    }
+
+    /// Returns the log of the updates, if it was enabled with `enable_recording`.
+    pub fn get_recording(&self) -> Option<&SessionLog> { self.recording.as_ref() }
+
+    /// Stops recording the updates and returns the log, if it was enabled.
+    pub fn take_recording(&mut self) -> Option<SessionLog> { self.recording.take() }
+}
+
+// Maps a difference, which may be negative in two's complement, to a small number.
+fn zigzag(delta: u64) -> u64 {
+    let delta = delta as i64;
+    ((delta << 1) ^ (delta >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> u64 {
+    ((value >> 1) as i64 ^ -((value & 1) as i64)) as u64
+}
+
+// LEB128: 7 bits per byte, the highest bit set on all bytes but the last.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
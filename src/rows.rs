@@ -0,0 +1,103 @@
+use crate::{GetTimestamp, Status, TimeContext};
+
+/// The progress of a row-based batch job, as reported by `RowProgress::batch`.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RowReport {
+    /// Rows processed so far.
+    pub rows_done: u64,
+
+    /// Total number of rows to process.
+    pub total_rows: u64,
+
+    /// Currently measured speed, in rows per unit of time.
+    pub rows_per_unit: f64,
+
+    /// Remaining time. See `TimeContext::get_remaining_time`.
+    pub remaining: i64,
+
+    /// Status of the job.
+    pub status: Status,
+}
+
+/// A tracker for batch jobs which work on rows, like database migrations or backfills.
+///
+/// The job reports every batch with `batch`, and gets back a `RowReport` to log, which gives the
+/// speed in rows per unit of time rather than in percent, as that's the unit people who run
+/// such jobs think in. A job which counts the processed rows itself can use `set_done` instead.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{RowProgress, SystemTimer};
+///   # struct Table(u64);
+///   # impl Table {
+///   #     fn batches(&self, size: u64) -> impl Iterator<Item = u64> + '_ {
+///   #         (0..self.0).step_by(size as usize).map(move |start| size.min(self.0 - start))
+///   #     }
+///   # }
+///   # fn migrate(batch: &u64) -> std::io::Result<u64> { Ok(*batch) }
+///   # fn main() -> std::io::Result<()> {
+///   # let total_rows = 25_000;
+///   # let table = Table(total_rows);
+///   let mut progress = RowProgress::new(total_rows, SystemTimer::new());
+///
+///   for batch in table.batches(10_000) {
+///       let rows = migrate(&batch)?;
+///       let report = progress.batch(rows);
+///       println!("{}/{} rows, {:.0} rows/s, {} s left", report.rows_done, report.total_rows,
+///                report.rows_per_unit * 1000.0, report.remaining / 1000);
+///   }
+///   # Ok(())
+///   # }
+/// ```
+pub struct RowProgress<T> {
+    ctx: TimeContext<T>,
+    done: u64,            // Rows processed so far.
+    total: u64,           // Total number of rows.
+}
+
+impl<T: GetTimestamp> RowProgress<T> {
+    /// Creates a tracker for a job which processes `total_rows` rows.
+    pub fn new(total_rows: u64, timefunc: T) -> RowProgress<T> {
+        RowProgress {
+            ctx: TimeContext::new(timefunc),
+            done: 0u64,
+            total: total_rows,
+        }
+    }
+
+    /// Records that a batch of `rows` rows was processed, and returns the updated report.
+    pub fn batch(&mut self, rows: u64) -> RowReport {
+        self.set_done(self.done.saturating_add(rows))
+    }
+
+    /// Sets the number of rows processed so far, and returns the updated report.
+    pub fn set_done(&mut self, rows_done: u64) -> RowReport {
+        self.done = rows_done;
+        self.ctx.update_eta(self.done, self.total);
+        self.report()
+    }
+
+    /// Changes the total number of rows, e.g. when the table grows during the migration.
+    pub fn set_total(&mut self, total_rows: u64) {
+        self.total = total_rows;
+    }
+
+    /// Returns the current report, without recording anything.
+    pub fn report(&self) -> RowReport {
+        RowReport {
+            rows_done: self.done,
+            total_rows: self.total,
+            rows_per_unit: self.ctx.calc_throughput(),
+            remaining: self.ctx.get_remaining_time(),
+            status: self.ctx.get_status(),
+        }
+    }
+
+    /// Gets a reference to the underlying `TimeContext`.
+    pub fn get_context(&self) -> &TimeContext<T> { &self.ctx }
+
+    /// Gets a mutable reference to the underlying `TimeContext`, e.g. to add observers.
+    pub fn get_context_mut(&mut self) -> &mut TimeContext<T> { &mut self.ctx }
+}
@@ -0,0 +1,96 @@
+use alloc::vec::Vec;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// A single call of `update_eta` in a `Scenario`, relative to the previous one.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Step {
+    /// Time passed since the previous call.
+    pub elapsed: u64,
+
+    /// Progress made since the previous call, in work units.
+    pub progress: u64,
+}
+
+/// A random sequence of updates of an estimator, for property tests and fuzzing.
+///
+/// It's only available with the `arbitrary` feature. Steps are stored as deltas, so any prefix of
+/// a scenario, and any scenario with smaller values, is valid too: fuzzers and property-testing
+/// tools which shrink the input bytes shrink scenarios to simpler ones as well.
+///
+/// ```rust
+/// # use arbitrary::{Arbitrary, Unstructured};
+/// # use gaeta::{Scenario, TestTimer, TimeContext};
+/// # macro_rules! fuzz_target {
+/// #     (|$input:ident: $ty:ty| $body:block) => {
+/// #         let data = [0x5au8; 256];
+/// #         let $input = <$ty>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+/// #         $body
+/// #     };
+/// # }
+/// fuzz_target!(|scenario: Scenario| {
+///     let mut ctx = TimeContext::new(TestTimer::new());
+///     scenario.replay(&mut ctx, |ctx| assert!(ctx.get_remaining_time() >= 0));
+/// });
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Scenario {
+    total: u64,           // The maximum progress value passed to every call.
+    steps: Vec<Step>,
+}
+
+impl Scenario {
+    /// Creates a scenario from the maximum progress value and a list of steps.
+    pub fn new(total: u64, steps: Vec<Step>) -> Scenario {
+        Scenario {
+            total,
+            steps,
+        }
+    }
+
+    /// Returns the maximum progress value passed to every call.
+    pub fn get_total(&self) -> u64 { self.total }
+
+    /// Returns the steps of the scenario.
+    pub fn get_steps(&self) -> &[Step] { &self.steps }
+
+    /// Feeds the scenario to `ctx` with `update_eta_fast`, starting at time 0 and progress 0, and
+    /// calls `check` after every step. Times and progress values saturate instead of overflowing.
+    pub fn replay<T, W, F>(&self, ctx: &mut TimeContext<T, W>, mut check: F)
+        where T: GetTimestamp, W: Window, F: FnMut(&TimeContext<T, W>)
+    {
+        let mut ts = 0u64;
+        let mut cur = 0u64;
+
+        for step in self.steps.iter() {
+            ts = ts.saturating_add(step.elapsed);
+            cur = cur.saturating_add(step.progress);
+            ctx.update_eta_fast(cur, self.total, ts);
+            check(ctx);
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Step {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Step> {
+        Ok(Step {
+            elapsed: u.arbitrary()?,
+            progress: u.arbitrary()?,
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <(u64, u64) as Arbitrary>::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Scenario {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Scenario> {
+        Ok(Scenario {
+            total: u.arbitrary()?,
+            steps: u.arbitrary()?,
+        })
+    }
+}
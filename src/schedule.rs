@@ -0,0 +1,86 @@
+use crate::{clamp_offset, GetTimestamp, TimeContext, Window, MAX_REMAINING_TIME};
+
+/// A plan an operation is compared with. See `TimeContext::set_schedule`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Schedule {
+    /// The operation should progress evenly from its start to 100% at this time, a timestamp of
+    /// the `GetTimestamp` struct.
+    FinishBy(u64),
+
+    /// The operation should progress at this speed, in work units per unit of time.
+    Rate(f64),
+}
+
+/// How far ahead of or behind its `Schedule` an operation is.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScheduleStatus {
+    /// Time ahead of the schedule, in units of time used by the `GetTimestamp` struct; negative if
+    /// behind. With `FinishBy`, it's the time between the projected completion and the target.
+    /// With `Rate`, it's the time the progress made would take at the target speed minus the
+    /// time it actually took.
+    pub time: i64,
+
+    /// Progress ahead of the schedule, in percentage points of the whole operation; negative if
+    /// behind.
+    pub percent: f64,
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Sets the plan the operation is compared with by `get_schedule_status`.
+    pub fn set_schedule(&mut self, schedule: Schedule) {
+        self.schedule = Some(schedule);
+    }
+
+    /// Returns how far ahead of or behind the schedule set with `set_schedule` the operation is,
+    /// as of the latest update. Returns `None` before the first update, if no schedule is set, or
+    /// if the projected completion time is needed but not known yet.
+    pub fn get_schedule_status(&self) -> Option<ScheduleStatus> {
+        let (schedule, origin, now) = (self.schedule?, self.origin?, self.called?);
+        let elapsed = now.saturating_sub(origin.timestamp);
+
+        match schedule {
+            Schedule::FinishBy(deadline) => {
+                // The target is given on the timeline of the `GetTimestamp` struct, without the
+                // shift.
+                let deadline = deadline.saturating_add(self.shift);
+                let percent = self.get_percent();
+
+                let projected = if percent >= 100.0f64 {
+                    now
+                } else if self.remaining > 0 && self.remaining < MAX_REMAINING_TIME {
+                    now.saturating_add(self.remaining as u64)
+                } else {
+                    return None;
+                };
+
+                let planned = match deadline.saturating_sub(origin.timestamp) {
+                    0 => 100.0f64,
+                    span => (100.0f64 * elapsed as f64 / span as f64).min(100.0f64),
+                };
+
+                Some(ScheduleStatus {
+                    time: clamp_offset(deadline as f64 - projected as f64),
+                    percent: percent - planned,
+                })
+            }
+            Schedule::Rate(rate) if rate > 0f64 => {
+                let done = self.cur.saturating_sub(origin.current) as f64;
+                let ahead = done - rate * elapsed as f64;
+
+                let percent = if self.known && self.max > 0 {
+                    ahead * 100.0f64 / self.max as f64
+                } else {
+                    0f64
+                };
+
+                Some(ScheduleStatus {
+                    time: clamp_offset(ahead / rate),
+                    percent,
+                })
+            }
+            Schedule::Rate(_) => None,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use core::ffi::{c_int, c_long, c_void};
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+
+use crate::GetProgress;
+
+const PROT_READ: c_int = 1;
+const PROT_WRITE: c_int = 2;
+const MAP_SHARED: c_int = 1;
+
+// The segment starts with a tag of the layout, followed by the counters.
+const MAGIC: u64 = u64::from_le_bytes(*b"gaetashm");
+const MAGIC_OFFSET: usize = 0;
+const CUR_OFFSET: usize = 1;
+const MAX_OFFSET: usize = 2;
+const SEGMENT_SIZE: usize = 3 * core::mem::size_of::<u64>();
+
+extern "C" {
+    fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: c_int, offset: c_long)
+        -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+}
+
+/// A progress counter in a memory-mapped file, shared between processes, on Unix.
+///
+/// Worker processes map the same file and advance the counter with a single atomic `fetch_add`,
+/// like an `AtomicProgress`, while the supervising process samples it into its own
+/// `TimeContext` with `poll_update`. Nothing is sent between the processes, so reporting stays
+/// cheap however often the workers do it, and the supervisor decides alone how often to read.
+/// Placing the file on a memory-backed filesystem, like `/dev/shm` on Linux, keeps it off the
+/// disk.
+///
+/// The segment outlives the processes which mapped it; the file is not removed when the counter
+/// is dropped.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{SharedProgress, SystemTimer, TimeContext};
+///   # use std::process::Command;
+///   # use std::thread;
+///   # use std::time::Duration;
+///   # fn process(_file: &str) -> std::io::Result<()> { Ok(()) }
+///   # fn supervisor(files: &[&str]) -> std::io::Result<()> {
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   // In the supervisor:
+///   let progress = SharedProgress::create("/dev/shm/job-42", files.len() as u64)?;
+///   let child = Command::new("worker").arg("/dev/shm/job-42").spawn()?;
+///   loop {
+///       ctx.poll_update(&progress);
+///       println!("{} remaining", ctx.get_remaining_time());
+///       thread::sleep(Duration::from_secs(1));
+///   }
+///   # }
+///   # fn worker(args: &[String], files: &[&str]) -> std::io::Result<()> {
+///
+///   // In the worker:
+///   let progress = SharedProgress::open(&args[1])?;
+///   for file in files.iter() {
+///       process(file)?;
+///       progress.inc(1);
+///   }
+///   # Ok(())
+///   # }
+/// ```
+pub struct SharedProgress {
+    map: *mut AtomicU64,  // The mapped segment.
+    path: PathBuf,
+}
+
+// The segment is only ever accessed through atomics.
+unsafe impl Send for SharedProgress {}
+unsafe impl Sync for SharedProgress {}
+
+impl SharedProgress {
+    /// Creates the segment at `path`, starting at zero, which reaches 100% when it gets to
+    /// `max_prog`. An existing file at `path` is overwritten.
+    pub fn create<P: Into<PathBuf>>(path: P, max_prog: u64) -> io::Result<SharedProgress> {
+        let path = path.into();
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path)?;
+        file.set_len(SEGMENT_SIZE as u64)?;
+
+        let progress = SharedProgress { map: map(&file)?, path };
+        progress.slot(CUR_OFFSET).store(0u64, Ordering::Relaxed);
+        progress.slot(MAX_OFFSET).store(max_prog, Ordering::Relaxed);
+        progress.slot(MAGIC_OFFSET).store(MAGIC, Ordering::Release);
+        Ok(progress)
+    }
+
+    /// Opens the segment created with `create` at `path`, e.g. by another process. Fails if the
+    /// file doesn't exist, or if it isn't such a segment.
+    pub fn open<P: Into<PathBuf>>(path: P) -> io::Result<SharedProgress> {
+        let path = path.into();
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+
+        if file.metadata()?.len() < SEGMENT_SIZE as u64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a progress segment"));
+        }
+
+        let progress = SharedProgress { map: map(&file)?, path };
+        if progress.slot(MAGIC_OFFSET).load(Ordering::Acquire) != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a progress segment"));
+        }
+
+        Ok(progress)
+    }
+
+    /// Advances the counter by `n` units.
+    pub fn inc(&self, n: u64) {
+        self.slot(CUR_OFFSET).fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Sets the counter to `cur_prog`.
+    pub fn set(&self, cur_prog: u64) {
+        self.slot(CUR_OFFSET).store(cur_prog, Ordering::Relaxed);
+    }
+
+    /// Changes the value that symbolizes 100%.
+    pub fn set_max(&self, max_prog: u64) {
+        self.slot(MAX_OFFSET).store(max_prog, Ordering::Relaxed);
+    }
+
+    /// Returns the current `(cur_prog, max_prog)` pair.
+    pub fn get(&self) -> (u64, u64) {
+        (self.slot(CUR_OFFSET).load(Ordering::Relaxed), self.slot(MAX_OFFSET).load(Ordering::Relaxed))
+    }
+
+    /// Returns the path of the file backing the segment.
+    pub fn get_path(&self) -> &Path { &self.path }
+
+    fn slot(&self, offset: usize) -> &AtomicU64 {
+        // The mapping is page-aligned and at least `SEGMENT_SIZE` long, and it lives as long as
+        // `self`.
+        unsafe { &*self.map.add(offset) }
+    }
+}
+
+// Maps the first `SEGMENT_SIZE` bytes of `file`. The mapping stays valid after the file is closed.
+fn map(file: &File) -> io::Result<*mut AtomicU64> {
+    let addr = unsafe {
+        mmap(core::ptr::null_mut(), SEGMENT_SIZE, PROT_READ | PROT_WRITE, MAP_SHARED, file.as_raw_fd(), 0)
+    };
+
+    // `MAP_FAILED` is `(void *) -1`.
+    if addr as usize == usize::MAX {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(addr as *mut AtomicU64)
+}
+
+impl Drop for SharedProgress {
+    fn drop(&mut self) {
+        unsafe { munmap(self.map as *mut c_void, SEGMENT_SIZE); }
+    }
+}
+
+impl GetProgress for SharedProgress {
+    fn get_progress(&self) -> (u64, u64) {
+        self.get()
+    }
+}
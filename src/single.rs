@@ -0,0 +1,124 @@
+use crate::{GetTimestamp, MAX_REMAINING_TIME};
+
+/// An estimator which uses only single-precision floating point arithmetic.
+///
+/// It works like `TimeContext`, but it never touches `f64`, which makes it suitable for 32-bit
+/// microcontrollers with a single-precision FPU (e.g. a Cortex-M4F), where every `f64` operation
+/// is emulated in software. The progress and the speed are in percent, like in `TimeContext`.
+/// See `FixedPointContext` for targets without any FPU.
+///
+/// Up to `N` recent samples are kept inline, so the estimator doesn't allocate either.
+#[derive(Copy, Clone, Debug)]
+pub struct F32Context<T, const N: usize = 10> {
+    timefunc: T,          // An interface that provides current time in some unit.
+    curspeed: f32,        // Current speed per time unit.
+    fts: Option<u64>,     // First timestamp.
+    fprog: Option<f32>,   // First progress.
+    cprog: f32,           // Current progress.
+    samples: [(u64, f32); N],
+                          // Recent (timestamp, progress) pairs, oldest at `head`.
+    head: usize,          // Index of the oldest sample.
+    len: usize,           // Number of stored samples.
+}
+
+impl<T: GetTimestamp, const N: usize> F32Context<T, N> {
+    /// Creates new `F32Context` which will use a user-supplied `GetTimestamp` struct.
+    pub fn new(timefunc: T) -> F32Context<T, N> {
+        F32Context {
+            timefunc,
+            curspeed: 0f32,
+            fts: None,
+            fprog: None,
+            cprog: 0f32,
+            samples: [(0u64, 0f32); N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Updates the state of this `F32Context` instance. See `TimeContext::update_eta`.
+    pub fn update_eta(&mut self, cur_prog: u64, max_prog: u64) {
+        let ts = self.timefunc.get_timestamp();
+        let prog = if max_prog > 0 {
+            let cur = if cur_prog > max_prog { max_prog } else { cur_prog };
+            cur as f32 * 100.0f32 / max_prog as f32
+        } else {
+            100.0f32
+        };
+        self.cprog = prog;
+
+        if self.fts.is_none() { self.fts = Some(ts); }
+        if self.fprog.is_none() { self.fprog = Some(prog); }
+
+        if self.len > 0 && self.samples[(self.head + self.len - 1) % N].1 == prog {
+            return;
+        }
+
+        self.push_sample(ts, prog);
+        self.curspeed = self.measure_speed();
+    }
+
+    fn push_sample(&mut self, ts: u64, prog: f32) {
+        if N == 0 {
+            return;
+        }
+
+        if self.len == N {
+            self.head = (self.head + 1) % N;
+            self.len -= 1;
+        }
+
+        self.samples[(self.head + self.len) % N] = (ts, prog);
+        self.len += 1;
+    }
+
+    fn measure_speed(&self) -> f32 {
+        if self.len == 0 {
+            return 0f32;
+        }
+
+        // Measured over the window, from the oldest to the newest sample, like in `TimeContext`.
+        let (ts, prog) = self.samples[(self.head + self.len - 1) % N];
+        let (from_ts, from_prog) = match self.samples[self.head] {
+            (oldest_ts, oldest_prog) if oldest_ts < ts => (oldest_ts, oldest_prog),
+            _ => (self.fts.unwrap_or(0u64), self.fprog.unwrap_or(0f32)),
+        };
+
+        let elapsed = ts.saturating_sub(from_ts);
+        (prog - from_prog) / if elapsed == 0 { 1 } else { elapsed } as f32
+    }
+
+    /// Returns the current progress, in percent.
+    pub fn get_progress(&self) -> f32 { self.cprog }
+
+    /// Returns the currently measured speed, in percent per unit of time.
+    ///
+    /// The unit of time is the same as chosen by the `GetTimestamp`'s `get_timestamp` method.
+    pub fn calc_speed_per_unit(&self) -> f32 { self.curspeed }
+
+    /// Returns the remaining time (ETA), or 0 if the operation is complete or it can't be
+    /// estimated yet. The result is clamped to `MAX_REMAINING_TIME`, like in `TimeContext`.
+    ///
+    /// The unit of time is the same as chosen by the `GetTimestamp`'s `get_timestamp` method.
+    pub fn get_remaining_time(&self) -> u64 {
+        if self.curspeed.is_nan() || self.curspeed <= 0f32 {
+            return 0u64;
+        }
+
+        let remaining = (100.0f32 - self.cprog) / self.curspeed;
+
+        if remaining >= MAX_REMAINING_TIME as f32 {
+            MAX_REMAINING_TIME as u64
+        } else if remaining > 0f32 {
+            remaining as u64
+        } else {
+            0u64
+        }
+    }
+
+    /// Gets a reference to the underlying `GetTimestamp` struct.
+    pub fn get_timefunc(&self) -> &T { &self.timefunc }
+
+    /// Gets a mutable reference to the underlying `GetTimestamp` struct.
+    pub fn get_timefunc_mut(&mut self) -> &mut T { &mut self.timefunc }
+}
@@ -0,0 +1,86 @@
+use crate::{GetTimestamp, Status, TimeContext, Window, MAX_REMAINING_TIME};
+
+/// A remaining time for per-frame display, which counts down smoothly between the updates of a
+/// `TimeContext`, and eases into every new estimate instead of jumping to it.
+///
+/// Every frame, the displayed value first counts down by the time since the previous frame, and
+/// then moves part of the way towards the latest estimate (also counted down, see
+/// `TimeContext::get_remaining_time_at`): the longer the frame, the larger the part, so the
+/// result barely depends on the frame rate. At typical frame rates, about two thirds of a
+/// difference are corrected within `settle` units of time. The displayed value is reset when the
+/// estimate becomes unavailable or infinite, and when the operation is finished.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{SmoothEta, SystemTimer, TimeContext};
+///   # use std::sync::Mutex;
+///   # fn draw_label(_seconds: i64) {}
+///   # let ctx = Mutex::new(TimeContext::new(SystemTimer::new()));
+///   let mut eta = SmoothEta::new(500);
+///
+///   loop {
+///       // The loader thread updates `ctx` whenever a chunk is loaded.
+///       draw_label(eta.frame(&ctx.lock().unwrap()) / 1000);
+///   #   break;
+///   }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct SmoothEta {
+    settle: u64,          // Time needed to mostly correct a difference.
+    shown: Option<f64>,   // The displayed remaining time.
+    last: Option<u64>,    // Timestamp of the previous frame.
+}
+
+impl SmoothEta {
+    /// Creates a smoother which corrects most of a difference within `settle` units of time.
+    pub fn new(settle: u64) -> SmoothEta {
+        SmoothEta {
+            settle,
+            shown: None,
+            last: None,
+        }
+    }
+
+    /// Returns the remaining time to display in the current frame, reading the current time
+    /// from the `GetTimestamp` struct of `ctx`.
+    pub fn frame<T: GetTimestamp, W: Window>(&mut self, ctx: &TimeContext<T, W>) -> i64 {
+        let now = ctx.get_timefunc().get_timestamp();
+        self.frame_at(ctx, now)
+    }
+
+    /// Returns the remaining time to display in the frame drawn at `now`, e.g. the frame time
+    /// known to a game loop.
+    pub fn frame_at<T: GetTimestamp, W: Window>(&mut self, ctx: &TimeContext<T, W>,
+                                                now: u64) -> i64 {
+        let target = ctx.get_remaining_time_at(now);
+        let elapsed = match self.last {
+            Some(last) => now.saturating_sub(last),
+            None => 0u64,
+        };
+
+        self.last = Some(now);
+
+        if target == 0 || target >= MAX_REMAINING_TIME || ctx.get_status() != Status::Running {
+            self.shown = None;
+            return target;
+        }
+
+        let shown = match self.shown {
+            Some(shown) => {
+                let counted = shown - elapsed as f64;
+                // The fraction of the difference corrected in this frame. It's close to the
+                // exponential decay for short frames, without needing `exp`, which isn't
+                // available under `no_std`.
+                let t = elapsed as f64 / self.settle.max(1) as f64;
+                let fraction = t / (1.0f64 + t);
+                counted + (target as f64 - counted) * fraction
+            }
+            None => target as f64,
+        };
+
+        let shown = if shown > 0f64 { shown } else { 0f64 };
+        self.shown = Some(shown);
+        shown as i64
+    }
+}
@@ -0,0 +1,368 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, BufReader, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::{clamp_time, GetTimestamp, TimeContext};
+
+struct Stage {
+    label: String,
+    weight: f64,          // Relative cost of this stage.
+    expected: Option<u64>,
+                          // Expected duration, e.g. taken from a previous run.
+    started: Option<u64>, // Timestamp of the start of this stage.
+    duration: Option<u64>,
+                          // Measured duration, known after the stage is finished.
+}
+
+/// An ordered list of phases (stages) of one job.
+///
+/// Every stage has a relative weight. Only the running stage is measured; the remaining time of
+/// stages which weren't started yet is estimated from their expected durations, if they were
+/// set with `set_expected_duration`, or from the time per unit of weight measured in the stages
+/// which already ran.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{Stages, SystemTimer};
+///   # let (cur, max) = (10, 100);
+///   let mut job = Stages::new(SystemTimer::new());
+///   job.add_stage("download", 5.0);
+///   job.add_stage("extract", 2.0);
+///   job.add_stage("configure", 1.0);
+///
+///   job.next_stage();                 // Starts "download".
+///   job.update_eta(cur, max);         // Progress of "download".
+///   job.next_stage();                 // Finishes "download", starts "extract".
+/// ```
+pub struct Stages<T> {
+    timefunc: T,
+    stages: Vec<Stage>,
+    current: Option<usize>,
+    ctx: TimeContext<T>,  // Measures the current stage.
+    cprog: f64,           // Progress of the current stage, in percent.
+}
+
+impl<T: GetTimestamp + Clone> Stages<T> {
+    /// Creates an empty list of stages, which will use a user-supplied `GetTimestamp` struct.
+    pub fn new(timefunc: T) -> Stages<T> {
+        Stages {
+            ctx: TimeContext::new(timefunc.clone()),
+            timefunc,
+            stages: Vec::new(),
+            current: None,
+            cprog: 0f64,
+        }
+    }
+
+    /// Appends a stage with a given relative `weight` and returns its index.
+    pub fn add_stage(&mut self, label: &str, weight: f64) -> usize {
+        self.stages.push(Stage {
+            label: label.to_string(),
+            weight: if weight > 0f64 { weight } else { 0f64 },
+            expected: None,
+            started: None,
+            duration: None,
+        });
+
+        self.stages.len() - 1
+    }
+
+    /// Sets the expected duration of the stage at `index`, in units of time used by the
+    /// `GetTimestamp` struct.
+    pub fn set_expected_duration(&mut self, index: usize, duration: u64) {
+        if let Some(stage) = self.stages.get_mut(index) {
+            stage.expected = Some(duration);
+        }
+    }
+
+    /// Finishes the current stage (if any) and starts the next one.
+    ///
+    /// Returns the index of the stage which was started, or `None` after the last stage was
+    /// finished.
+    pub fn next_stage(&mut self) -> Option<usize> {
+        let now = self.timefunc.get_timestamp();
+
+        if let Some(index) = self.current {
+            let stage = &mut self.stages[index];
+            let started = stage.started.unwrap_or(now);
+            stage.duration = Some(now.saturating_sub(started));
+        }
+
+        let next = match self.current {
+            None => 0,
+            Some(index) => index + 1,
+        };
+
+        if next >= self.stages.len() {
+            self.current = None;
+            return None;
+        }
+
+        self.stages[next].started = Some(now);
+        self.current = Some(next);
+        self.ctx = TimeContext::new(self.timefunc.clone());
+        self.cprog = 0f64;
+        Some(next)
+    }
+
+    /// Updates the progress of the current stage. See `TimeContext::update_eta`.
+    pub fn update_eta(&mut self, cur_prog: u64, max_prog: u64) {
+        if self.current.is_none() {
+            return;
+        }
+
+        self.ctx.update_eta(cur_prog, max_prog);
+        self.cprog = if max_prog == 0 {
+            0f64
+        } else {
+            (cur_prog as f64 * 100.0f64 / max_prog as f64).min(100.0f64)
+        };
+    }
+
+    /// Returns the index of the running stage.
+    pub fn get_current(&self) -> Option<usize> { self.current }
+
+    /// Returns the label of the stage at `index`.
+    pub fn get_label(&self, index: usize) -> Option<&str> {
+        self.stages.get(index).map(|s| &s.label[..])
+    }
+
+    /// Returns the number of stages.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns `true` if no stages were added.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Changes the relative weight of the stage at `index`.
+    pub fn set_weight(&mut self, index: usize, weight: f64) {
+        if let Some(stage) = self.stages.get_mut(index) {
+            stage.weight = if weight > 0f64 { weight } else { 0f64 };
+        }
+    }
+
+    /// Returns the measured duration of the stage at `index`, if it's finished.
+    pub fn get_duration(&self, index: usize) -> Option<u64> {
+        self.stages.get(index).and_then(|s| s.duration)
+    }
+
+    /// Gets a reference to the context measuring the current stage.
+    pub fn get_context(&self) -> &TimeContext<T> { &self.ctx }
+
+    /// Returns the overall progress, in percent.
+    pub fn get_progress(&self) -> f64 {
+        let total = self.stages.iter().fold(0f64, |acc, s| acc + s.weight);
+        if total <= 0f64 {
+            return 0f64;
+        }
+
+        let done = self.stages.iter()
+            .filter(|s| s.duration.is_some())
+            .fold(0f64, |acc, s| acc + s.weight);
+
+        let running = match self.current {
+            Some(index) => self.stages[index].weight * self.cprog / 100.0f64,
+            None => 0f64,
+        };
+
+        (done + running) * 100.0f64 / total
+    }
+
+    /// Returns the remaining time of the whole job (ETA), including the stages which weren't
+    /// started yet.
+    ///
+    /// Stages with no expected duration are estimated only when the time per unit of weight is
+    /// known, i.e. after at least one stage made measurable progress; until then they count as 0.
+    pub fn get_remaining_time(&self) -> i64 {
+        let now = self.timefunc.get_timestamp();
+        let rate = self.time_per_weight(now);
+        let mut remaining = 0f64;
+
+        if let Some(index) = self.current {
+            let stage = &self.stages[index];
+            let elapsed = now.saturating_sub(stage.started.unwrap_or(now)) as f64;
+
+            remaining = if self.ctx.calc_speed_per_unit() > 0f64 {
+                self.ctx.get_remaining_time() as f64
+            } else if let Some(expected) = stage.expected {
+                (expected as f64 - elapsed).max(0f64)
+            } else {
+                (stage.weight * rate - elapsed).max(0f64)
+            };
+        }
+
+        let first_pending = match self.current {
+            Some(index) => index + 1,
+            None => self.stages.iter().position(|s| s.duration.is_none())
+                            .unwrap_or(self.stages.len()),
+        };
+
+        for stage in self.stages[first_pending..].iter() {
+            remaining += match stage.expected {
+                Some(expected) => expected as f64,
+                None => stage.weight * rate,
+            };
+        }
+
+        clamp_time(remaining)
+    }
+
+    // Time per unit of weight, measured in the finished stages, or projected from the running
+    // one if no stage is finished yet.
+    fn time_per_weight(&self, now: u64) -> f64 {
+        let (time, weight) = self.stages.iter()
+            .filter_map(|s| s.duration.map(|d| (d as f64, s.weight)))
+            .fold((0f64, 0f64), |(t, w), (d, sw)| (t + d, w + sw));
+
+        if weight > 0f64 {
+            return time / weight;
+        }
+
+        if let Some(index) = self.current {
+            let stage = &self.stages[index];
+            if stage.weight > 0f64 && self.ctx.calc_speed_per_unit() > 0f64 {
+                let elapsed = now.saturating_sub(stage.started.unwrap_or(now)) as f64;
+                let projected = elapsed + self.ctx.get_remaining_time() as f64;
+                return projected / stage.weight;
+            }
+        }
+
+        0f64
+    }
+}
+
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug)]
+struct Timing {
+    mean: f64,            // Mean duration of the stage.
+    runs: u64,            // Number of runs the mean was calculated from.
+}
+
+/// Per-stage timings learned from completed runs of a `Stages` job.
+///
+/// After a run is finished, `record` stores the measured duration of every stage, keyed by the
+/// stage's label. Before the next run, `apply` sets the expected duration and the weight of every
+/// known stage to its mean duration, so the whole-job ETA is accurate even before the later
+/// stages start. The history can be persisted with `save` and `load`.
+///
+/// Whole runs are compared with the previous runs of the same job by a `RunStore` instead.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{StageHistory, Stages, SystemTimer};
+///   # fn main() -> std::io::Result<()> {
+///   # let path = "stages.txt";
+///   # let mut job = Stages::new(SystemTimer::new());
+///   let mut history = StageHistory::load(&path).unwrap_or(StageHistory::new());
+///   history.apply(&mut job);
+///
+///   // ... run all stages of `job` ...
+///
+///   history.record(&job);
+///   history.save(&path)?;
+///   # Ok(())
+///   # }
+/// ```
+#[cfg(feature = "std")]
+pub struct StageHistory {
+    timings: HashMap<String, Timing>,
+}
+
+#[cfg(feature = "std")]
+impl StageHistory {
+    /// Creates an empty history.
+    pub fn new() -> StageHistory {
+        StageHistory { timings: HashMap::new() }
+    }
+
+    /// Records the durations of all finished stages of `stages`.
+    pub fn record<T: GetTimestamp + Clone>(&mut self, stages: &Stages<T>) {
+        for stage in stages.stages.iter() {
+            if let Some(duration) = stage.duration {
+                self.record_stage(&stage.label, duration);
+            }
+        }
+    }
+
+    /// Records a single run of the stage labeled `label`, which took `duration` units of time.
+    pub fn record_stage(&mut self, label: &str, duration: u64) {
+        let timing = self.timings.entry(label.to_string())
+            .or_insert(Timing { mean: 0f64, runs: 0u64 });
+
+        timing.runs += 1;
+        timing.mean = timing.mean + (duration as f64 - timing.mean) / timing.runs as f64;
+    }
+
+    /// Returns the mean duration of the stage labeled `label`.
+    pub fn get_mean_duration(&self, label: &str) -> Option<u64> {
+        self.timings.get(label).map(|t| t.mean as u64)
+    }
+
+    /// Sets the expected duration and the weight of every stage of `stages` which is known to this
+    /// history. Stages which are not known are left untouched.
+    pub fn apply<T: GetTimestamp + Clone>(&self, stages: &mut Stages<T>) {
+        for stage in stages.stages.iter_mut() {
+            if let Some(timing) = self.timings.get(&stage.label) {
+                stage.expected = Some(timing.mean as u64);
+                stage.weight = timing.mean;
+            }
+        }
+    }
+
+    /// Writes the history in a simple text format: one `label<TAB>mean<TAB>runs` line per stage.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for (label, timing) in self.timings.iter() {
+            let label = label.replace(['\t', '\n'], " ");
+            writeln!(w, "{}\t{}\t{}", label, timing.mean, timing.runs)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a history written by `write_to`. Malformed lines are skipped.
+    pub fn read_from<R: BufRead>(r: R) -> io::Result<StageHistory> {
+        let mut history = StageHistory::new();
+
+        for line in r.lines() {
+            let line = line?;
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 3 {
+                continue;
+            }
+
+            if let (Ok(mean), Ok(runs)) = (fields[1].parse::<f64>(), fields[2].parse::<u64>()) {
+                history.timings.insert(fields[0].to_string(), Timing { mean, runs });
+            }
+        }
+
+        Ok(history)
+    }
+
+    /// Saves the history to a file at `path`. See `write_to`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        self.write_to(&mut file)
+    }
+
+    /// Loads the history from a file at `path`. See `read_from`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<StageHistory> {
+        StageHistory::read_from(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StageHistory {
+    fn default() -> StageHistory { StageHistory::new() }
+}
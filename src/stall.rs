@@ -0,0 +1,95 @@
+use alloc::vec::Vec;
+
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// A period without progress detected by a `TimeContext`. See `TimeContext::set_stall_timeout`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Stall {
+    /// Time of the last progress before the stall.
+    pub start: u64,
+
+    /// Time without progress, up to the progress which ended the stall, or up to the latest
+    /// update if it's still going on.
+    pub duration: u64,
+
+    /// No progress was made since the stall started.
+    pub ongoing: bool,
+}
+
+/// A log of the stalls detected over a whole run.
+///
+/// A stall starts when the progress was last made, and it's logged once it lasts longer than the
+/// stall timeout, so short pauses aren't included. Together with the start times, the log can be
+/// correlated with external events, e.g. network outages.
+///
+/// The log holds a fixed number of stalls, reserved up front so that `update_eta` doesn't
+/// allocate; the stalls detected once it's full are only counted.
+///
+/// It's enabled with `TimeContext::enable_stall_log`, which requires a stall timeout to be set
+/// with `TimeContext::set_stall_timeout`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StallLog {
+    stalls: Vec<Stall>,
+    capacity: usize,      // Maximum number of stalls kept.
+    dropped: usize,       // Number of stalls detected after the log was full.
+}
+
+impl StallLog {
+    fn new(capacity: usize) -> StallLog {
+        StallLog {
+            stalls: Vec::with_capacity(capacity),
+            capacity,
+            dropped: 0,
+        }
+    }
+
+    /// Returns the logged stalls, from the oldest to the newest.
+    pub fn get_stalls(&self) -> &[Stall] { &self.stalls }
+
+    /// Returns the number of stalls, including the ones which didn't fit in the log.
+    pub fn get_count(&self) -> usize { self.stalls.len().saturating_add(self.dropped) }
+
+    /// Returns the number of stalls which didn't fit in the log.
+    pub fn get_dropped(&self) -> usize { self.dropped }
+
+    /// Returns the total time of the logged stalls.
+    pub fn get_total_duration(&self) -> u64 {
+        self.stalls.iter().fold(0u64, |total, s| total.saturating_add(s.duration))
+    }
+
+    // Called by `TimeContext` when a stall is detected.
+    pub(crate) fn start(&mut self, start: u64, now: u64) {
+        if self.stalls.len() >= self.capacity {
+            self.dropped = self.dropped.saturating_add(1);
+            return;
+        }
+
+        self.stalls.push(Stall {
+            start,
+            duration: now.saturating_sub(start),
+            ongoing: true,
+        });
+    }
+
+    // Called by `TimeContext` for every update, which either extends the ongoing stall, or ends it
+    // if `progressed`.
+    pub(crate) fn update(&mut self, now: u64, progressed: bool) {
+        if let Some(stall) = self.stalls.last_mut() {
+            if stall.ongoing {
+                stall.duration = now.saturating_sub(stall.start);
+                stall.ongoing = !progressed;
+            }
+        }
+    }
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Starts logging the stalls of the run, keeping at most `capacity` of them. See `StallLog`.
+    pub fn enable_stall_log(&mut self, capacity: usize) {
+        self.stall_log = Some(StallLog::new(capacity));
+    }
+
+    /// Returns the log of the stalls, if it was enabled with `enable_stall_log`.
+    pub fn get_stall_log(&self) -> Option<&StallLog> { self.stall_log.as_ref() }
+}
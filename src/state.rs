@@ -0,0 +1,118 @@
+use alloc::vec::Vec;
+
+use crate::{GetTimestamp, Sample, Status, TimeContext, Window};
+
+// A sample of the estimator's window, with its timestamp relative to the first update.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct SampleState {
+    offset: u64,
+    current: u64,
+}
+
+/// The complete state of a `TimeContext` estimator, without its `GetTimestamp` struct and
+/// observers.
+///
+/// It's obtained with `TimeContext::get_state`, and turned back into a context with
+/// `TimeContext::from_state`. All timestamps are stored relative to the first update, so the
+/// state can be restored with a clock which started counting from scratch, e.g. after a process
+/// restart. With the `serde` feature enabled, it implements `Serialize` and `Deserialize`, so a
+/// long-running job can checkpoint its estimator together with its own progress.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContextState {
+    elapsed: Option<u64>, // Time elapsed since the first update, if there was one.
+    first_current: Option<u64>,
+    current: u64,
+    total: u64,
+    last_offset: Option<u64>,
+    samples: Vec<SampleState>,
+    stall_timeout: Option<u64>,
+    monotone: Option<u64>,
+    status: Status,
+    known: bool,          // The maximum progress value is known (not `update_count`).
+    epsilon: u64,
+    discard: usize,       // Intervals still to be excluded from the speed.
+    anchor: Option<SampleState>,
+                          // The last sample of an excluded interval.
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    /// Returns the state of the estimator. See `ContextState`.
+    pub fn get_state(&self) -> ContextState {
+        let fts = self.origin.map(|o| o.timestamp).unwrap_or(0u64);
+
+        ContextState {
+            elapsed: self.origin.map(|o| self.now().saturating_sub(o.timestamp)),
+            first_current: self.origin.map(|o| o.current),
+            current: self.cur,
+            total: self.max,
+            last_offset: self.lts.map(|lts| lts.saturating_sub(fts)),
+            samples: self.samples.iter().map(|s| SampleState {
+                offset: s.timestamp.saturating_sub(fts),
+                current: s.current,
+            }).collect(),
+            stall_timeout: self.stall_timeout,
+            monotone: self.monotone,
+            status: self.status,
+            known: self.known,
+            epsilon: self.epsilon,
+            discard: self.discard,
+            anchor: self.anchor.map(|a| SampleState {
+                offset: a.timestamp.saturating_sub(fts),
+                current: a.current,
+            }),
+        }
+    }
+}
+
+impl<T: GetTimestamp> TimeContext<T> {
+    /// Creates a `TimeContext` from a state saved with `get_state`, which will use a
+    /// user-supplied `GetTimestamp` struct.
+    ///
+    /// The time elapsed between saving and restoring the state is not counted: the restored
+    /// context continues as if the job was never interrupted.
+    pub fn from_state(state: &ContextState, timefunc: T) -> TimeContext<T> {
+        let mut ctx = TimeContext::new(timefunc);
+        ctx.known = state.known;
+        ctx.epsilon = state.epsilon;
+        ctx.discard = state.discard;
+
+        let elapsed = match state.elapsed {
+            Some(elapsed) => elapsed,
+            None => return ctx,
+        };
+
+        // The new clock may be behind the saved elapsed time (e.g. it started from 0 again), so
+        // it's shifted forward as needed to keep all timestamps representable.
+        let now = ctx.timefunc.get_timestamp();
+        ctx.shift = elapsed.saturating_sub(now);
+
+        let fts = ctx.now().saturating_sub(elapsed);
+        ctx.origin = Some(Sample {
+            timestamp: fts,
+            current: state.first_current.unwrap_or(0u64),
+        });
+        ctx.cur = state.current;
+        ctx.max = state.total;
+        ctx.lts = state.last_offset.map(|offset| fts.saturating_add(offset));
+        ctx.stall_timeout = state.stall_timeout;
+        ctx.monotone = state.monotone;
+        ctx.status = state.status;
+        ctx.anchor = state.anchor.map(|a| Sample {
+            timestamp: fts.saturating_add(a.offset),
+            current: a.current,
+        });
+
+        for sample in state.samples.iter() {
+            ctx.push_sample(Sample {
+                timestamp: fts.saturating_add(sample.offset),
+                current: sample.current,
+            });
+        }
+
+        let now = ctx.now();
+        ctx.refresh(now);
+        ctx
+    }
+}
@@ -0,0 +1,105 @@
+use std::format;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// A handle to the status server started by `spawn_status_server`.
+///
+/// Dropping the handle detaches the server thread; it keeps answering until the process exits,
+/// and the socket file is left behind. Use `stop` to shut it down and remove the socket.
+pub struct StatusServer {
+    running: Arc<AtomicBool>,
+    path: PathBuf,
+    thread: JoinHandle<()>,
+}
+
+impl StatusServer {
+    /// Stops the server, waits for its thread to exit, and removes the socket file.
+    pub fn stop(self) {
+        self.running.store(false, Ordering::Relaxed);
+
+        // Wakes up the thread blocked on `accept`.
+        let _ = UnixStream::connect(&self.path);
+        let _ = self.thread.join();
+        let _ = fs::remove_file(&self.path);
+    }
+
+    /// Returns the path of the socket the server listens on.
+    pub fn get_path(&self) -> &Path { &self.path }
+}
+
+/// Starts a thread which answers connections to a Unix domain socket at `path` with the current
+/// `Snapshot` of a shared context, so the ETA of a running job can be queried from outside, e.g.
+/// with `gaeta-status <path>` or `socat - UNIX-CONNECT:<path>`.
+///
+/// Every connection gets a single line of JSON (see `Snapshot::to_json`), after which it's
+/// closed. A stale socket at `path`, left by a server which wasn't stopped, is replaced; any other
+/// file there makes it fail with `AddrInUse`.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{spawn_status_server, EtaHandle, SystemTimer, TimeContext};
+///   # fn process(_file: &str) -> std::io::Result<()> { Ok(()) }
+///   # fn main() -> std::io::Result<()> {
+///   # let files = ["a", "b", "c"];
+///   let handle = EtaHandle::new(TimeContext::new(SystemTimer::new()));
+///   let server = spawn_status_server("/tmp/job.sock", handle.get_shared())?;
+///
+///   for (i, file) in files.iter().enumerate() {
+///       process(file)?;
+///       handle.update_eta(i as u64 + 1, files.len() as u64);
+///   }
+///
+///   server.stop();
+///   # Ok(())
+///   # }
+/// ```
+pub fn spawn_status_server<T, W, P>(path: P, ctx: Arc<Mutex<TimeContext<T, W>>>)
+    -> io::Result<StatusServer>
+    where T: GetTimestamp + Send + 'static,
+          W: Window + Send + 'static,
+          P: Into<PathBuf> {
+    let path = path.into();
+
+    // Only a socket which nobody answers on is stale; anything else makes `bind` fail.
+    let socket = fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_socket());
+    if socket && UnixStream::connect(&path).is_err() {
+        fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    let running = Arc::new(AtomicBool::new(true));
+    let flag = running.clone();
+
+    let thread = thread::spawn(move || {
+        for stream in listener.incoming() {
+            if !flag.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let snapshot = match ctx.lock() {
+                Ok(ctx) => ctx.snapshot(),
+                Err(_) => break,
+            };
+
+            // A client which went away doesn't concern the server.
+            if let Ok(mut stream) = stream {
+                let _ = stream.write_all(format!("{}\n", snapshot.to_json()).as_bytes());
+            }
+        }
+    });
+
+    Ok(StatusServer {
+        running,
+        path,
+        thread,
+    })
+}
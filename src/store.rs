@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use crate::{GetTimestamp, TimeContext, Window};
+
+/// A summary of one completed run of a job.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RunSummary {
+    /// Time the run took, in units of time used by the `GetTimestamp` struct. The idle time isn't
+    /// included if the context excludes it; see `TimeContext::set_idle_excluded`.
+    pub duration: u64,
+
+    /// Progress made by the run, in work units.
+    pub progress: u64,
+}
+
+impl RunSummary {
+    /// Summarizes the run of `ctx` so far, from its first to its newest sample, over the same time
+    /// as `TimeContext::calc_average_speed`. Returns `None` if there's no sample after the first
+    /// one.
+    pub fn from_context<T: GetTimestamp, W: Window>(ctx: &TimeContext<T, W>) -> Option<RunSummary> {
+        let (duration, progress) = ctx.measure_run()?;
+        Some(RunSummary { duration, progress })
+    }
+
+    /// Returns the average speed of the run, in work units per unit of time.
+    pub fn get_speed(&self) -> f64 {
+        if self.duration == 0 { 0f64 } else { self.progress as f64 / self.duration as f64 }
+    }
+}
+
+/// Statistics of all recorded runs of one job.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RunStats {
+    /// Number of recorded runs.
+    pub runs: u64,
+
+    /// Mean duration of the runs.
+    pub mean_duration: f64,
+
+    /// Mean progress made by the runs.
+    pub mean_progress: f64,
+
+    /// Mean of the average speeds of the runs, in work units per unit of time.
+    pub mean_speed: f64,
+}
+
+impl RunStats {
+    fn add(&mut self, run: &RunSummary) {
+        self.runs += 1;
+
+        let runs = self.runs as f64;
+        self.mean_duration += (run.duration as f64 - self.mean_duration) / runs;
+        self.mean_progress += (run.progress as f64 - self.mean_progress) / runs;
+        self.mean_speed += (run.get_speed() - self.mean_speed) / runs;
+    }
+}
+
+/// A store of summaries of completed runs, keyed by the labels of the jobs.
+///
+/// It's the source of what's known about a job before it starts: the expected duration and the
+/// usual speed, which a running job can be compared with using `compare_speed`. `MemoryStore`
+/// keeps the summaries for the lifetime of the process, and `FileStore` persists them across
+/// runs; other backends (a database, a config service) can implement this trait.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{FileStore, RunStore, SystemTimer, TimeContext};
+///   # fn main() -> std::io::Result<()> {
+///   # let path = "runs.txt";
+///   # let ctx = TimeContext::new(SystemTimer::new());
+///   let mut store = FileStore::open(&path)?;
+///
+///   // ... run the job, updating `ctx` ...
+///
+///   if let Some(ratio) = store.compare_speed("backup", &ctx) {
+///       println!("running at {:.0}% of the usual speed", ratio * 100.0);
+///   }
+///
+///   store.record_context("backup", &ctx)?;
+///   # Ok(())
+///   # }
+/// ```
+pub trait RunStore {
+    /// Records a completed run of the job labeled `label`.
+    fn record(&mut self, label: &str, run: RunSummary) -> io::Result<()>;
+
+    /// Returns the statistics of the recorded runs of the job labeled `label`, or `None` if none
+    /// was recorded.
+    fn get_stats(&self, label: &str) -> Option<RunStats>;
+
+    /// Records the run of `ctx` as a completed run of the job labeled `label`. Does nothing if
+    /// `ctx` didn't measure any progress.
+    fn record_context<T, W>(&mut self, label: &str, ctx: &TimeContext<T, W>) -> io::Result<()>
+        where T: GetTimestamp, W: Window, Self: Sized
+    {
+        match RunSummary::from_context(ctx) {
+            Some(run) => self.record(label, run),
+            None => Ok(()),
+        }
+    }
+
+    /// Compares the average speed of the run of `ctx` so far with the mean speed of the recorded
+    /// runs of the job labeled `label`. Returns the ratio of the two: e.g. 0.6 means the run is
+    /// 40% slower than usual. Returns `None` if no run of the job was recorded, or if `ctx` didn't
+    /// measure any progress yet.
+    fn compare_speed<T, W>(&self, label: &str, ctx: &TimeContext<T, W>) -> Option<f64>
+        where T: GetTimestamp, W: Window, Self: Sized
+    {
+        let usual = self.get_stats(label)?.mean_speed;
+        let speed = ctx.calc_average_speed();
+
+        if usual > 0f64 && speed > 0f64 { Some(speed / usual) } else { None }
+    }
+}
+
+/// A `RunStore` which keeps the summaries in memory.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    stats: HashMap<String, RunStats>,
+}
+
+impl MemoryStore {
+    /// Creates an empty store.
+    pub fn new() -> MemoryStore {
+        MemoryStore { stats: HashMap::new() }
+    }
+
+    /// Writes the store in a simple text format: one
+    /// `label<TAB>runs<TAB>mean_duration<TAB>mean_progress<TAB>mean_speed` line per job.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        for (label, stats) in self.stats.iter() {
+            let label = label.replace(['\t', '\n'], " ");
+            writeln!(w, "{}\t{}\t{}\t{}\t{}", label, stats.runs, stats.mean_duration,
+                     stats.mean_progress, stats.mean_speed)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a store written by `write_to`. Malformed lines are skipped.
+    pub fn read_from<R: BufRead>(r: R) -> io::Result<MemoryStore> {
+        let mut store = MemoryStore::new();
+
+        for line in r.lines() {
+            let line = line?;
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 5 {
+                continue;
+            }
+
+            let parsed = (fields[1].parse::<u64>(), fields[2].parse::<f64>(),
+                          fields[3].parse::<f64>(), fields[4].parse::<f64>());
+
+            if let (Ok(runs), Ok(mean_duration), Ok(mean_progress), Ok(mean_speed)) = parsed {
+                let stats = RunStats { runs, mean_duration, mean_progress, mean_speed };
+                store.stats.insert(fields[0].to_string(), stats);
+            }
+        }
+
+        Ok(store)
+    }
+}
+
+impl RunStore for MemoryStore {
+    fn record(&mut self, label: &str, run: RunSummary) -> io::Result<()> {
+        self.stats.entry(label.to_string())
+            .or_insert(RunStats { runs: 0u64, mean_duration: 0f64, mean_progress: 0f64,
+                                  mean_speed: 0f64 })
+            .add(&run);
+
+        Ok(())
+    }
+
+    fn get_stats(&self, label: &str) -> Option<RunStats> {
+        self.stats.get(label).copied()
+    }
+}
+
+/// A `RunStore` backed by a file, which is rewritten after every recorded run. See
+/// `MemoryStore::write_to` for the format.
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    path: PathBuf,
+    memory: MemoryStore,
+}
+
+impl FileStore {
+    /// Opens the store in the file at `path`. If the file doesn't exist, the store is empty, and
+    /// the file will be created by the first recorded run.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<FileStore> {
+        let path = path.as_ref().to_path_buf();
+
+        let memory = match File::open(&path) {
+            Ok(file) => MemoryStore::read_from(BufReader::new(file))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => MemoryStore::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(FileStore { path, memory })
+    }
+
+    /// Returns the path of the file.
+    pub fn get_path(&self) -> &Path { &self.path }
+}
+
+impl RunStore for FileStore {
+    fn record(&mut self, label: &str, run: RunSummary) -> io::Result<()> {
+        self.memory.record(label, run)?;
+
+        let mut file = File::create(&self.path)?;
+        self.memory.write_to(&mut file)
+    }
+
+    fn get_stats(&self, label: &str) -> Option<RunStats> {
+        self.memory.get_stats(label)
+    }
+}
@@ -0,0 +1,91 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+
+use crate::{EtaHandle, GetTimestamp, TimeContext};
+
+/// A stream adapter which counts yielded items against a known total and updates a context.
+///
+/// It's created by the `track_eta` method of the `EtaStreamExt` trait.
+pub struct EtaStream<S, T> {
+    stream: S,
+    handle: EtaHandle<T>,
+    count: u64,           // Number of items yielded so far.
+    total: u64,           // Expected number of items.
+}
+
+impl<S, T: GetTimestamp> EtaStream<S, T> {
+    /// Returns a handle which can be used to read the live ETA while the stream is consumed.
+    pub fn handle(&self) -> EtaHandle<T> {
+        self.handle.clone()
+    }
+
+    /// Returns the number of items yielded so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl<S: Stream + Unpin, T: GetTimestamp> Stream for EtaStream<S, T> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<S::Item>> {
+        let this = &mut *self;
+        let item = Pin::new(&mut this.stream).poll_next(cx);
+
+        match item {
+            Poll::Ready(Some(_)) => {
+                this.count = this.count.saturating_add(1);
+                this.handle.update_eta(this.count, this.total);
+            }
+            Poll::Ready(None) => {
+                this.handle.update_eta(this.total, this.total);
+            }
+            Poll::Pending => {}
+        }
+
+        item
+    }
+}
+
+/// An extension trait which adds ETA tracking to any `futures::Stream`.
+///
+/// # Example
+///
+/// ```rust
+///   # use futures::StreamExt;
+///   # use gaeta::{EtaStreamExt, SystemTimer};
+///   # struct Download(u64);
+///   # impl Download {
+///   #     fn chunks(&self) -> impl futures::Stream<Item = Vec<u8>> {
+///   #         futures::stream::iter((0..self.0).map(|_| vec![0u8; 16]))
+///   #     }
+///   # }
+///   # futures::executor::block_on(async {
+///   # let (download, chunk_count) = (Download(4), 4);
+///   let mut chunks = download.chunks().track_eta(chunk_count, SystemTimer::new());
+///   let eta = chunks.handle();
+///
+///   while let Some(chunk) = chunks.next().await {
+///       // `eta.get_remaining_time()` can be read from any task.
+///   #   drop(chunk);
+///   }
+///   # assert_eq!(eta.snapshot().progress, 100.0);
+///   # });
+/// ```
+///
+/// The wrapped stream must be `Unpin`; use `Box::pin` for streams which aren't.
+pub trait EtaStreamExt: Stream + Sized {
+    /// Wraps this stream, which is expected to yield `total` items, into an `EtaStream`.
+    fn track_eta<T: GetTimestamp>(self, total: u64, timefunc: T) -> EtaStream<Self, T> {
+        EtaStream {
+            stream: self,
+            handle: EtaHandle::new(TimeContext::new(timefunc)),
+            count: 0u64,
+            total,
+        }
+    }
+}
+
+impl<S: Stream> EtaStreamExt for S {}
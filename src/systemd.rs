@@ -0,0 +1,136 @@
+use std::env;
+use std::ffi::OsStr;
+use std::format;
+use std::io;
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::net::{SocketAddr, UnixDatagram};
+use std::time::Duration;
+
+use crate::{Observer, Snapshot, Status, MAX_REMAINING_TIME};
+
+/// A client of the systemd notification protocol (`sd_notify`), on Linux, which keeps a service
+/// doing a long job at startup, like a database migration, from being killed by systemd's start
+/// timeout.
+///
+/// As an `Observer` of the job's context, it sends `EXTEND_TIMEOUT_USEC` with every progress
+/// notification, set to the remaining time plus a margin, together with a `STATUS` line shown by
+/// `systemctl status`. systemd then waits until the job is expected to finish, as long as the
+/// progress keeps coming; when the ETA is unknown, the timeout is extended by the margin alone.
+/// The service needs `Type=notify` for any of it to have an effect.
+///
+/// Every notification is a datagram sent to the socket from `NOTIFY_SOCKET`; errors are ignored
+/// by the observer. To send fewer of them, register it with `TimeContext::add_throttled_observer`.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{SystemdNotifier, SystemTimer, TimeContext};
+///   # use std::time::Duration;
+///   # fn migrate(ctx: &mut TimeContext<SystemTimer>) -> std::io::Result<()> {
+///   #     ctx.update_eta(1, 1);
+///   #     Ok(())
+///   # }
+///   # fn main() -> std::io::Result<()> {
+///   # let mut ctx = TimeContext::new(SystemTimer::new());
+///   let unit = Duration::from_millis(1);
+///   if let Some(notifier) = SystemdNotifier::from_env(unit) {
+///       ctx.add_throttled_observer(notifier.with_margin(Duration::from_secs(30)), 1);
+///   }
+///
+///   migrate(&mut ctx)?;
+///
+///   if let Some(notifier) = SystemdNotifier::from_env(unit) {
+///       notifier.ready()?;
+///   }
+///   # Ok(())
+///   # }
+/// ```
+pub struct SystemdNotifier {
+    socket: UnixDatagram,
+    addr: SocketAddr,     // Address of the notification socket of systemd.
+    unit: Duration,       // Unit of time of the `GetTimestamp` struct.
+    margin: Duration,     // Time added to the remaining time.
+}
+
+impl SystemdNotifier {
+    /// Creates a notifier connected to the socket given by systemd in `NOTIFY_SOCKET`, or returns
+    /// `None` if the variable isn't set (the process doesn't run as a `Type=notify` service) or
+    /// the socket can't be used. `unit` is the `Duration` of a single unit of time of the
+    /// `GetTimestamp` struct (e.g. `Duration::from_millis(1)` for `SystemTimer`). The margin is
+    /// 10 seconds.
+    pub fn from_env(unit: Duration) -> Option<SystemdNotifier> {
+        let path = env::var_os("NOTIFY_SOCKET")?;
+        SystemdNotifier::new(path, unit).ok()
+    }
+
+    /// Creates a notifier sending to the socket at `path`, which is an abstract socket if it
+    /// starts with `@`, like in `NOTIFY_SOCKET`.
+    pub fn new<P: AsRef<OsStr>>(path: P, unit: Duration) -> io::Result<SystemdNotifier> {
+        let path = path.as_ref();
+        let addr = match path.as_bytes().strip_prefix(b"@") {
+            Some(name) => SocketAddr::from_abstract_name(name)?,
+            None => SocketAddr::from_pathname(path)?,
+        };
+
+        Ok(SystemdNotifier {
+            socket: UnixDatagram::unbound()?,
+            addr,
+            unit,
+            margin: Duration::from_secs(10),
+        })
+    }
+
+    /// Sets the time added to the remaining time, which also covers the gaps between the
+    /// progress notifications.
+    pub fn with_margin(mut self, margin: Duration) -> SystemdNotifier {
+        self.margin = margin;
+        self
+    }
+
+    /// Returns the time added to the remaining time.
+    pub fn get_margin(&self) -> Duration { self.margin }
+
+    /// Sends `state`, one or more newline-separated assignments like `READY=1`, to systemd.
+    pub fn notify(&self, state: &str) -> io::Result<()> {
+        self.socket.send_to_addr(state.as_bytes(), &self.addr).map(|_| ())
+    }
+
+    /// Tells systemd that the start timeout should end `extension` from now.
+    pub fn extend_timeout(&self, extension: Duration) -> io::Result<()> {
+        self.notify(&format!("EXTEND_TIMEOUT_USEC={}", extension.as_micros()))
+    }
+
+    /// Tells systemd that the service finished starting up (`READY=1`).
+    pub fn ready(&self) -> io::Result<()> {
+        self.notify("READY=1")
+    }
+
+    // Returns the remaining time from `snapshot`, if it's known and finite.
+    fn remaining(&self, snapshot: &Snapshot) -> Option<Duration> {
+        if snapshot.remaining <= 0 || snapshot.remaining >= MAX_REMAINING_TIME {
+            return None;
+        }
+
+        Some(self.unit.saturating_mul(snapshot.remaining.min(u32::MAX as i64) as u32))
+    }
+}
+
+impl Observer for SystemdNotifier {
+    fn on_progress(&mut self, snapshot: &Snapshot) {
+        if snapshot.status != Status::Running {
+            return;
+        }
+
+        let remaining = self.remaining(snapshot);
+        let extension = remaining.unwrap_or_default().saturating_add(self.margin);
+        let mut state = format!("EXTEND_TIMEOUT_USEC={}\nSTATUS={:.0}% done",
+            extension.as_micros(), snapshot.progress);
+
+        if let Some(remaining) = remaining {
+            state += &format!(", {} s left", remaining.as_secs());
+        }
+
+        let _ = self.notify(&state);
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::{Duration, Instant};
+
+use crate::{Observer, Snapshot};
+
+/// An `Observer` wrapper which limits the rate of progress notifications.
+///
+/// At most `max_per_sec` `on_progress` calls per second (of wall-clock time) are passed to the
+/// wrapped observer; the others are dropped, except for the most recent one, which is delivered
+/// before `on_complete` (or `on_cancel`), so the observer always sees the final state. Other
+/// notifications are passed through immediately.
+///
+/// This lets naive GUI bindings subscribe directly to a context which is updated in a tight
+/// loop, without flooding their event loops.
+pub struct Throttled<O> {
+    inner: O,
+    min_interval: Duration,
+    last: Option<Instant>,
+    pending: Option<Snapshot>,
+                          // Most recent snapshot which was not delivered.
+}
+
+impl<O: Observer> Throttled<O> {
+    /// Wraps `inner`, letting through at most `max_per_sec` progress notifications per second.
+    /// A value of 0 is treated as 1.
+    pub fn new(inner: O, max_per_sec: u32) -> Throttled<O> {
+        let max_per_sec = if max_per_sec > 0 { max_per_sec } else { 1 };
+
+        Throttled {
+            inner,
+            min_interval: Duration::from_secs(1) / max_per_sec,
+            last: None,
+            pending: None,
+        }
+    }
+
+    /// Unwraps this struct, returning the wrapped observer.
+    pub fn into_inner(self) -> O { self.inner }
+}
+
+impl<O: Observer> Observer for Throttled<O> {
+    fn on_start(&mut self) {
+        self.inner.on_start();
+    }
+
+    fn on_progress(&mut self, snapshot: &Snapshot) {
+        let now = Instant::now();
+        let due = match self.last {
+            None => true,
+            Some(last) => now.duration_since(last) >= self.min_interval,
+        };
+
+        if due {
+            self.last = Some(now);
+            self.pending = None;
+            self.inner.on_progress(snapshot);
+        } else {
+            self.pending = Some(*snapshot);
+        }
+    }
+
+    fn on_stall(&mut self) {
+        self.inner.on_stall();
+    }
+
+    fn on_deadline_missed(&mut self, projected: u64) {
+        self.inner.on_deadline_missed(projected);
+    }
+
+    fn on_slow(&mut self, speed: f64) {
+        self.inner.on_slow(speed);
+    }
+
+    fn on_complete(&mut self) {
+        if let Some(snapshot) = self.pending.take() {
+            self.inner.on_progress(&snapshot);
+        }
+
+        self.inner.on_complete();
+    }
+
+    fn on_cancel(&mut self) {
+        if let Some(snapshot) = self.pending.take() {
+            self.inner.on_progress(&snapshot);
+        }
+
+        self.inner.on_cancel();
+    }
+
+    fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{Aggregate, GetTimestamp, TimeContext, Tracker, Window, MAX_REMAINING_TIME};
+
+/// The span of one task of a multi-task run, for rendering a Gantt-style timeline.
+///
+/// All times are timestamps of the `GetTimestamp` struct. With the `serde` feature enabled, it
+/// implements `Serialize` and `Deserialize`, so a whole timeline can be exported e.g. as JSON.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimelineSpan {
+    /// Label of the task. Items of an `Aggregate` are labeled with their indices.
+    pub label: String,
+
+    /// Depth of the task in the tree, 0 for the root or for the items of an `Aggregate`.
+    pub depth: usize,
+
+    /// Time of the first update of the task, or `None` if it didn't start yet.
+    pub start: Option<u64>,
+
+    /// Time the task reached 100%, or `None` if it didn't finish yet.
+    pub end: Option<u64>,
+
+    /// Completion time predicted by the first estimate of the task, to compare with `end`.
+    pub predicted_end: Option<u64>,
+
+    /// Completion time projected by the current estimate, if the task is running and its speed
+    /// is known.
+    pub projected_end: Option<u64>,
+
+    /// Progress of the task, in percent.
+    pub progress: f64,
+}
+
+impl<T: GetTimestamp, W: Window> TimeContext<T, W> {
+    // Returns the span of the run of this context.
+    fn span(&self, label: String, depth: usize, progress: f64) -> TimelineSpan {
+        let unshift = |ts: u64| ts.saturating_sub(self.shift);
+
+        let projected_end = match (self.finished_at, self.called) {
+            (None, Some(now)) if self.remaining > 0 && self.remaining < MAX_REMAINING_TIME => {
+                Some(unshift(now.saturating_add(self.remaining as u64)))
+            }
+            _ => None,
+        };
+
+        TimelineSpan {
+            label,
+            depth,
+            start: self.origin.map(|origin| unshift(origin.timestamp)),
+            end: self.finished_at.map(unshift),
+            predicted_end: self.predicted_end.map(unshift),
+            projected_end,
+            progress,
+        }
+    }
+
+    // Called after every update, to remember the first estimate of the completion time.
+    pub(crate) fn predict_end(&mut self, ts: u64) {
+        if self.predicted_end.is_none() && self.remaining > 0 && self.remaining < MAX_REMAINING_TIME {
+            self.predicted_end = Some(ts.saturating_add(self.remaining as u64));
+        }
+    }
+}
+
+impl<T: GetTimestamp + Clone> Tracker<T> {
+    /// Returns the spans of this node and all of its descendants, depth first, with every node
+    /// followed by its children. See `TimelineSpan`.
+    pub fn timeline(&self) -> Vec<TimelineSpan> {
+        let mut spans = Vec::new();
+        self.collect_spans(0, &mut spans);
+        spans
+    }
+
+    fn collect_spans(&self, depth: usize, spans: &mut Vec<TimelineSpan>) {
+        spans.push(self.get_context().span(self.get_label().to_string(), depth, self.get_progress()));
+
+        let mut index = 0;
+        while let Some(child) = self.child(index) {
+            child.collect_spans(depth + 1, spans);
+            index += 1;
+        }
+    }
+}
+
+impl<T: GetTimestamp> Aggregate<T> {
+    /// Returns the spans of all items, in the order they were added. See `TimelineSpan`.
+    pub fn timeline(&self) -> Vec<TimelineSpan> {
+        (0..self.len())
+            .filter_map(|index| self.get(index).map(|ctx| (index, ctx)))
+            .map(|(index, ctx)| {
+                let progress = ctx.snapshot().progress.min(100.0f64);
+                ctx.span(index.to_string(), 0, progress)
+            })
+            .collect()
+    }
+}
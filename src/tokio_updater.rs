@@ -0,0 +1,88 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::{self, Interval, MissedTickBehavior};
+
+use crate::{GetProgress, GetTimestamp, Snapshot, TimeContext};
+
+struct Ticker<T, P> {
+    ctx: TimeContext<T>,
+    source: P,
+    interval: Interval,
+    tx: watch::Sender<Snapshot>,
+}
+
+impl<T: GetTimestamp + Unpin, P: GetProgress + Unpin> Future for Ticker<T, P> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let this = &mut *self;
+
+        loop {
+            if this.interval.poll_tick(cx).is_pending() {
+                return Poll::Pending;
+            }
+
+            let (cur_prog, max_prog) = this.source.get_progress();
+            this.ctx.update_eta(cur_prog, max_prog);
+
+            // Nobody listens anymore, or there's nothing left to measure.
+            if this.tx.send(this.ctx.snapshot()).is_err() || cur_prog >= max_prog {
+                return Poll::Ready(());
+            }
+        }
+    }
+}
+
+/// Spawns a tokio task which periodically polls a progress source and updates a context.
+///
+/// Every `period`, `source` is asked for the current `(cur_prog, max_prog)` pair (e.g. a shared
+/// `AtomicProgress`), `ctx` is updated, and a fresh `Snapshot` is broadcast over the returned
+/// `watch` channel. The task ends after it observes `cur_prog >= max_prog`, or when all
+/// receivers are dropped.
+///
+/// It must be called from within a tokio runtime. Available only with the `tokio` feature.
+///
+/// # Example
+///
+/// ```no_run
+///   # use gaeta::{spawn_tokio_updater, AtomicProgress, Snapshot, SystemTimer, TimeContext};
+///   # use std::sync::Arc;
+///   # use std::time::Duration;
+///   # fn render(_snapshot: Snapshot) {}
+///   # let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+///   # runtime.block_on(async {
+///   # let total = 100;
+///   let progress = Arc::new(AtomicProgress::new(total));
+///   let ctx = TimeContext::new(SystemTimer::new());
+///   let (task, mut snapshots) = spawn_tokio_updater(ctx, Duration::from_millis(250),
+///                                                   progress.clone());
+///
+///   while snapshots.changed().await.is_ok() {
+///       render(*snapshots.borrow());
+///   }
+///   # drop(task);
+///   # });
+/// ```
+pub fn spawn_tokio_updater<T, P>(ctx: TimeContext<T>, period: Duration, source: P)
+    -> (JoinHandle<()>, watch::Receiver<Snapshot>)
+    where T: GetTimestamp + Send + Unpin + 'static,
+          P: GetProgress + Send + Unpin + 'static {
+    let (tx, rx) = watch::channel(ctx.snapshot());
+
+    let mut interval = time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    let task = tokio::spawn(Ticker {
+        ctx,
+        source,
+        interval,
+        tx,
+    });
+
+    (task, rx)
+}
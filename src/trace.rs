@@ -0,0 +1,194 @@
+use alloc::vec::Vec;
+
+use crate::{GetTimestamp, LogEntry, SessionLog, TimeContext, Window, MAX_REMAINING_TIME};
+
+/// The shape of the speed of a synthetic trace.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Workload {
+    /// The same speed from the start to the end.
+    Steady,
+
+    /// Five bursts of work, each followed by a pause longer than the burst.
+    Bursty,
+
+    /// A steady speed, but no progress at all between 40% and 60% of the time.
+    Stalling,
+
+    /// A speed which keeps falling, down to a fifth of the initial speed at the end, like a disk
+    /// slowing down as its cache fills.
+    Decelerating,
+}
+
+impl Workload {
+    // The relative speed at `time`, which goes from 0 to 1 over the whole trace.
+    fn speed(&self, time: f64) -> f64 {
+        match *self {
+            Workload::Steady => 1f64,
+            Workload::Bursty => {
+                let cycle = time * 5f64;
+                let phase = cycle - (cycle as u64) as f64;
+                if phase < 0.4f64 { 1f64 } else { 0f64 }
+            }
+            Workload::Stalling => if (0.4f64..0.6f64).contains(&time) { 0f64 } else { 1f64 },
+            Workload::Decelerating => 1f64 - 0.8f64 * time,
+        }
+    }
+}
+
+/// A generator of realistic synthetic traces, for evaluating estimator configurations.
+///
+/// A trace is a `SessionLog` of updates which starts at time 0 and progress 0 and ends exactly at
+/// the given total and duration, with the speed shaped by a `Workload` and perturbed by random
+/// noise. The noise comes from a seeded generator, so the same settings always give the same
+/// trace. `evaluate` measures how well a configured `TimeContext` predicts the end of a trace,
+/// e.g. to choose a `Preset`.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{Preset, SystemTimer, TimeContext, TraceGenerator, Workload};
+///   let trace = TraceGenerator::new(Workload::Bursty).generate();
+///
+///   let mut ctx = TimeContext::new(SystemTimer::new());
+///   ctx.apply_preset(Preset::Bursty, 1000);
+///   println!("average error: {} ms", TraceGenerator::evaluate(&trace, &mut ctx));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct TraceGenerator {
+    workload: Workload,
+    total: u64,           // Progress at the end of the trace.
+    duration: u64,        // Time at the end of the trace.
+    steps: usize,         // Number of updates.
+    jitter: f64,          // Largest relative deviation of the speed of a step.
+    seed: u64,
+}
+
+impl TraceGenerator {
+    /// Creates a generator of traces of `workload`, by default with 1000 updates over 100000 units
+    /// of time (100 seconds in milliseconds), up to a total of 1000000, with 20% of noise.
+    pub fn new(workload: Workload) -> TraceGenerator {
+        TraceGenerator {
+            workload,
+            total: 1_000_000u64,
+            duration: 100_000u64,
+            steps: 1000,
+            jitter: 0.2f64,
+            seed: 1u64,
+        }
+    }
+
+    /// Sets the progress value at the end of the trace, which is also the maximum progress value
+    /// of all updates.
+    pub fn set_total(&mut self, total: u64) {
+        self.total = total;
+    }
+
+    /// Sets the time of the last update.
+    pub fn set_duration(&mut self, duration: u64) {
+        self.duration = duration;
+    }
+
+    /// Sets the number of updates, which are evenly spaced in time.
+    pub fn set_steps(&mut self, steps: usize) {
+        self.steps = steps.max(1);
+    }
+
+    /// Sets the noise: the speed of every step is multiplied by a random factor between
+    /// `1 - jitter` and `1 + jitter`. It's limited to the range from 0 to 1.
+    pub fn set_jitter(&mut self, jitter: f64) {
+        self.jitter = jitter.clamp(0f64, 1f64);
+    }
+
+    /// Sets the seed of the noise. Different seeds give different traces of the same shape.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Returns the workload the traces are shaped by.
+    pub fn get_workload(&self) -> Workload { self.workload }
+
+    /// Generates a trace.
+    pub fn generate(&self) -> SessionLog {
+        let mut random = self.seed;
+        let mut weights = Vec::with_capacity(self.steps);
+
+        for step in 0..self.steps {
+            let time = (step as f64 + 0.5f64) / self.steps as f64;
+            let noise = 1f64 + self.jitter * (2f64 * next_random(&mut random) - 1f64);
+            weights.push(self.workload.speed(time) * noise);
+        }
+
+        let sum: f64 = weights.iter().sum();
+        let mut log = SessionLog::new();
+        let mut done = 0f64;
+
+        log.push(LogEntry { timestamp: 0u64, current: 0u64, total: Some(self.total) });
+
+        for (step, weight) in weights.iter().enumerate() {
+            done += weight;
+
+            let step = step as u64 + 1;
+            let steps = self.steps as u64;
+            let current = if step == steps {
+                // Exactly at the end, without rounding errors.
+                self.total
+            } else if sum > 0f64 {
+                (self.total as f64 * (done / sum)) as u64
+            } else {
+                0u64
+            };
+
+            log.push(LogEntry {
+                timestamp: (self.duration as u128 * step as u128 / steps as u128) as u64,
+                current,
+                total: Some(self.total),
+            });
+        }
+
+        log
+    }
+
+    /// Replays `trace` through `ctx` and returns the average absolute error of the remaining time,
+    /// in units of time, over the updates where `ctx` had an estimate. The end of the trace is
+    /// the time of its last update. Returns 0 if `ctx` never had an estimate.
+    pub fn evaluate<T, W>(trace: &SessionLog, ctx: &mut TimeContext<T, W>) -> f64
+        where T: GetTimestamp, W: Window
+    {
+        let end = match trace.get_entries().last() {
+            Some(last) => last.timestamp,
+            None => return 0f64,
+        };
+
+        let mut error = 0f64;
+        let mut count = 0u64;
+
+        for entry in trace.get_entries() {
+            entry.feed(ctx);
+
+            let remaining = ctx.get_remaining_time();
+
+            if remaining > 0 && remaining < MAX_REMAINING_TIME {
+                let actual = end.saturating_sub(entry.timestamp) as f64;
+                error += (remaining as f64 - actual).abs();
+                count += 1;
+            }
+        }
+
+        if count == 0 { 0f64 } else { error / count as f64 }
+    }
+}
+
+// A xorshift64* generator, returning values from 0 to 1.
+fn next_random(state: &mut u64) -> f64 {
+    if *state == 0 {
+        *state = 0x9e37_79b9_7f4a_7c15u64;
+    }
+
+    *state ^= *state >> 12;
+    *state ^= *state << 25;
+    *state ^= *state >> 27;
+
+    let value = state.wrapping_mul(0x2545_f491_4f6c_dd1du64);
+    (value >> 11) as f64 / (1u64 << 53) as f64
+}
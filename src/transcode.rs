@@ -0,0 +1,120 @@
+use crate::{GetTimestamp, Snapshot, TimeContext};
+
+/// A copy of the values calculated by a `TranscodeContext` at some point in time.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TranscodeSnapshot {
+    /// The snapshot of the input, which the progress and the remaining time are based on.
+    pub input: Snapshot,
+
+    /// Currently measured speed of the input, in bytes per unit of time.
+    pub input_per_unit: f64,
+
+    /// Currently measured speed of the output, in bytes per unit of time.
+    pub output_per_unit: f64,
+
+    /// Bytes written so far.
+    pub output: u64,
+
+    /// Output bytes per input byte so far, e.g. the compression ratio.
+    pub ratio: f64,
+}
+
+/// An estimator for compression and transcoding jobs, which tracks the input consumed and the
+/// output produced.
+///
+/// The input is the primary counter: its size is known, so the progress and the remaining time
+/// are derived from it only. The size of the output usually isn't known until the end, so only
+/// its rate is measured (see `TimeContext::update_count`), which is what most tools report as
+/// their throughput.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{SystemTimer, TranscodeContext};
+///   # use std::io::{Cursor, Write};
+///   # struct Decoder(u64);
+///   # impl Decoder {
+///   #     fn next(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+///   #         self.0 += 1;
+///   #         Ok(if self.0 <= 3 { Some(vec![0u8; 4]) } else { None })
+///   #     }
+///   #     fn position(&self) -> u64 { self.0.min(3) * 4 }
+///   # }
+///   # struct Encoder;
+///   # impl Encoder {
+///   #     fn encode(&mut self, frame: &[u8]) -> std::io::Result<Vec<u8>> {
+///   #         Ok(frame[..2].to_vec())
+///   #     }
+///   # }
+///   # fn main() -> std::io::Result<()> {
+///   # let (mut decoder, mut encoder, mut output) = (Decoder(0), Encoder, Cursor::new(Vec::new()));
+///   # let input_size = 12;
+///   let mut ctx = TranscodeContext::new(SystemTimer::new());
+///
+///   while let Some(frame) = decoder.next()? {
+///       output.write_all(&encoder.encode(&frame)?)?;
+///       ctx.update(decoder.position(), input_size, output.position());
+///   }
+///   # Ok(())
+///   # }
+/// ```
+pub struct TranscodeContext<T> {
+    input: TimeContext<T>,
+    output: TimeContext<T>,
+}
+
+impl<T: GetTimestamp + Clone> TranscodeContext<T> {
+    /// Creates a `TranscodeContext` which will use a user-supplied `GetTimestamp` struct.
+    pub fn new(timefunc: T) -> TranscodeContext<T> {
+        TranscodeContext {
+            input: TimeContext::new(timefunc.clone()),
+            output: TimeContext::new(timefunc),
+        }
+    }
+
+    /// Updates the input consumed so far, out of `max_input` bytes, and the output produced so
+    /// far.
+    pub fn update(&mut self, input: u64, max_input: u64, output: u64) {
+        self.input.update_eta(input, max_input);
+        self.output.update_count(output);
+    }
+
+    /// Returns the remaining time (ETA), estimated from the input. See
+    /// `TimeContext::get_remaining_time`.
+    pub fn get_remaining_time(&self) -> i64 {
+        self.input.get_remaining_time()
+    }
+
+    /// Returns the currently measured speed of the input, in bytes per unit of time.
+    pub fn get_input_rate(&self) -> f64 { self.input.calc_throughput() }
+
+    /// Returns the currently measured speed of the output, in bytes per unit of time.
+    pub fn get_output_rate(&self) -> f64 { self.output.calc_throughput() }
+
+    /// Returns the number of output bytes per input byte so far, or 0 before any input was
+    /// consumed.
+    pub fn get_ratio(&self) -> f64 {
+        match self.input.get_current() {
+            0 => 0f64,
+            input => self.output.get_current() as f64 / input as f64,
+        }
+    }
+
+    /// Returns a `TranscodeSnapshot` of the values calculated by this `TranscodeContext`.
+    pub fn snapshot(&self) -> TranscodeSnapshot {
+        TranscodeSnapshot {
+            input: self.input.snapshot(),
+            input_per_unit: self.get_input_rate(),
+            output_per_unit: self.get_output_rate(),
+            output: self.output.get_current(),
+            ratio: self.get_ratio(),
+        }
+    }
+
+    /// Gets a reference to the `TimeContext` tracking the input.
+    pub fn get_input(&self) -> &TimeContext<T> { &self.input }
+
+    /// Gets a mutable reference to the `TimeContext` tracking the input, e.g. to add observers.
+    pub fn get_input_mut(&mut self) -> &mut TimeContext<T> { &mut self.input }
+}
@@ -0,0 +1,196 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{GetTimestamp, TimeContext};
+
+// Derived progress is fed to the parent's context with this resolution.
+const SCALE: u64 = 1_000_000u64;
+
+/// A node in a hierarchical task tree.
+///
+/// Leaves are updated directly with `update_eta`, just like a `TimeContext`. A node which has
+/// children derives its progress from them: it's the weighted average of the children's progress,
+/// and it's measured by the node's own context, so every level of the tree has its own ETA.
+/// The tree can be arbitrarily deep.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{SystemTimer, Tracker};
+///   let mut install = Tracker::new("install", SystemTimer::new());
+///   install.add_child("download", 3.0);
+///   install.add_child("extract", 1.0);
+///
+///   // File 31/210 of the "extract" phase:
+///   install.update_path(&[1], 31, 210);
+///
+///   let snapshot = install.snapshot();
+/// ```
+pub struct Tracker<T> {
+    label: String,
+    weight: f64,          // Share of this node in its parent's progress.
+    ctx: TimeContext<T>,
+    cur: u64,             // Own progress, used by leaves.
+    max: u64,
+    children: Vec<Tracker<T>>,
+}
+
+/// A snapshot of a `Tracker` and all of its descendants.
+#[derive(Clone, Debug)]
+pub struct TaskSnapshot {
+    /// Label of the node.
+    pub label: String,
+
+    /// For leaves, the current progress value. For other nodes, the number of finished children.
+    pub current: u64,
+
+    /// For leaves, the maximum progress value. For other nodes, the number of children.
+    pub total: u64,
+
+    /// Progress of the node, in percent.
+    pub progress: f64,
+
+    /// Remaining time of the node. See `TimeContext::get_remaining_time`.
+    pub remaining: i64,
+
+    /// Snapshots of the children.
+    pub children: Vec<TaskSnapshot>,
+}
+
+impl<T: GetTimestamp + Clone> Tracker<T> {
+    /// Creates a new root node, which will use a user-supplied `GetTimestamp` struct.
+    pub fn new(label: &str, timefunc: T) -> Tracker<T> {
+        Tracker::with_weight(label, 1.0f64, timefunc)
+    }
+
+    fn with_weight(label: &str, weight: f64, timefunc: T) -> Tracker<T> {
+        Tracker {
+            label: label.to_string(),
+            weight: if weight > 0f64 { weight } else { 0f64 },
+            ctx: TimeContext::new(timefunc),
+            cur: 0u64,
+            max: 0u64,
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child with a given `weight` and returns a mutable reference to it.
+    ///
+    /// The child uses a copy of this node's `GetTimestamp` struct.
+    pub fn add_child(&mut self, label: &str, weight: f64) -> &mut Tracker<T> {
+        let timefunc = self.ctx.get_timefunc().clone();
+        self.children.push(Tracker::with_weight(label, weight, timefunc));
+
+        let last = self.children.len() - 1;
+        &mut self.children[last]
+    }
+
+    /// Returns the label of this node.
+    pub fn get_label(&self) -> &str { &self.label }
+
+    /// Gets a reference to the child at `index`.
+    pub fn child(&self, index: usize) -> Option<&Tracker<T>> {
+        self.children.get(index)
+    }
+
+    /// Gets a mutable reference to the child at `index`.
+    ///
+    /// Updating the child this way doesn't update this node; call `refresh` afterwards, or use
+    /// `update_path` instead.
+    pub fn child_mut(&mut self, index: usize) -> Option<&mut Tracker<T>> {
+        self.children.get_mut(index)
+    }
+
+    /// Updates the progress of this node. It's meant for leaves; the progress of a node which has
+    /// children is derived from them, and this call is ignored for such nodes.
+    pub fn update_eta(&mut self, cur_prog: u64, max_prog: u64) {
+        if self.children.is_empty() {
+            self.cur = cur_prog;
+            self.max = max_prog;
+            self.ctx.update_eta(cur_prog, max_prog);
+        }
+    }
+
+    /// Updates the leaf found by following `path` (a list of child indices) from this node, and
+    /// then refreshes every node on the way back up.
+    ///
+    /// Does nothing if the path doesn't exist.
+    pub fn update_path(&mut self, path: &[usize], cur_prog: u64, max_prog: u64) {
+        match path.split_first() {
+            None => self.update_eta(cur_prog, max_prog),
+            Some((&index, rest)) => {
+                match self.children.get_mut(index) {
+                    Some(child) => child.update_path(rest, cur_prog, max_prog),
+                    None => return,
+                }
+                self.refresh_self();
+            }
+        }
+    }
+
+    /// Recalculates the derived progress of this node and all of its descendants.
+    pub fn refresh(&mut self) {
+        for child in self.children.iter_mut() {
+            child.refresh();
+        }
+
+        self.refresh_self();
+    }
+
+    fn refresh_self(&mut self) {
+        if self.children.is_empty() {
+            return;
+        }
+
+        let progress = self.get_progress();
+        self.ctx.update_eta((progress / 100.0f64 * SCALE as f64) as u64, SCALE);
+    }
+
+    /// Returns the progress of this node, in percent.
+    pub fn get_progress(&self) -> f64 {
+        if self.children.is_empty() {
+            if self.max == 0 {
+                // Like a `TimeContext`, an empty leaf is finished once it's updated.
+                return self.ctx.get_percent();
+            }
+
+            let progress = self.cur as f64 * 100.0f64 / self.max as f64;
+            return if progress > 100.0f64 { 100.0f64 } else { progress };
+        }
+
+        let total = self.children.iter().fold(0f64, |acc, c| acc + c.weight);
+        if total <= 0f64 {
+            return 0f64;
+        }
+
+        let sum = self.children.iter().fold(0f64, |acc, c| acc + c.get_progress() * c.weight);
+        sum / total
+    }
+
+    /// Returns `true` if this node reached 100%.
+    pub fn is_finished(&self) -> bool {
+        self.get_progress() >= 100.0f64
+    }
+
+    /// Gets a reference to the context measuring this node.
+    pub fn get_context(&self) -> &TimeContext<T> { &self.ctx }
+
+    /// Returns a snapshot of this node and all of its descendants.
+    pub fn snapshot(&self) -> TaskSnapshot {
+        let (current, total) = if self.children.is_empty() {
+            (self.cur, self.max)
+        } else {
+            let finished = self.children.iter().filter(|c| c.is_finished()).count();
+            (finished as u64, self.children.len() as u64)
+        };
+
+        TaskSnapshot {
+            label: self.label.clone(),
+            current,
+            total,
+            progress: self.get_progress(),
+            remaining: self.ctx.get_remaining_time(),
+            children: self.children.iter().map(|c| c.snapshot()).collect(),
+        }
+    }
+}
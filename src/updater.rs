@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::{GetProgress, GetTimestamp, TimeContext};
+
+/// A handle to the background thread started by `spawn_updater`.
+///
+/// Dropping the handle detaches the thread; it will keep running until the tracked operation is
+/// finished. Use `stop` to terminate it earlier.
+pub struct Updater {
+    running: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl Updater {
+    /// Stops the background thread and waits for it to exit.
+    pub fn stop(self) {
+        self.running.store(false, Ordering::Relaxed);
+        let _ = self.thread.join();
+    }
+
+    /// Returns `true` if the background thread is still polling the progress source.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed)
+    }
+}
+
+/// Starts a thread which periodically polls a progress source and updates a shared context.
+///
+/// Every `interval`, `source` is asked for the current `(cur_prog, max_prog)` pair, and the
+/// result is passed to the context's `update_eta` method. Any `GetProgress` implementation can be
+/// used as the source, including a closure or a shared `AtomicProgress`. The thread exits by
+/// itself after it observes `cur_prog >= max_prog`.
+///
+/// This is useful when the work can't be instrumented inline, e.g. when the program waits for an
+/// external process to finish.
+pub fn spawn_updater<T, P>(ctx: Arc<Mutex<TimeContext<T>>>, interval: Duration, source: P)
+    -> Updater
+    where T: GetTimestamp + Send + 'static,
+          P: GetProgress + Send + 'static {
+    let running = Arc::new(AtomicBool::new(true));
+    let flag = running.clone();
+
+    let thread = thread::spawn(move || {
+        while flag.load(Ordering::Relaxed) {
+            let (cur_prog, max_prog) = source.get_progress();
+
+            if let Ok(mut ctx) = ctx.lock() {
+                ctx.update_eta(cur_prog, max_prog);
+            }
+
+            if cur_prog >= max_prog {
+                break;
+            }
+
+            thread::sleep(interval);
+        }
+
+        flag.store(false, Ordering::Relaxed);
+    });
+
+    Updater {
+        running,
+        thread,
+    }
+}
@@ -0,0 +1,41 @@
+use wasm_bindgen::prelude::*;
+
+use crate::GetTimestamp;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+/// A `GetTimestamp` implementation backed by the browser's `performance.now()`.
+///
+/// The timestamps are milliseconds elapsed since the timer was created, like the ones of
+/// `SystemTimer`, which can't be used on `wasm32-unknown-unknown` because the standard library
+/// has no clock there. It works both in windows and in workers. Available only with the `wasm`
+/// feature.
+#[derive(Copy, Clone, Debug)]
+pub struct PerformanceTimer {
+    start: f64,
+}
+
+impl PerformanceTimer {
+    /// Creates a new timer, starting at 0.
+    pub fn new() -> PerformanceTimer {
+        PerformanceTimer { start: performance_now() }
+    }
+}
+
+impl Default for PerformanceTimer {
+    fn default() -> PerformanceTimer {
+        PerformanceTimer::new()
+    }
+}
+
+impl GetTimestamp for PerformanceTimer {
+    /// Returns the number of milliseconds elapsed since the timer was created.
+    fn get_timestamp(&self) -> u64 {
+        let elapsed = performance_now() - self.start;
+        if elapsed > 0f64 { elapsed as u64 } else { 0u64 }
+    }
+}
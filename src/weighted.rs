@@ -0,0 +1,150 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{clamp_time, GetTimestamp, TimeContext};
+
+/// A tracker for a list of items of different sizes, like files of a copy or tasks with
+/// estimated costs, which are processed one at a time.
+///
+/// The weights of all items are given upfront, and the progress advances by the weight of every
+/// completed item. The time an item takes is modeled as a fixed overhead plus a cost per unit of
+/// weight, fitted to the durations of the completed items; the remaining time is the sum of the
+/// model over the remaining items. So a list which ends with many tiny files gets a longer ETA
+/// than one which ends with a single big file of the same total size, unlike with an estimate
+/// which only looks at the total weight.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{SystemTimer, WeightedItems};
+///   # fn copy(_file: &[u8]) -> std::io::Result<()> { Ok(()) }
+///   # fn main() -> std::io::Result<()> {
+///   # let files = vec![vec![0u8; 10], vec![0u8; 30]];
+///   let sizes = files.iter().map(|f| f.len() as u64).collect();
+///   let mut progress = WeightedItems::new(sizes, SystemTimer::new());
+///
+///   for (index, file) in files.iter().enumerate() {
+///       copy(file)?;
+///       progress.complete(index);
+///       println!("{:.1}%, {} s left", progress.get_progress(),
+///                progress.get_remaining_time() / 1000);
+///   }
+///   # Ok(())
+///   # }
+/// ```
+pub struct WeightedItems<T> {
+    ctx: TimeContext<T>,  // Measures the weight done, which gives the snapshot and a fallback.
+    weights: Vec<u64>,
+    done: Vec<bool>,      // Completed items, indexed like `weights`.
+    done_weight: u64,
+    total_weight: u64,
+    remaining_items: usize,
+    boundary: u64,        // End of the previous item, or the creation of the tracker.
+    fit: [f64; 5],        // Sums of the fit: n, w, d, w*w, w*d.
+}
+
+impl<T: GetTimestamp> WeightedItems<T> {
+    /// Creates a tracker for items with the given `weights`. The time is measured from now.
+    pub fn new(weights: Vec<u64>, timefunc: T) -> WeightedItems<T> {
+        let total_weight = weights.iter().fold(0u64, |total, w| total.saturating_add(*w));
+        let mut ctx = TimeContext::new(timefunc);
+        ctx.update_eta(0u64, total_weight);
+
+        WeightedItems {
+            boundary: ctx.get_timefunc().get_timestamp(),
+            ctx,
+            done: vec![false; weights.len()],
+            remaining_items: weights.len(),
+            weights,
+            done_weight: 0u64,
+            total_weight,
+            fit: [0f64; 5],
+        }
+    }
+
+    /// Marks the item at `index` as completed. Does nothing if there's no such item, or if it
+    /// was already completed.
+    pub fn complete(&mut self, index: usize) {
+        if self.done.get(index) != Some(&false) {
+            return;
+        }
+
+        let now = self.ctx.get_timefunc().get_timestamp();
+        let weight = self.weights[index];
+        let (w, d) = (weight as f64, now.saturating_sub(self.boundary) as f64);
+
+        for (sum, value) in self.fit.iter_mut().zip([1f64, w, d, w * w, w * d].iter()) {
+            *sum += value;
+        }
+
+        self.done[index] = true;
+        self.done_weight = self.done_weight.saturating_add(weight);
+        self.remaining_items -= 1;
+        self.boundary = now;
+        self.ctx.update_eta(self.done_weight, self.total_weight);
+    }
+
+    /// Marks the first item which isn't completed yet as completed, and returns its index.
+    pub fn complete_next(&mut self) -> Option<usize> {
+        let index = self.done.iter().position(|done| !done)?;
+        self.complete(index);
+        Some(index)
+    }
+
+    /// Returns the number of items which aren't completed yet.
+    pub fn get_remaining_items(&self) -> usize { self.remaining_items }
+
+    /// Returns the progress, in percent of the total weight.
+    pub fn get_progress(&self) -> f64 {
+        if self.total_weight == 0 {
+            return if self.remaining_items == 0 { 100.0f64 } else { 0f64 };
+        }
+
+        self.done_weight as f64 * 100.0f64 / self.total_weight as f64
+    }
+
+    /// Returns the fitted model of the duration of an item: the fixed overhead per item, and the
+    /// cost per unit of weight, both in units of time. While the completed items don't differ in
+    /// weight, there's no overhead and the whole duration is attributed to the weight.
+    pub fn get_model(&self) -> (f64, f64) {
+        let [n, w, d, ww, wd] = self.fit;
+        let spread = n * ww - w * w;
+
+        if n >= 2f64 && spread > 0f64 {
+            let cost = (n * wd - w * d) / spread;
+            let overhead = (d - cost * w) / n;
+
+            // A negative part would let large or many items take no time at all, so the model
+            // falls back to the other part alone.
+            if cost >= 0f64 && overhead >= 0f64 {
+                return (overhead, cost);
+            } else if cost < 0f64 {
+                return (d / n, 0f64);
+            }
+        }
+
+        if w > 0f64 { (0f64, d / w) } else if n > 0f64 { (d / n, 0f64) } else { (0f64, 0f64) }
+    }
+
+    /// Returns the remaining time (ETA), from the model of the duration of an item applied to
+    /// every item which isn't completed yet, minus the time the current item has taken so far.
+    /// Returns 0 before the first item is completed.
+    pub fn get_remaining_time(&self) -> i64 {
+        if self.fit[0] == 0f64 || self.remaining_items == 0 {
+            return 0;
+        }
+
+        let (overhead, cost) = self.get_model();
+        let weight = self.total_weight.saturating_sub(self.done_weight) as f64;
+        let remaining = overhead * self.remaining_items as f64 + cost * weight;
+
+        let now = self.ctx.get_timefunc().get_timestamp();
+        let current = now.saturating_sub(self.boundary) as f64;
+
+        // The ETA shouldn't reach 0 while items are left, even if the current one is slow.
+        clamp_time((remaining - current).max(1f64))
+    }
+
+    /// Gets a reference to the `TimeContext` measuring the completed weight.
+    pub fn get_context(&self) -> &TimeContext<T> { &self.ctx }
+}
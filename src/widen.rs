@@ -0,0 +1,94 @@
+use core::cell::Cell;
+
+use crate::GetTimestamp;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type narrower than `u64`, as read from a hardware counter.
+///
+/// This trait is sealed; it's implemented for `u8`, `u16` and `u32`.
+pub trait Narrow: Copy + private::Sealed {
+    #[doc(hidden)]
+    const BITS: u32;
+
+    #[doc(hidden)]
+    fn widen(self) -> u64;
+}
+
+macro_rules! impl_narrow {
+    ($($t:ident),*) => {
+        $(
+            impl private::Sealed for $t {}
+
+            impl Narrow for $t {
+                const BITS: u32 = $t::BITS;
+
+                fn widen(self) -> u64 { self as u64 }
+            }
+        )*
+    }
+}
+
+impl_narrow!(u8, u16, u32);
+
+/// A `GetTimestamp` implementation which widens the value of a narrow, wrapping hardware
+/// counter (e.g. a 16-bit timer register) to 64 bits.
+///
+/// `read` is called for every timestamp, and a rollover of the counter is detected when the value
+/// is lower than the previous one. This only works if the timer is read at least once per period
+/// of the counter (e.g. every 65.5 ms for a 16-bit counter running at 1 MHz), so `update_eta`
+/// needs to be called often enough, or the timer can be read explicitly with `get_timestamp`
+/// (e.g. from a periodic interrupt).
+///
+/// The last value is kept in a `Cell`, so the timer is not `Sync`.
+///
+/// # Example
+///
+/// ```rust
+///   # use gaeta::{FixedTimeContext, WideTimer};
+///   # struct Bits(u32);
+///   # impl Bits { fn bits(&self) -> u32 { self.0 } }
+///   # struct Counter;
+///   # impl Counter { fn read(&self) -> Bits { Bits(0) } }
+///   # struct Timer { cnt: Counter }
+///   # static TIM2: Timer = Timer { cnt: Counter };
+///   let timer = WideTimer::new(|| TIM2.cnt.read().bits() as u16);
+///   let mut ctx = FixedTimeContext::<_, 8>::with_window(timer);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WideTimer<F> {
+    read: F,
+    last: Cell<u64>,      // The last widened timestamp.
+}
+
+impl<N: Narrow, F: Fn() -> N> WideTimer<F> {
+    /// Creates a new timer, reading the raw counter value with `read`.
+    pub fn new(read: F) -> WideTimer<F> {
+        let first = read().widen();
+        WideTimer {
+            read,
+            last: Cell::new(first),
+        }
+    }
+}
+
+impl<N: Narrow, F: Fn() -> N> GetTimestamp for WideTimer<F> {
+    /// Returns the value of the counter, widened to 64 bits.
+    fn get_timestamp(&self) -> u64 {
+        let raw = (self.read)().widen();
+        let mask = (1u64 << N::BITS) - 1;
+
+        let last = self.last.get();
+        let mut high = last & !mask;
+
+        if raw < last & mask {
+            high = high.wrapping_add(mask + 1);
+        }
+
+        let now = high | raw;
+        self.last.set(now);
+        now
+    }
+}
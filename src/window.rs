@@ -0,0 +1,157 @@
+use alloc::collections::VecDeque;
+
+use crate::Sample;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Storage of the recent samples used by a `TimeContext` to smooth its estimate.
+///
+/// This trait is sealed; the crate provides two implementations:
+///
+/// * `FixedWindow<N>`, which keeps up to `N` samples in an inline array and never allocates,
+///   suitable for allocation-free embedded and realtime environments; `DefaultWindow`, used by
+///   `TimeContext` unless specified otherwise, is a `FixedWindow` of 10 samples,
+/// * `HeapWindow`, which keeps the samples in a heap-allocated ring buffer, so moving the
+///   context around doesn't copy them.
+pub trait Window: private::Sealed {
+    #[doc(hidden)]
+    fn new() -> Self;
+
+    #[doc(hidden)]
+    fn limit(&self) -> usize;
+
+    #[doc(hidden)]
+    fn len(&self) -> usize;
+
+    #[doc(hidden)]
+    fn get(&self, index: usize) -> Option<&Sample>;
+
+    #[doc(hidden)]
+    fn push_back(&mut self, sample: Sample);
+
+    #[doc(hidden)]
+    fn pop_front(&mut self) -> Option<Sample>;
+
+    #[doc(hidden)]
+    fn back(&self) -> Option<&Sample> {
+        if self.len() == 0 { None } else { self.get(self.len() - 1) }
+    }
+
+    #[doc(hidden)]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[doc(hidden)]
+    fn iter(&self) -> Iter<'_, Self> where Self: Sized {
+        Iter { window: self, index: 0 }
+    }
+}
+
+/// An iterator over the samples of a `Window`, from the oldest to the newest.
+pub struct Iter<'a, W: 'a> {
+    window: &'a W,
+    index: usize,
+}
+
+impl<'a, W: Window> Iterator for Iter<'a, W> {
+    type Item = &'a Sample;
+
+    fn next(&mut self) -> Option<&'a Sample> {
+        let sample = self.window.get(self.index);
+        self.index += 1;
+        sample
+    }
+}
+
+/// A `Window` keeping the 10 most recent samples in a heap-allocated ring buffer.
+///
+/// The buffer is allocated once, when the window is created; it never grows afterwards.
+#[derive(Clone, Debug)]
+pub struct HeapWindow {
+    buf: VecDeque<Sample>,
+}
+
+impl private::Sealed for HeapWindow {}
+
+impl Window for HeapWindow {
+    fn new() -> HeapWindow {
+        HeapWindow { buf: VecDeque::with_capacity(10) }
+    }
+
+    fn limit(&self) -> usize { 10 }
+
+    fn len(&self) -> usize { self.buf.len() }
+
+    fn get(&self, index: usize) -> Option<&Sample> { self.buf.get(index) }
+
+    fn push_back(&mut self, sample: Sample) { self.buf.push_back(sample) }
+
+    fn pop_front(&mut self) -> Option<Sample> { self.buf.pop_front() }
+}
+
+/// The `Window` used by `TimeContext` by default: the 10 most recent samples, kept inline.
+pub type DefaultWindow = FixedWindow<10>;
+
+/// A `Window` keeping up to `N` most recent samples in an inline array.
+///
+/// It never allocates, so a `TimeContext` using it (see `FixedTimeContext`) can be used where
+/// heap allocation is not available or not allowed.
+#[derive(Copy, Clone)]
+pub struct FixedWindow<const N: usize> {
+    buf: [Sample; N],
+    head: usize,          // Index of the oldest sample.
+    len: usize,           // Number of stored samples.
+}
+
+impl<const N: usize> private::Sealed for FixedWindow<N> {}
+
+impl<const N: usize> Window for FixedWindow<N> {
+    fn new() -> FixedWindow<N> {
+        FixedWindow {
+            buf: [Sample { timestamp: 0u64, current: 0u64 }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn limit(&self) -> usize { N }
+
+    fn len(&self) -> usize { self.len }
+
+    fn get(&self, index: usize) -> Option<&Sample> {
+        if index < self.len { Some(&self.buf[(self.head + index) % N]) } else { None }
+    }
+
+    fn push_back(&mut self, sample: Sample) {
+        if N == 0 {
+            return;
+        }
+
+        if self.len == N {
+            self.pop_front();
+        }
+
+        self.buf[(self.head + self.len) % N] = sample;
+        self.len += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<Sample> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let sample = self.buf[self.head];
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        Some(sample)
+    }
+}
+
+impl<const N: usize> ::core::fmt::Debug for FixedWindow<N> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,103 @@
+use gaeta::{Aggregate, Mode, TestTimer, TimeContext};
+
+// Updates the item `index` of `aggregate` at `ts`.
+fn update_at(aggregate: &mut Aggregate<TestTimer>, index: usize, ts: u64, cur_prog: u64,
+             max_prog: u64) {
+    aggregate.get_mut(index).unwrap().get_timefunc_mut().set_timestamp(ts);
+    aggregate.update_eta(index, cur_prog, max_prog);
+}
+
+// Returns an aggregate of a fast item (10% done in 10 units of time) and a slow one (5% done).
+fn two_items() -> Aggregate<TestTimer> {
+    two_weighted_items(1.0, 1.0)
+}
+
+// Like `two_items`, with the fast item weighing `fast` and the slow one `slow`.
+fn two_weighted_items(fast: f64, slow: f64) -> Aggregate<TestTimer> {
+    let mut aggregate = Aggregate::new();
+    aggregate.add_weighted(TimeContext::new(TestTimer::new()), fast);
+    aggregate.add_weighted(TimeContext::new(TestTimer::new()), slow);
+
+    update_at(&mut aggregate, 0, 0, 0, 100);
+    update_at(&mut aggregate, 0, 10, 10, 100);
+    update_at(&mut aggregate, 1, 0, 0, 100);
+    update_at(&mut aggregate, 1, 10, 5, 100);
+    aggregate
+}
+
+#[test]
+fn averages_items() {
+    let aggregate = two_items();
+    assert_eq!(aggregate.len(), 2);
+    assert_eq!(aggregate.get_progress(), 7.5f64);
+    assert_eq!(aggregate.calc_speed_per_unit(), 0.75f64);
+    assert_eq!(aggregate.get_remaining_time(), 123);
+}
+
+#[test]
+fn finished_items_do_not_add_speed() {
+    let mut aggregate = two_items();
+    update_at(&mut aggregate, 0, 20, 100, 100);
+
+    assert_eq!(aggregate.get_progress(), 52.5f64);
+    assert_eq!(aggregate.calc_speed_per_unit(), 0.25f64);
+    assert_eq!(aggregate.get_remaining_time(), 190);
+}
+
+#[test]
+fn unknown_index_is_ignored() {
+    let mut aggregate = two_items();
+    aggregate.update_eta(5, 50, 100);
+    assert!(aggregate.get(5).is_none());
+    assert_eq!(aggregate.get_progress(), 7.5f64);
+}
+
+#[test]
+fn empty_aggregate() {
+    let aggregate = Aggregate::<TestTimer>::new();
+    assert!(aggregate.is_empty());
+    assert_eq!(aggregate.get_progress(), 0f64);
+    assert_eq!(aggregate.get_remaining_time(), 0);
+}
+
+#[test]
+fn weights_items() {
+    let aggregate = two_weighted_items(3.0, 1.0);
+    assert_eq!(aggregate.get_progress(), 8.75f64);
+    assert_eq!(aggregate.calc_speed_per_unit(), 0.875f64);
+    assert_eq!(aggregate.get_remaining_time(), 104);
+}
+
+#[test]
+fn negative_weight_is_zero() {
+    let aggregate = two_weighted_items(1.0, -2.0);
+    assert_eq!(aggregate.get_weight(1), Some(0f64));
+    assert_eq!(aggregate.get_progress(), 10f64);
+    assert_eq!(aggregate.calc_speed_per_unit(), 1f64);
+}
+
+#[test]
+fn zero_total_weight() {
+    let aggregate = two_weighted_items(0.0, 0.0);
+    assert_eq!(aggregate.get_progress(), 0f64);
+    assert_eq!(aggregate.calc_speed_per_unit(), 0f64);
+}
+
+#[test]
+fn slowest_item_decides() {
+    let mut aggregate = two_items();
+    aggregate.set_mode(Mode::Slowest);
+    assert_eq!(aggregate.get_mode(), Mode::Slowest);
+    assert_eq!(aggregate.get_remaining_time(), 190);
+}
+
+#[test]
+fn slowest_skips_finished_items() {
+    let mut aggregate = two_items();
+    aggregate.set_mode(Mode::Slowest);
+    update_at(&mut aggregate, 1, 20, 100, 100);
+    assert_eq!(aggregate.get_remaining_time(), 90);
+
+    update_at(&mut aggregate, 0, 20, 100, 100);
+    assert_eq!(aggregate.get_remaining_time(), 0);
+}
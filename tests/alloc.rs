@@ -0,0 +1,94 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use gaeta::{FixedTimeContext, TestTimer};
+
+// Counts the allocations made by the threads which enable it, so the test harness doesn't
+// interfere.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.with(|enabled| enabled.get()) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+// Returns the number of allocations made by `f` on this thread.
+fn count_allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    ENABLED.with(|enabled| enabled.set(true));
+    f();
+    ENABLED.with(|enabled| enabled.set(false));
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+#[test]
+fn update_eta_does_not_allocate() {
+    let mut ctx = FixedTimeContext::<_, 8>::with_window(TestTimer::new());
+    ctx.set_stall_timeout(5);
+    ctx.set_monotone_margin(10);
+    ctx.enable_stall_log(4);
+
+    let allocations = count_allocations(|| {
+        let mut update = |ts: u64, cur_prog: u64| {
+            ctx.get_timefunc_mut().set_timestamp(ts);
+            ctx.update_eta(cur_prog, 1000);
+        };
+
+        // Steady progress, filling and wrapping the window.
+        for ts in 0..100 {
+            update(ts, ts * 5);
+        }
+
+        // A stall, a repeated call, and a regression.
+        for ts in 100..120 {
+            update(ts, 500);
+        }
+        update(120, 500);
+        update(121, 300);
+
+        // Completion, and updates after it.
+        for ts in 122..150 {
+            update(ts, (300 + (ts - 121) * 30).min(1000));
+        }
+    });
+
+    assert_eq!(allocations, 0);
+    assert_eq!(ctx.get_remaining_time(), 0);
+    assert_eq!(ctx.get_stall_log().unwrap().get_count(), 1);
+}
+
+#[test]
+fn history_does_not_reallocate() {
+    for capacity in [0, 1, 3] {
+        let mut ctx = FixedTimeContext::<_, 8>::with_window(TestTimer::new());
+        ctx.enable_history(1, capacity);
+
+        let allocations = count_allocations(|| {
+            for ts in 0..100 {
+                ctx.get_timefunc_mut().set_timestamp(ts);
+                ctx.update_eta(ts * 5, 1000);
+            }
+        });
+
+        assert_eq!(allocations, 0, "capacity {}", capacity);
+    }
+}
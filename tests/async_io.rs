@@ -0,0 +1,37 @@
+#![cfg(feature = "futures")]
+
+use futures::executor::block_on;
+use futures::io::{self, AsyncReadExt, AsyncWriteExt, Cursor};
+
+use gaeta::{EtaAsyncReader, EtaAsyncWriter, TestTimer};
+
+#[test]
+fn reader_counts_bytes() {
+    let mut reader = EtaAsyncReader::new(Cursor::new(vec![7u8; 1000]), 1000, TestTimer::new());
+    let eta = reader.handle();
+
+    let mut buf = [0u8; 300];
+    block_on(reader.read_exact(&mut buf)).unwrap();
+    assert_eq!(reader.count(), 300);
+    assert_eq!(eta.snapshot().progress, 30.0f64);
+
+    let mut rest = Vec::new();
+    block_on(reader.read_to_end(&mut rest)).unwrap();
+    assert_eq!(rest.len(), 700);
+    assert_eq!(eta.snapshot().progress, 100.0f64);
+}
+
+#[test]
+fn writer_counts_bytes() {
+    let mut writer = EtaAsyncWriter::new(Cursor::new(Vec::new()), 100, TestTimer::new());
+
+    block_on(async {
+        writer.write_all(&[1u8; 40]).await?;
+        writer.flush().await?;
+        io::Result::Ok(())
+    }).unwrap();
+
+    assert_eq!(writer.count(), 40);
+    assert_eq!(writer.handle().snapshot().progress, 40.0f64);
+    assert_eq!(writer.into_inner().into_inner(), vec![1u8; 40]);
+}
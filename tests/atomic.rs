@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::thread;
+
+use gaeta::{AtomicProgress, TestTimer, TimeContext, Worker};
+
+#[test]
+fn counts_from_many_threads() {
+    let progress = Arc::new(AtomicProgress::new(8000));
+
+    let threads: Vec<_> = (0..8).map(|_| {
+        let progress = progress.clone();
+        thread::spawn(move || {
+            for _ in 0..1000 {
+                progress.inc(1);
+            }
+        })
+    }).collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(progress.get(), (8000, 8000));
+}
+
+#[test]
+fn set_and_set_max() {
+    let progress = AtomicProgress::new(100);
+    progress.inc(30);
+    progress.set(10);
+    progress.set_max(50);
+    assert_eq!(progress.get(), (10, 50));
+}
+
+#[test]
+fn sample_feeds_context() {
+    let progress = AtomicProgress::new(100);
+    let mut ctx = TimeContext::new(TestTimer::new());
+
+    for ts in 0..=10 {
+        ctx.get_timefunc_mut().set_timestamp(ts * 10);
+        progress.set(ts * 2);
+        ctx.sample(&progress);
+    }
+
+    // 20 units in 100 units of time, so 80 left take 400.
+    assert_eq!(ctx.get_remaining_time(), 400);
+}
+
+#[test]
+fn unit_counts_on_every_exit() {
+    let progress = AtomicProgress::new(10);
+
+    for i in 0..3 {
+        let _unit = progress.unit();
+        if i == 1 {
+            continue;
+        }
+    }
+
+    let result = std::panic::catch_unwind(|| {
+        let _unit = progress.units(5);
+        panic!("failed item");
+    });
+
+    assert!(result.is_err());
+    assert_eq!(progress.get(), (8, 10));
+}
+
+#[test]
+fn worker_flushes_in_batches() {
+    let progress = AtomicProgress::new(100);
+    let mut worker = progress.worker(10);
+
+    worker.inc(9);
+    assert_eq!(progress.get().0, 0);
+
+    worker.inc(1);
+    assert_eq!(progress.get().0, 10);
+
+    worker.inc(25);
+    assert_eq!(progress.get().0, 35);
+
+    worker.inc(3);
+    worker.flush();
+    assert_eq!(progress.get().0, 38);
+
+    worker.inc(2);
+    drop(worker);
+    assert_eq!(progress.get().0, 40);
+}
+
+#[test]
+fn worker_without_batching_flushes_every_increment() {
+    let progress = AtomicProgress::new(100);
+    let mut worker = progress.worker(0);
+
+    worker.inc(1);
+    assert_eq!(progress.get().0, 1);
+}
+
+#[test]
+fn workers_of_shared_counter() {
+    let progress = Arc::new(AtomicProgress::new(4000));
+
+    let threads: Vec<_> = (0..4).map(|_| {
+        let mut worker = Worker::new(progress.clone(), 64);
+        thread::spawn(move || {
+            for _ in 0..1000 {
+                worker.inc(1);
+            }
+        })
+    }).collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(progress.get(), (4000, 4000));
+}
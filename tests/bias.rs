@@ -0,0 +1,57 @@
+use gaeta::{TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur: u64, max: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur, max);
+}
+
+type Pair = (TimeContext<TestTimer>, TimeContext<TestTimer>);
+
+// Feeds the same run to a context with and without the correction.
+fn run(steps: impl Iterator<Item = u64>, max: u64) -> Pair {
+    let mut plain = TimeContext::new(TestTimer::new());
+    let mut corrected = TimeContext::new(TestTimer::new());
+    corrected.enable_bias_correction();
+
+    let mut cur = 0;
+    for (ts, step) in steps.enumerate() {
+        cur += step;
+        update_at(&mut plain, ts as u64 * 10, cur, max);
+        update_at(&mut corrected, ts as u64 * 10, cur, max);
+    }
+
+    (plain, corrected)
+}
+
+#[test]
+fn disabled_by_default() {
+    let (plain, _) = run((0..40).map(|_| 100), 1_000_000);
+    assert_eq!(plain.get_bias_factor(), 1.0);
+}
+
+#[test]
+fn steady_job_is_not_corrected() {
+    let (plain, corrected) = run((0..40).map(|_| 100), 1_000_000);
+    assert!((corrected.get_bias_factor() - 1.0).abs() < 1e-9);
+    assert_eq!(corrected.get_remaining_time(), plain.get_remaining_time());
+}
+
+#[test]
+fn slowing_job_is_corrected() {
+    let (plain, corrected) = run((0..40).map(|i| 4000 - i * 90), 1_000_000);
+    let factor = corrected.get_bias_factor();
+    assert!(factor > 1.0 && factor < 4.0, "{}", factor);
+    assert!(corrected.get_remaining_time() > plain.get_remaining_time());
+
+    // A speeding up job is corrected the other way.
+    let (plain, corrected) = run((0..40).map(|i| 100 + i * 90), 1_000_000);
+    assert!(corrected.get_bias_factor() < 1.0);
+    assert!(corrected.get_remaining_time() < plain.get_remaining_time());
+}
+
+#[test]
+fn factor_is_limited() {
+    let (_, corrected) = run((0..36).map(|i| 1 << (36 - i)), 1 << 40);
+    // Each step is half of the previous one.
+    assert_eq!(corrected.get_bias_factor(), 4.0);
+}
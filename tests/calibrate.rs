@@ -0,0 +1,33 @@
+use gaeta::{TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn steady_speed_keeps_whole_window() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    assert_eq!(ctx.get_effective_window(), 9);
+    ctx.enable_calibration();
+    assert_eq!(ctx.get_effective_window(), 9);
+
+    for ts in 0..30 {
+        update_at(&mut ctx, ts * 10, ts * 10, 10_000);
+    }
+    assert_eq!(ctx.get_effective_window(), 9);
+}
+
+#[test]
+fn changing_speed_shortens_window() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_calibration();
+
+    // The speed changes every 5 intervals, so the recent ones predict the next one best.
+    let mut cur = 0;
+    for ts in 0..60 {
+        cur += if ts / 5 % 2 == 0 { 1 } else { 20 };
+        update_at(&mut ctx, ts * 10, cur, 100_000);
+    }
+    assert_eq!(ctx.get_effective_window(), 3);
+}
@@ -0,0 +1,32 @@
+use gaeta::{Status, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn cancelled_context_is_frozen() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 60, 100);
+    let before = ctx.snapshot();
+
+    ctx.cancel();
+    update_at(&mut ctx, 20, 100, 100);
+
+    let after = ctx.snapshot();
+    assert_eq!(after.status, Status::Cancelled);
+    assert_eq!(after.progress, 60f64);
+    assert_eq!(after.remaining, before.remaining);
+    assert_eq!(ctx.get_current(), 60);
+}
+
+#[test]
+fn finished_context_is_not_cancelled() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 100, 100);
+    ctx.cancel();
+    assert_eq!(ctx.get_status(), Status::Finished);
+}
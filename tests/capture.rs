@@ -0,0 +1,60 @@
+use gaeta::{Capture, TestTimer, TimeContext};
+
+#[test]
+fn splits_once() {
+    static CAPTURE: Capture<4> = Capture::new();
+
+    assert!(CAPTURE.split().is_some());
+    assert!(CAPTURE.split().is_none());
+}
+
+#[test]
+fn full_queue_drops_values() {
+    let capture = Capture::<2>::new();
+    let (mut recorder, mut reader) = capture.split().unwrap();
+
+    assert!(recorder.record(1, 10));
+    assert!(recorder.record(2, 20));
+    assert!(!recorder.record(3, 30));
+
+    assert_eq!(reader.pop(), Some((1, 10)));
+    assert!(recorder.record(4, 40));
+    assert_eq!(reader.pop(), Some((2, 20)));
+    assert_eq!(reader.pop(), Some((4, 40)));
+    assert_eq!(reader.pop(), None);
+}
+
+#[test]
+fn wraps_around() {
+    let capture = Capture::<3>::new();
+    let (mut recorder, mut reader) = capture.split().unwrap();
+
+    for i in 0..100u64 {
+        assert!(recorder.record(i, i * 10));
+        assert_eq!(reader.pop(), Some((i, i * 10)));
+    }
+}
+
+#[test]
+fn empty_queue_records_nothing() {
+    let capture = Capture::<0>::new();
+    let (mut recorder, mut reader) = capture.split().unwrap();
+
+    assert!(!recorder.record(1, 10));
+    assert_eq!(reader.pop(), None);
+}
+
+#[test]
+fn drains_into_context() {
+    let capture = Capture::<8>::new();
+    let (mut recorder, mut reader) = capture.split().unwrap();
+    let mut ctx = TimeContext::new(TestTimer::new());
+
+    for tick in 0..5u64 {
+        recorder.record(tick * 10, tick * 100);
+    }
+
+    assert_eq!(reader.drain_into(&mut ctx, 100), 5);
+    assert_eq!(reader.drain_into(&mut ctx, 100), 0);
+    assert_eq!(ctx.get_remaining_time(), 600);
+}
@@ -0,0 +1,40 @@
+use std::thread;
+
+use gaeta::{ChannelContext, ProgressEvent, TestTimer};
+
+#[test]
+fn applies_events_in_order() {
+    let mut ctx = ChannelContext::new(TestTimer::new(), 100);
+    let tx = ctx.sender();
+
+    thread::spawn(move || {
+        tx.send(ProgressEvent::Advance(10)).unwrap();
+        tx.send(ProgressEvent::Set(50, 200)).unwrap();
+        tx.send(ProgressEvent::Advance(50)).unwrap();
+    }).join().unwrap();
+
+    ctx.update();
+    assert_eq!(ctx.get_context().snapshot().progress, 50.0f64);
+}
+
+#[test]
+fn update_without_events_does_nothing() {
+    let mut ctx = ChannelContext::new(TestTimer::new(), 100);
+    ctx.update();
+    assert_eq!(ctx.get_context().get_total(), 0);
+}
+
+#[test]
+fn measures_speed_from_events() {
+    let mut ctx = ChannelContext::new(TestTimer::new(), 100);
+    let tx = ctx.sender();
+
+    for ts in 0..=10 {
+        ctx.get_context_mut().get_timefunc_mut().set_timestamp(ts * 10);
+        tx.send(ProgressEvent::Advance(if ts == 0 { 0 } else { 5 })).unwrap();
+        ctx.update();
+    }
+
+    // 50 units in 100 units of time.
+    assert_eq!(ctx.get_context().get_remaining_time(), 100);
+}
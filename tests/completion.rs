@@ -0,0 +1,69 @@
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+
+use gaeta::{Outcome, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn resolves_when_finished() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let completion = ctx.completion();
+    update_at(&mut ctx, 0, 0, 100);
+    assert_eq!(completion.try_get(), None);
+    assert_eq!(completion.wait_timeout(Duration::from_millis(1)), None);
+
+    update_at(&mut ctx, 10, 100, 100);
+    assert_eq!(completion.try_get(), Some(Outcome::Finished));
+    assert_eq!(ctx.completion().try_get(), Some(Outcome::Finished));
+}
+
+#[test]
+fn resolves_when_cancelled_or_dropped() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let cancelled = ctx.completion();
+    ctx.cancel();
+    assert_eq!(cancelled.try_get(), Some(Outcome::Cancelled));
+    assert_eq!(ctx.completion().try_get(), Some(Outcome::Cancelled));
+
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let dropped = ctx.completion();
+    drop(ctx);
+    assert_eq!(dropped.try_get(), Some(Outcome::Cancelled));
+}
+
+#[test]
+fn wait_blocks_until_resolved() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let completion = ctx.completion();
+    let waiter = thread::spawn(move || completion.wait());
+
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 100, 100);
+    assert_eq!(waiter.join().unwrap(), Outcome::Finished);
+}
+
+#[test]
+fn polls_as_future() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let mut completion = pin!(ctx.completion());
+    let mut cx = Context::from_waker(Waker::noop());
+    assert_eq!(completion.as_mut().poll(&mut cx), Poll::Pending);
+
+    ctx.cancel();
+    assert_eq!(completion.as_mut().poll(&mut cx), Poll::Ready(Outcome::Cancelled));
+}
+
+#[test]
+fn resolves_when_first_update_is_complete() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let completion = ctx.completion();
+    update_at(&mut ctx, 0, 100, 100);
+    assert_eq!(completion.try_get(), Some(Outcome::Finished));
+}
@@ -0,0 +1,81 @@
+use gaeta::{ProgressCurve, ScheduleStatus, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur: u64, max: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur, max);
+}
+
+// 10 units of time of setup, then an even copy taking 50.
+fn phased() -> TimeContext<TestTimer> {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_expected_curve(ProgressCurve::new(vec![(10, 0.0), (60, 100.0)]));
+    update_at(&mut ctx, 0, 0, 1000);
+    ctx
+}
+
+#[test]
+fn points_are_normalized() {
+    let curve = ProgressCurve::new(vec![(20, 50.0), (10, 60.0), (30, f64::NAN), (30, 120.0)]);
+    assert_eq!(curve.get_points(), [(0, 0.0), (10, 60.0), (20, 60.0), (30, 100.0)]);
+
+    assert_eq!(curve.get_percent_at(5), 30.0);
+    assert_eq!(curve.get_percent_at(15), 60.0);
+    assert_eq!(curve.get_percent_at(40), 100.0);
+
+    assert_eq!(curve.get_time_at(0.0), Some(0.0));
+    assert_eq!(curve.get_time_at(60.0), Some(10.0));
+    assert_eq!(curve.get_time_at(80.0), Some(25.0));
+    assert_eq!(ProgressCurve::new(vec![(10, 50.0)]).get_time_at(80.0), None);
+}
+
+#[test]
+fn on_schedule_within_flat_part() {
+    let mut ctx = phased();
+    update_at(&mut ctx, 5, 0, 1000);
+    assert_eq!(ctx.get_curve_status(), Some(ScheduleStatus { time: 0, percent: 0.0 }));
+    assert_eq!(ctx.get_curve_remaining_time(), 55);
+
+    update_at(&mut ctx, 35, 500, 1000);
+    assert_eq!(ctx.get_curve_status(), Some(ScheduleStatus { time: 0, percent: 0.0 }));
+    assert_eq!(ctx.get_curve_remaining_time(), 25);
+}
+
+#[test]
+fn ahead_and_behind() {
+    // 70% is expected at 45, so the rest is expected to take 15 at the pace of 35 / 45.
+    let mut ctx = phased();
+    update_at(&mut ctx, 35, 700, 1000);
+    assert_eq!(ctx.get_curve_status(), Some(ScheduleStatus { time: 10, percent: 20.0 }));
+    assert_eq!(ctx.get_curve_remaining_time(), 11);
+
+    // 20% is expected at 20, and the rest to take 40 at half the pace.
+    let mut ctx = phased();
+    update_at(&mut ctx, 40, 200, 1000);
+    assert_eq!(ctx.get_curve_status(), Some(ScheduleStatus { time: -20, percent: -40.0 }));
+    assert_eq!(ctx.get_curve_remaining_time(), 80);
+}
+
+#[test]
+fn beyond_the_curve() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    assert_eq!(ctx.get_curve_status(), None);
+
+    ctx.set_expected_curve(ProgressCurve::new(vec![(10, 50.0)]));
+    update_at(&mut ctx, 0, 0, 1000);
+    update_at(&mut ctx, 4, 800, 1000);
+    assert_eq!(ctx.get_curve_status(), Some(ScheduleStatus { time: 6, percent: 60.0 }));
+    assert_eq!(ctx.get_curve_remaining_time(), 0);
+}
+
+#[test]
+fn from_history() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_history(1, 100);
+    for (ts, cur) in [(0, 0), (10, 100), (20, 150), (30, 300)] {
+        update_at(&mut ctx, ts, cur, 400);
+    }
+
+    // The history starts at the first sample.
+    let curve = ProgressCurve::from_history(ctx.get_history().unwrap(), 200);
+    assert_eq!(curve.get_points(), [(0, 0.0), (10, 25.0), (20, 100.0)]);
+}
@@ -0,0 +1,62 @@
+use std::sync::{Arc, Mutex};
+
+use gaeta::{Observer, TestTimer, TimeContext};
+
+// Logs the projected completion times of the reported misses.
+struct Misses(Arc<Mutex<Vec<u64>>>);
+
+impl Observer for Misses {
+    fn on_deadline_missed(&mut self, projected: u64) {
+        self.0.lock().unwrap().push(projected);
+    }
+}
+
+fn observed(deadline: u64) -> (TimeContext<TestTimer>, Arc<Mutex<Vec<u64>>>) {
+    let misses = Arc::new(Mutex::new(Vec::new()));
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.add_observer(Box::new(Misses(misses.clone())));
+    ctx.set_deadline(deadline);
+    (ctx, misses)
+}
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur: u64, max: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur, max);
+}
+
+#[test]
+fn reported_once() {
+    let (mut ctx, misses) = observed(150);
+    for (ts, cur) in [(0, 0), (10, 100), (20, 200)] {
+        update_at(&mut ctx, ts, cur, 1000);
+    }
+    assert!(misses.lock().unwrap().is_empty());
+
+    // Slowing down pushes the projected completion past the deadline.
+    update_at(&mut ctx, 30, 210, 1000);
+    let projected = 30 + ctx.get_remaining_time() as u64;
+    assert!(projected > 150);
+    assert_eq!(*misses.lock().unwrap(), [projected]);
+
+    update_at(&mut ctx, 40, 220, 1000);
+    assert_eq!(misses.lock().unwrap().len(), 1);
+
+    // A new deadline can be missed again.
+    ctx.set_deadline(10_000);
+    update_at(&mut ctx, 60, 240, 1000);
+    assert_eq!(misses.lock().unwrap().len(), 1);
+    ctx.set_deadline(100);
+    update_at(&mut ctx, 70, 250, 1000);
+    assert_eq!(misses.lock().unwrap().len(), 2);
+    assert_eq!(misses.lock().unwrap()[1], 70 + ctx.get_remaining_time() as u64);
+}
+
+#[test]
+fn not_reported_when_finished() {
+    let (mut ctx, misses) = observed(5);
+    for (ts, cur) in [(0, 0), (10, 1000)] {
+        update_at(&mut ctx, ts, cur, 1000);
+    }
+    update_at(&mut ctx, 20, 1000, 1000);
+    assert!(misses.lock().unwrap().is_empty());
+}
@@ -0,0 +1,72 @@
+use std::cell::Cell;
+
+use gaeta::{Decimation, GetTimestamp, TimeContext};
+
+// A timer which counts how many times it was read.
+#[derive(Default)]
+struct CountingTimer {
+    now: Cell<u64>,
+    reads: Cell<u64>,
+}
+
+impl GetTimestamp for CountingTimer {
+    fn get_timestamp(&self) -> u64 {
+        self.reads.set(self.reads.get() + 1);
+        self.now.get()
+    }
+}
+
+fn update_at(ctx: &mut TimeContext<CountingTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc().now.set(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn every_nth_call_is_real() {
+    let mut ctx = TimeContext::new(CountingTimer::default());
+    ctx.set_decimation(Decimation::Every(3));
+    assert_eq!(ctx.get_decimation(), Decimation::Every(3));
+
+    for i in 0..5 {
+        update_at(&mut ctx, i, i, 100);
+    }
+    assert_eq!(ctx.get_timefunc().reads.get(), 2);
+    assert_eq!(ctx.get_current(), 3);
+
+    // The completion is never skipped.
+    update_at(&mut ctx, 5, 100, 100);
+    assert_eq!(ctx.get_current(), 100);
+}
+
+#[test]
+fn budget_adapts_stride() {
+    let mut ctx = TimeContext::new(CountingTimer::default());
+    ctx.set_decimation(Decimation::Budget(10));
+
+    // One call per unit of time: the stride grows until the real updates are 10 units apart.
+    for i in 0..200 {
+        update_at(&mut ctx, i, i, 1000);
+    }
+    let fast = ctx.get_timefunc().reads.get();
+    assert_eq!(fast, 16);
+
+    // One call per 100 units of time: the stride is halved on every real update, until it's back
+    // to every call.
+    for i in 0..100 {
+        update_at(&mut ctx, 200 + i * 100, 200 + i, 1000);
+    }
+    let slow = ctx.get_timefunc().reads.get() - fast;
+    assert_eq!(slow, 82);
+}
+
+#[test]
+fn fast_update_ignores_clock_and_decimation() {
+    let mut ctx = TimeContext::new(CountingTimer::default());
+    ctx.set_decimation(Decimation::Every(10));
+    ctx.update_eta_fast(0, 100, 0);
+    ctx.update_eta_fast(10, 100, 10);
+
+    assert_eq!(ctx.get_timefunc().reads.get(), 0);
+    assert_eq!(ctx.get_current(), 10);
+    assert_eq!(ctx.get_remaining_time(), 90);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "http")]
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+use http::header::{HeaderMap, HeaderValue, CONTENT_LENGTH};
+
+use gaeta::{content_length, track_body, EtaBody, TestTimer};
+
+fn headers(length: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_LENGTH, HeaderValue::from_static(length));
+    headers
+}
+
+fn chunks(sizes: &[usize]) -> impl futures::Stream<Item = io::Result<Vec<u8>>> + Unpin {
+    let chunks: Vec<io::Result<Vec<u8>>> = sizes.iter().map(|&n| Ok(vec![0u8; n])).collect();
+    stream::iter(chunks)
+}
+
+#[test]
+fn parses_content_length() {
+    assert_eq!(content_length(&headers(" 1024 ")), Some(1024));
+    assert_eq!(content_length(&headers("-1")), None);
+    assert_eq!(content_length(&headers("lots")), None);
+    assert_eq!(content_length(&HeaderMap::new()), None);
+}
+
+#[test]
+fn tracks_body_against_length() {
+    let (mut body, eta) = track_body(&headers("100"), chunks(&[25, 25]), TestTimer::new());
+    assert_eq!(body.get_total(), Some(100));
+
+    block_on(async { body.next().await });
+    assert_eq!(EtaBody::count(&body), 25);
+    assert_eq!(eta.snapshot().progress, 25f64);
+
+    // The server sent less than announced.
+    block_on(async { while body.next().await.is_some() {} });
+    assert_eq!(EtaBody::count(&body), 50);
+    assert_eq!(eta.snapshot().progress, 100f64);
+}
+
+#[test]
+fn body_without_length_is_counted() {
+    let (mut body, eta) = track_body(&HeaderMap::new(), chunks(&[10, 20]), TestTimer::new());
+    block_on(async { body.next().await });
+    assert_eq!(eta.snapshot().progress, 0f64);
+    assert_eq!(eta.with(|ctx| ctx.get_current()), 10);
+
+    body.set_total(40);
+    block_on(async { body.next().await });
+    assert_eq!(eta.snapshot().progress, 75f64);
+}
@@ -0,0 +1,35 @@
+use gaeta::{DualContext, Metric, TestTimer};
+
+fn update_at(ctx: &mut DualContext<TestTimer>, ts: u64, bytes: u64, items: u64) {
+    ctx.get_context_mut(Metric::Bytes).get_timefunc_mut().set_timestamp(ts);
+    ctx.update(bytes, 1000, items, 10);
+}
+
+#[test]
+fn steadier_metric_predicts() {
+    let mut ctx = DualContext::new(TestTimer::new());
+    assert_eq!(ctx.get_predictor(), Metric::Bytes);
+
+    // The items are processed at a steady pace, but their sizes vary a lot.
+    for (i, bytes) in [0, 100, 110, 310, 320, 520].iter().enumerate() {
+        update_at(&mut ctx, i as u64 * 10, *bytes, i as u64);
+    }
+
+    assert_eq!(ctx.get_predictor(), Metric::Items);
+    assert_eq!(ctx.get_remaining_time(), 50);
+
+    let snapshot = ctx.snapshot();
+    assert_eq!(snapshot.predictor, Metric::Items);
+    assert_eq!(snapshot.remaining, 50);
+    assert_eq!(snapshot.items_per_unit, 0.1f64);
+    assert_eq!(snapshot.items.progress, 50f64);
+}
+
+#[test]
+fn metric_with_estimate_predicts() {
+    let mut ctx = DualContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 0);
+    update_at(&mut ctx, 10, 0, 1);
+    assert_eq!(ctx.get_predictor(), Metric::Items);
+    assert_eq!(ctx.get_remaining_time(), 90);
+}
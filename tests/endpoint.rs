@@ -0,0 +1,100 @@
+use std::io::{self, Read, Write};
+
+use gaeta::{ProgressEndpoint, Snapshot, Status};
+
+fn snapshot() -> Snapshot {
+    Snapshot { progress: 42.5, speed: f64::NAN, remaining: 120, status: Status::Running }
+}
+
+const JSON: &str = "{\"progress\":42.5,\"speed\":null,\"remaining\":120,\"status\":\"running\"}";
+
+fn respond(request: &str) -> String {
+    let endpoint = ProgressEndpoint::new("/progress");
+    String::from_utf8(endpoint.respond(request.as_bytes(), &snapshot())).unwrap()
+}
+
+#[test]
+fn serves_snapshot() {
+    assert_eq!(snapshot().to_json(), JSON);
+
+    let response = respond("GET /progress?verbose=1 HTTP/1.1\r\nHost: x\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Content-Type: application/json\r\n"));
+    assert!(response.contains(&format!("Content-Length: {}\r\n", JSON.len())));
+    assert!(response.ends_with(&format!("\r\n\r\n{}", JSON)));
+
+    // The head of the response is the same, without the body.
+    let head = respond("HEAD /progress HTTP/1.1\r\n\r\n");
+    assert!(head.contains(&format!("Content-Length: {}\r\n", JSON.len())));
+    assert!(head.ends_with("\r\n\r\n"));
+}
+
+#[test]
+fn rejects_other_requests() {
+    assert!(respond("GET /other HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404 Not Found\r\n"));
+    assert!(respond("GET /progress/ HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404 "));
+
+    let response = respond("POST /progress HTTP/1.1\r\n\r\n");
+    assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    assert!(response.contains("Allow: GET, HEAD\r\n"));
+
+    assert!(respond("").starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    assert!(respond("GET\r\n\r\n").starts_with("HTTP/1.1 400 "));
+}
+
+// A connection which delivers the request in small pieces.
+struct Connection {
+    request: Vec<u8>,
+    response: Vec<u8>,
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.request.len().min(buf.len()).min(4);
+        buf[..n].copy_from_slice(&self.request[..n]);
+        self.request.drain(..n);
+        Ok(n)
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.response.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+#[test]
+fn serves_connection() {
+    let request = b"GET /progress HTTP/1.1\r\nHost: x\r\n\r\n".to_vec();
+    let mut connection = Connection { request, response: Vec::new() };
+    ProgressEndpoint::new("/progress").serve(&mut connection, &snapshot()).unwrap();
+
+    let response = String::from_utf8(connection.response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with(JSON));
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn responds_to_http_requests() {
+    let endpoint = ProgressEndpoint::new("/progress");
+    let request = http::Request::get("/progress?x=1").body(()).unwrap();
+    let response = endpoint.respond_http(&request, &snapshot());
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert_eq!(response.body(), JSON.as_bytes());
+
+    let request = http::Request::head("/progress").body(()).unwrap();
+    assert!(endpoint.respond_http(&request, &snapshot()).body().is_empty());
+
+    let request = http::Request::delete("/progress").body(()).unwrap();
+    let response = endpoint.respond_http(&request, &snapshot());
+    assert_eq!(response.status(), 405);
+    assert_eq!(response.headers()["allow"], "GET, HEAD");
+
+    let request = http::Request::get("/").body(()).unwrap();
+    assert_eq!(endpoint.respond_http(&request, &snapshot()).status(), 404);
+}
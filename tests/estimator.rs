@@ -0,0 +1,248 @@
+use gaeta::{Aggregate, Status, TestTimer, TimeContext, MAX_REMAINING_TIME};
+
+// Updates `ctx` at `ts`.
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn progress_is_zero_before_first_update() {
+    let ctx = TimeContext::new(TestTimer::new());
+    assert_eq!(ctx.snapshot().progress, 0f64);
+}
+
+#[test]
+fn empty_job_is_complete() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 0);
+    assert_eq!(ctx.snapshot().progress, 100.0f64);
+}
+
+#[test]
+fn aggregate_counts_items_not_started_as_zero() {
+    let mut aggregate = Aggregate::new();
+    aggregate.add(TimeContext::new(TestTimer::new()));
+    aggregate.add(TimeContext::new(TestTimer::new()));
+
+    aggregate.update_eta(0, 10, 100);
+    assert_eq!(aggregate.get_progress(), 5.0f64);
+}
+
+#[test]
+fn regression_keeps_speed() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    update_at(&mut ctx, 20, 20, 100);
+    assert_eq!(ctx.get_remaining_time(), 80);
+
+    // 10 units of work are lost, so 90 are left, at the same speed.
+    update_at(&mut ctx, 30, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 90);
+
+    update_at(&mut ctx, 31, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 90);
+
+    update_at(&mut ctx, 40, 20, 100);
+    assert_eq!(ctx.get_remaining_time(), 80);
+}
+
+#[test]
+fn poll_update_reads_source() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let mut done = 0;
+
+    for ts in 0..=4 {
+        ctx.get_timefunc_mut().set_timestamp(ts * 10);
+        ctx.poll_update(&|| (done, 40));
+        done += 5;
+    }
+
+    assert_eq!((ctx.get_current(), ctx.get_total()), (20, 40));
+    assert_eq!(ctx.get_remaining_time(), 40);
+}
+
+#[test]
+fn speed_follows_recent_samples() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    for ts in 0..=10 {
+        update_at(&mut ctx, ts * 10, ts * 10, 10_000);
+    }
+    assert_eq!(ctx.calc_throughput(), 1f64);
+
+    // Once the window holds only the faster samples, the slow start doesn't count anymore.
+    for ts in 11..=30 {
+        update_at(&mut ctx, ts * 10, 100 + (ts - 10) * 30, 10_000);
+    }
+    assert_eq!(ctx.calc_throughput(), 3f64);
+    assert_eq!(ctx.calc_speed_per_unit(), 0.03f64);
+}
+
+#[test]
+fn accessors_return_cached_values() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    let (speed, remaining) = (ctx.calc_speed_per_unit(), ctx.get_remaining_time());
+
+    // Without an update, the clock is not read again.
+    ctx.get_timefunc_mut().set_timestamp(1000);
+    assert_eq!(ctx.calc_speed_per_unit(), speed);
+    assert_eq!(ctx.get_remaining_time(), remaining);
+}
+
+#[test]
+fn speed_follows_changed_total() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(ctx.calc_speed_per_unit(), 1f64);
+
+    // The samples are kept in work units, so they still count when the total grows.
+    update_at(&mut ctx, 20, 20, 200);
+    assert_eq!(ctx.calc_throughput(), 1f64);
+    assert_eq!(ctx.calc_speed_per_unit(), 0.5f64);
+    assert_eq!(ctx.get_remaining_time(), 180);
+}
+
+#[test]
+fn huge_totals_are_exact() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, u64::MAX - 1, u64::MAX);
+    assert!(ctx.snapshot().progress < 100f64);
+    assert_eq!(ctx.get_status(), Status::Running);
+
+    update_at(&mut ctx, 10, u64::MAX / 100 * 37, u64::MAX / 100 * 100);
+    assert_eq!(ctx.snapshot().progress, 37f64);
+}
+
+#[test]
+fn extreme_values_are_clamped() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, u64::MAX);
+    update_at(&mut ctx, u64::MAX, 1, u64::MAX);
+    assert_eq!(ctx.get_remaining_time(), MAX_REMAINING_TIME);
+
+    // The clock going backwards doesn't underflow.
+    update_at(&mut ctx, 10, 2, u64::MAX);
+    assert_eq!(ctx.get_remaining_time(), MAX_REMAINING_TIME);
+
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 150, 100);
+    assert_eq!(ctx.get_remaining_time(), 0);
+}
+
+#[test]
+fn remaining_time_grows_while_stalled() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 90);
+
+    update_at(&mut ctx, 20, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 180);
+    update_at(&mut ctx, 30, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 270);
+
+    // The measured speed isn't changed by the stall, until the progress resumes: then the stall
+    // is a part of the measured interval.
+    assert_eq!(ctx.calc_speed_per_unit(), 1f64);
+    update_at(&mut ctx, 31, 20, 100);
+    assert_eq!(ctx.get_remaining_time(), 168);
+}
+
+#[test]
+fn monotone_mode_absorbs_small_increases() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_monotone_margin(100);
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 90);
+
+    update_at(&mut ctx, 20, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 90);
+    update_at(&mut ctx, 30, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 270);
+
+    update_at(&mut ctx, 31, 20, 100);
+    assert_eq!(ctx.get_remaining_time(), 168);
+}
+
+#[test]
+fn discarded_intervals_are_not_measured() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_discard_intervals(1);
+    update_at(&mut ctx, 0, 0, 100);
+
+    // A slow setup interval.
+    update_at(&mut ctx, 100, 1, 100);
+    assert_eq!(ctx.calc_speed_per_unit(), 0f64);
+    assert_eq!(ctx.get_remaining_time(), 0);
+
+    update_at(&mut ctx, 110, 11, 100);
+    assert_eq!(ctx.calc_speed_per_unit(), 1f64);
+    assert_eq!(ctx.get_remaining_time(), 89);
+}
+
+#[test]
+fn progress_within_epsilon_is_not_a_sample() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_progress_epsilon(5);
+    update_at(&mut ctx, 0, 0, 100);
+
+    update_at(&mut ctx, 10, 3, 100);
+    assert_eq!(ctx.get_current(), 3);
+    assert_eq!(ctx.calc_speed_per_unit(), 0f64);
+
+    update_at(&mut ctx, 20, 6, 100);
+    assert_eq!(ctx.calc_speed_per_unit(), 0.3f64);
+}
+
+#[test]
+fn transfer_function_maps_progress() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    // The second half of the job takes three times as long as the first one.
+    ctx.set_transfer_function(|cur, max| {
+        if cur <= max / 2 { cur / 2 } else { max / 4 + (cur - max / 2) * 3 / 2 }
+    });
+
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 20, 100);
+    assert_eq!(ctx.get_current(), 10);
+    assert_eq!(ctx.get_remaining_time(), 90);
+
+    update_at(&mut ctx, 20, 100, 100);
+    assert_eq!(ctx.snapshot().progress, 100f64);
+
+    // The raw value is used while the total is unknown.
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_transfer_function(|cur, _| cur / 2);
+    ctx.update_count(40);
+    assert_eq!(ctx.get_current(), 40);
+}
+
+#[test]
+fn rate_cap_limits_speed() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_rate_cap(2.0);
+    update_at(&mut ctx, 0, 0, 10_000);
+
+    // Above the cap, and close to it, the cap is used.
+    update_at(&mut ctx, 10, 30, 10_000);
+    assert_eq!(ctx.calc_speed_per_unit(), 0.02f64);
+    update_at(&mut ctx, 100, 200, 10_000);
+    assert_eq!(ctx.calc_speed_per_unit(), 0.02f64);
+
+    // Well below the cap, the measurement is used.
+    update_at(&mut ctx, 1000, 1020, 10_000);
+    assert_eq!(ctx.calc_throughput(), 1f64);
+    assert_eq!(ctx.calc_speed_per_unit(), 0.01f64);
+
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_rate_cap(0.0);
+    update_at(&mut ctx, 0, 0, 10_000);
+    update_at(&mut ctx, 10, 30, 10_000);
+    assert_eq!(ctx.calc_speed_per_unit(), 0.03f64);
+}
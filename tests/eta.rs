@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use gaeta::{EtaValue, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn converts_to_duration() {
+    let unit = Duration::from_millis(1);
+    assert_eq!(EtaValue::Remaining(1500).to_duration(unit), Some(Duration::from_millis(1500)));
+    assert_eq!(EtaValue::Remaining(0).to_duration(unit), Some(Duration::ZERO));
+    assert_eq!(EtaValue::Unknown.to_duration(unit), None);
+    assert_eq!(EtaValue::Infinite.to_duration(unit), None);
+    assert_eq!(EtaValue::Remaining(u64::MAX).to_duration(Duration::from_secs(2)), None);
+}
+
+#[test]
+fn reports_state_of_estimate() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    assert_eq!(ctx.get_eta(), EtaValue::Unknown);
+
+    update_at(&mut ctx, 0, 0, 100);
+    assert_eq!(ctx.get_eta(), EtaValue::Unknown);
+
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(ctx.get_eta(), EtaValue::Remaining(90));
+
+    update_at(&mut ctx, 20, 100, 100);
+    assert_eq!(ctx.get_eta(), EtaValue::Remaining(0));
+}
+
+#[test]
+fn near_zero_speed_is_infinite() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, u64::MAX);
+    update_at(&mut ctx, u64::MAX, 1, u64::MAX);
+    assert_eq!(ctx.get_eta(), EtaValue::Infinite);
+}
@@ -0,0 +1,99 @@
+use std::sync::{Arc, Mutex};
+
+use gaeta::{Event, Observer, Snapshot, Status, TestTimer, TimeContext};
+
+// Logs the names of the invoked methods, and closes itself after `limit` of them.
+struct Log {
+    calls: Arc<Mutex<Vec<&'static str>>>,
+    limit: usize,
+}
+
+impl Log {
+    fn push(&mut self, name: &'static str) {
+        self.calls.lock().unwrap().push(name);
+    }
+}
+
+impl Observer for Log {
+    fn on_start(&mut self) { self.push("start"); }
+    fn on_progress(&mut self, _snapshot: &Snapshot) { self.push("progress"); }
+    fn on_stall(&mut self) { self.push("stall"); }
+    fn on_complete(&mut self) { self.push("complete"); }
+    fn on_cancel(&mut self) { self.push("cancel"); }
+    fn is_closed(&self) -> bool { self.calls.lock().unwrap().len() >= self.limit }
+}
+
+fn observed(limit: usize) -> (TimeContext<TestTimer>, Arc<Mutex<Vec<&'static str>>>) {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.add_observer(Box::new(Log { calls: calls.clone(), limit }));
+    (ctx, calls)
+}
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn subscriber_receives_lifecycle() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let rx = ctx.subscribe();
+
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 50, 100);
+    update_at(&mut ctx, 20, 100, 100);
+    update_at(&mut ctx, 30, 100, 100);
+
+    let events: Vec<Event> = rx.try_iter().collect();
+    assert_eq!(events.len(), 4);
+    assert!(matches!(events[0], Event::Started));
+    assert!(matches!(events[1], Event::Progressed { snapshot } if snapshot.progress == 50f64));
+    assert!(matches!(events[2], Event::Progressed { snapshot } if snapshot.progress == 100f64));
+    assert!(matches!(events[3], Event::Finished));
+}
+
+#[test]
+fn dropped_receiver_unsubscribes() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    drop(ctx.subscribe());
+    let rx = ctx.subscribe();
+
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 50, 100);
+    assert_eq!(rx.try_iter().count(), 2);
+}
+
+#[test]
+fn observer_sees_stall_once() {
+    let (mut ctx, calls) = observed(usize::MAX);
+    ctx.set_stall_timeout(5);
+
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    update_at(&mut ctx, 20, 10, 100);
+    update_at(&mut ctx, 30, 10, 100);
+    update_at(&mut ctx, 40, 20, 100);
+    assert_eq!(*calls.lock().unwrap(), ["start", "progress", "stall", "progress"]);
+}
+
+#[test]
+fn observer_sees_cancel_once() {
+    let (mut ctx, calls) = observed(usize::MAX);
+    update_at(&mut ctx, 0, 0, 100);
+    ctx.cancel();
+    ctx.cancel();
+    update_at(&mut ctx, 10, 100, 100);
+
+    assert_eq!(ctx.get_status(), Status::Cancelled);
+    assert_eq!(*calls.lock().unwrap(), ["start", "cancel"]);
+}
+
+#[test]
+fn closed_observer_is_removed() {
+    let (mut ctx, calls) = observed(2);
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 50, 100);
+    update_at(&mut ctx, 20, 100, 100);
+    assert_eq!(*calls.lock().unwrap(), ["start", "progress"]);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "ffi")]
+
+use std::cell::Cell;
+use std::os::raw::c_void;
+use std::ptr;
+
+use gaeta::ffi::*;
+
+extern "C" fn clock(user: *mut c_void) -> u64 {
+    unsafe { (*(user as *const Cell<u64>)).get() }
+}
+
+#[test]
+fn estimates_with_user_clock() {
+    let now = Cell::new(0u64);
+    let ctx = gaeta_create_with_clock(Some(clock), &now as *const Cell<u64> as *mut c_void);
+    assert!(!ctx.is_null());
+
+    unsafe {
+        gaeta_update(ctx, 0, 100);
+        now.set(10);
+        gaeta_update(ctx, 10, 100);
+        assert_eq!(gaeta_speed(ctx), 1f64);
+        assert_eq!(gaeta_remaining(ctx), 90);
+        gaeta_destroy(ctx);
+    }
+}
+
+#[test]
+fn null_is_accepted() {
+    assert!(gaeta_create_with_clock(None, ptr::null_mut()).is_null());
+
+    unsafe {
+        gaeta_update(ptr::null_mut(), 10, 100);
+        assert_eq!(gaeta_remaining(ptr::null()), 0);
+        assert_eq!(gaeta_speed(ptr::null()), 0f64);
+        gaeta_destroy(ptr::null_mut());
+    }
+}
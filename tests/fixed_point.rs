@@ -0,0 +1,59 @@
+use gaeta::{FixedPointContext, TestTimer, PROGRESS_SCALE};
+
+fn update_at<const N: usize, const F: u32>(ctx: &mut FixedPointContext<TestTimer, N, F>, ts: u64,
+                                           cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn estimates_in_fixed_point() {
+    let mut ctx: FixedPointContext<TestTimer> = FixedPointContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    assert_eq!(ctx.get_remaining_time(), 0);
+
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(ctx.get_progress(), PROGRESS_SCALE / 10);
+    assert_eq!(ctx.calc_speed_per_unit(), (PROGRESS_SCALE / 100) << 16);
+    assert_eq!(ctx.get_remaining_time(), 90);
+}
+
+#[test]
+fn huge_and_empty_totals() {
+    let mut ctx: FixedPointContext<TestTimer> = FixedPointContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, (u64::MAX - 1) / 2, u64::MAX - 1);
+    assert_eq!(ctx.get_progress(), PROGRESS_SCALE / 2);
+
+    update_at(&mut ctx, 10, u64::MAX, 10);
+    assert_eq!(ctx.get_progress(), PROGRESS_SCALE);
+
+    update_at(&mut ctx, 20, 0, 0);
+    assert_eq!(ctx.get_progress(), PROGRESS_SCALE);
+    assert_eq!(ctx.get_remaining_time(), 0);
+}
+
+#[test]
+fn measures_over_window() {
+    let mut ctx: FixedPointContext<TestTimer, 2> = FixedPointContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 1, 100);
+    update_at(&mut ctx, 20, 21, 100);
+    assert_eq!(ctx.calc_speed_per_unit(), (PROGRESS_SCALE / 50) << 16);
+    assert_eq!(ctx.get_remaining_time(), 39);
+}
+
+#[test]
+fn speed_precision_is_configurable() {
+    let mut coarse: FixedPointContext<TestTimer, 10, 0> = FixedPointContext::new(TestTimer::new());
+    let mut fine: FixedPointContext<TestTimer, 10, 32> = FixedPointContext::new(TestTimer::new());
+    update_at(&mut coarse, 0, 0, 3000);
+    update_at(&mut coarse, 7, 1, 3000);
+    update_at(&mut fine, 0, 0, 3000);
+    update_at(&mut fine, 7, 1, 3000);
+
+    // 333 millionths in 7 units of time; the exact remaining time is 21014.05.
+    assert_eq!(coarse.calc_speed_per_unit(), 47);
+    assert_eq!(fine.calc_speed_per_unit(), (333u64 << 32) / 7);
+    assert_eq!(coarse.get_remaining_time(), 21269);
+    assert_eq!(fine.get_remaining_time(), 21014);
+}
@@ -0,0 +1,70 @@
+use std::sync::{Arc, Mutex};
+
+use gaeta::{Observer, TestTimer, TimeContext};
+
+// Logs the speeds of the reported periods of low speed.
+struct Slow(Arc<Mutex<Vec<f64>>>);
+
+impl Observer for Slow {
+    fn on_slow(&mut self, speed: f64) {
+        self.0.lock().unwrap().push(speed);
+    }
+}
+
+// Runs a job which is slow between the timestamps 50 and 150, and 250 and 350, and returns the
+// timestamps and speeds of the reports.
+fn run(grace: u64) -> Vec<(u64, f64)> {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.add_observer(Box::new(Slow(reports.clone())));
+    ctx.set_speed_floor(5.0, grace);
+
+    let (mut cur, mut reported) = (0, Vec::new());
+    for ts in 0..400 {
+        if (50..150).contains(&(ts % 200)) {
+            cur += u64::from(ts % 10 == 0);
+        } else {
+            cur += 10;
+        }
+
+        ctx.get_timefunc_mut().set_timestamp(ts);
+        ctx.update_eta(cur, 100_000);
+
+        if let Some(speed) = reports.lock().unwrap().pop() {
+            reported.push((ts, speed));
+        }
+    }
+
+    reported
+}
+
+#[test]
+fn reported_once_per_period() {
+    let reported = run(20);
+    assert_eq!(reported.len(), 2);
+    assert!(reported.iter().all(|&(_, speed)| speed < 5.0));
+    assert!((50..150).contains(&reported[0].0));
+    assert!((250..350).contains(&reported[1].0));
+}
+
+#[test]
+fn waits_for_grace() {
+    let immediate = run(0);
+    let delayed = run(20);
+    assert_eq!(delayed[0].0, immediate[0].0 + 20);
+    assert_eq!(delayed[1].0, immediate[1].0 + 20);
+}
+
+#[test]
+fn not_reported_while_fast() {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.add_observer(Box::new(Slow(reports.clone())));
+    ctx.set_speed_floor(5.0, 0);
+
+    for ts in 0..100 {
+        ctx.get_timefunc_mut().set_timestamp(ts);
+        ctx.update_eta(ts * 10, 100_000);
+    }
+    assert!(reports.lock().unwrap().is_empty());
+}
@@ -0,0 +1,51 @@
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use gaeta::{EtaFuture, TestTimer};
+
+// A future which is pending `steps` times before it's ready.
+struct Steps {
+    steps: u64,
+}
+
+impl Future for Steps {
+    type Output = ();
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, _cx: &mut Context) -> Poll<()> {
+        if self.steps == 0 {
+            return Poll::Ready(());
+        }
+
+        self.steps -= 1;
+        Poll::Pending
+    }
+}
+
+#[test]
+fn reports_progress_while_pending() {
+    let job = EtaFuture::new(TestTimer::new(), |eta| async move {
+        for i in 1..=4 {
+            Steps { steps: 1 }.await;
+            eta.update_eta(i, 4);
+        }
+        "done"
+    });
+
+    let eta = job.handle();
+    let mut job = pin!(job);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    assert!(job.as_mut().poll(&mut cx).is_pending());
+    assert!(job.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(eta.snapshot().progress, 25.0f64);
+
+    let output = loop {
+        if let Poll::Ready(output) = job.as_mut().poll(&mut cx) {
+            break output;
+        }
+    };
+
+    assert_eq!(output, "done");
+    assert_eq!(eta.snapshot().progress, 100.0f64);
+}
@@ -0,0 +1,26 @@
+use std::fs;
+use std::path::PathBuf;
+
+use gaeta::{FileGrowth, GetProgress};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("gaeta-{}-{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn reads_size_of_file() {
+    let path = temp_path("growth.bin");
+    let mut source = FileGrowth::new(&path, 100);
+
+    // A file which doesn't exist yet has no progress.
+    assert_eq!(source.get_progress(), (0, 100));
+
+    fs::write(&path, [0u8; 40]).unwrap();
+    assert_eq!(source.get_progress(), (40, 100));
+
+    source.set_expected(80);
+    assert_eq!(source.get_progress(), (40, 80));
+    fs::remove_file(&path).unwrap();
+}
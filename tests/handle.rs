@@ -0,0 +1,27 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use gaeta::{EtaHandle, TestTimer, TimeContext};
+
+#[test]
+fn clones_share_context() {
+    let handle = EtaHandle::new(TimeContext::new(TestTimer::new()));
+    let other = handle.clone();
+
+    handle.update_eta(25, 100);
+    assert_eq!(other.snapshot().progress, 25.0f64);
+    assert_eq!(other.with(|ctx| ctx.get_current()), 25);
+}
+
+#[test]
+fn survives_panic_while_locked() {
+    let handle = EtaHandle::new(TimeContext::new(TestTimer::new()));
+    handle.update_eta(10, 100);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        handle.with(|_| panic!("panic while updating"));
+    }));
+    assert!(result.is_err());
+
+    handle.update_eta(20, 100);
+    assert_eq!(handle.snapshot().progress, 20.0f64);
+}
@@ -0,0 +1,48 @@
+use gaeta::{SpeedHistogram, TestTimer, TimeContext};
+
+#[test]
+fn bounds_are_sorted() {
+    let histogram = SpeedHistogram::new(vec![20.0, f64::NAN, 10.0, 20.0]);
+    assert_eq!(histogram.get_bounds(), [10.0, 20.0]);
+    assert_eq!(histogram.get_counts(), [0, 0, 0]);
+    assert_eq!(histogram.get_range(0), (f64::NEG_INFINITY, 10.0));
+    assert_eq!(histogram.get_range(2), (20.0, f64::INFINITY));
+
+    assert_eq!(SpeedHistogram::exponential(1.0, 2.0, 3).get_bounds(), [1.0, 2.0, 4.0, 8.0]);
+}
+
+#[test]
+fn records_interval_speeds() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_speed_histogram(SpeedHistogram::linear(0.0, 10.0, 3));
+    ctx.update_eta(0, 10_000);
+
+    let (mut ts, mut cur) = (0, 0);
+    for speed in [5, 5, 25, 25, 25, 10] {
+        ts += 1;
+        cur += speed;
+        ctx.get_timefunc_mut().set_timestamp(ts);
+        ctx.update_eta(cur, 10_000);
+    }
+
+    // A speed equal to a bound belongs to the bucket above it.
+    let histogram = ctx.get_speed_histogram().unwrap();
+    assert_eq!(histogram.get_counts(), [0, 2, 1, 3, 0]);
+    assert_eq!(histogram.get_total(), 6);
+    assert_eq!(histogram.get_peaks(), [1, 3]);
+}
+
+#[test]
+fn plateau_is_one_peak() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_speed_histogram(SpeedHistogram::linear(0.0, 10.0, 3));
+    ctx.update_eta(0, 10_000);
+    for (ts, cur) in [(1, 15), (2, 30), (3, 55), (4, 80)] {
+        ctx.get_timefunc_mut().set_timestamp(ts);
+        ctx.update_eta(cur, 10_000);
+    }
+
+    let histogram = ctx.get_speed_histogram().unwrap();
+    assert_eq!(histogram.get_counts(), [0, 0, 2, 2, 0]);
+    assert_eq!(histogram.get_peaks(), [2]);
+}
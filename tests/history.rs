@@ -0,0 +1,69 @@
+use gaeta::{TestTimer, TimeContext};
+
+// Updates `ctx` at `ts`.
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+// Returns the timestamps of the points of the history of `ctx`.
+fn timestamps(ctx: &TimeContext<TestTimer>) -> Vec<u64> {
+    ctx.get_history().unwrap().get_points().iter().map(|p| p.get_timestamp()).collect()
+}
+
+#[test]
+fn compaction_keeps_first_and_newest_point() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_history(1, 3);
+
+    update_at(&mut ctx, 0, 0, 100);
+
+    for ts in 1..20 {
+        update_at(&mut ctx, ts * 10, ts, 100);
+
+        let points = timestamps(&ctx);
+        assert!(points.len() <= 3);
+        assert_eq!(points.first(), Some(&10));
+        assert_eq!(points.last(), Some(&(ts * 10)));
+    }
+}
+
+#[test]
+fn compaction_doubles_resolution() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_history(10, 4);
+
+    for ts in 0..6 {
+        update_at(&mut ctx, ts * 10, ts, 100);
+    }
+
+    assert_eq!(timestamps(&ctx), vec![10, 30, 50]);
+    assert_eq!(ctx.get_history().unwrap().get_resolution(), 20);
+}
+
+#[test]
+fn same_period_replaces_newest_point() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_history(100, 8);
+
+    for ts in 0..5 {
+        update_at(&mut ctx, ts * 10, ts, 100);
+    }
+
+    assert_eq!(timestamps(&ctx), vec![10, 40]);
+    assert_eq!(ctx.get_history().unwrap().get_average_speed(), 0.1f64);
+}
+
+#[test]
+fn small_capacity_is_clamped() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_history(1, 0);
+
+    update_at(&mut ctx, 0, 0, 100);
+
+    for ts in 1..10 {
+        update_at(&mut ctx, ts, ts, 100);
+        assert!(timestamps(&ctx).len() <= 2);
+        assert_eq!(timestamps(&ctx).last(), Some(&ts));
+    }
+}
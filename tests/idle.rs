@@ -0,0 +1,48 @@
+use gaeta::{TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur: u64, max: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur, max);
+}
+
+fn run(ctx: &mut TimeContext<TestTimer>) {
+    // Working, without progress, below the minimum speed of 5, and working again.
+    for (ts, cur) in [(0, 0), (10, 100), (20, 100), (30, 120), (40, 220)] {
+        update_at(ctx, ts, cur, 1000);
+    }
+}
+
+#[test]
+fn not_measured_by_default() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    run(&mut ctx);
+    assert_eq!(ctx.get_idle_time(), 0);
+    assert_eq!(ctx.calc_average_speed(), 5.5);
+}
+
+#[test]
+fn counts_slow_intervals() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_idle_rate(5.0);
+    run(&mut ctx);
+    assert_eq!(ctx.get_idle_time(), 20);
+    assert_eq!(ctx.calc_average_speed(), 5.5);
+
+    // An update at the same timestamp adds nothing.
+    update_at(&mut ctx, 40, 220, 1000);
+    assert_eq!(ctx.get_idle_time(), 20);
+}
+
+#[test]
+fn excluded_from_average() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    // Ignored, as the idle time isn't measured yet.
+    ctx.set_idle_excluded(true);
+    ctx.set_idle_rate(5.0);
+    run(&mut ctx);
+    assert_eq!(ctx.calc_average_speed(), 5.5);
+
+    ctx.set_idle_excluded(true);
+    assert_eq!(ctx.calc_average_speed(), 11.0);
+
+}
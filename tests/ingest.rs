@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+use gaeta::{SampleQueue, TestTimer, TimeContext};
+
+#[test]
+fn full_queue_rejects_increments() {
+    let queue: SampleQueue<2> = SampleQueue::new();
+    assert!(queue.push(1, 10));
+    assert!(queue.push(2, 20));
+    assert!(!queue.push(3, 30));
+
+    assert_eq!(queue.pop(), Some((1, 10)));
+    assert!(queue.push(4, 40));
+    assert_eq!(queue.pop(), Some((2, 20)));
+    assert_eq!(queue.pop(), Some((4, 40)));
+    assert_eq!(queue.pop(), None);
+}
+
+#[test]
+fn empty_queue_holds_nothing() {
+    let queue: SampleQueue<0> = SampleQueue::new();
+    assert!(!queue.push(1, 10));
+    assert_eq!(queue.pop(), None);
+}
+
+#[test]
+fn drains_running_total_into_context() {
+    let queue: SampleQueue<8> = SampleQueue::new();
+    let mut ctx = TimeContext::new(TestTimer::new());
+    queue.push(0, 0);
+    queue.push(10, 5);
+    queue.push(20, 5);
+
+    assert_eq!(queue.drain_into(&mut ctx, 100), 3);
+    assert_eq!(queue.get_total(), 10);
+    assert_eq!(ctx.get_current(), 10);
+    assert_eq!(ctx.get_remaining_time(), 180);
+}
+
+#[test]
+fn producers_lose_no_accepted_increments() {
+    static QUEUE: SampleQueue<16> = SampleQueue::new();
+    static PUSHED: AtomicU64 = AtomicU64::new(0);
+
+    let producers: Vec<_> = (0..4).map(|_| thread::spawn(|| {
+        for i in 0..5000u64 {
+            if QUEUE.push(i, 1) {
+                PUSHED.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    })).collect();
+
+    let mut ctx = TimeContext::new(TestTimer::new());
+    while producers.iter().any(|p| !p.is_finished()) {
+        QUEUE.drain_into(&mut ctx, 1_000_000);
+    }
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    QUEUE.drain_into(&mut ctx, 1_000_000);
+
+    assert_eq!(QUEUE.get_total(), PUSHED.load(Ordering::Relaxed));
+    assert_eq!(ctx.get_current(), QUEUE.get_total());
+}
@@ -0,0 +1,76 @@
+use std::io::{self, Cursor, ErrorKind, Read, Write};
+
+use gaeta::{copy_metered, EtaReader, EtaWriter, TestTimer, TimeContext};
+
+// Fails with `kind` once, then reads from `inner`.
+struct FailOnce<R> {
+    inner: R,
+    kind: Option<ErrorKind>,
+}
+
+fn fail_once(kind: ErrorKind) -> FailOnce<Cursor<Vec<u8>>> {
+    FailOnce { inner: Cursor::new(vec![7u8; 100]), kind: Some(kind) }
+}
+
+impl<R: Read> Read for FailOnce<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.kind.take() {
+            Some(kind) => Err(io::Error::from(kind)),
+            None => self.inner.read(buf),
+        }
+    }
+}
+
+#[test]
+fn reader_counts_bytes() {
+    let mut reader = EtaReader::new(Cursor::new(vec![3u8; 1000]), 1000, TestTimer::new());
+
+    let mut buf = [0u8; 250];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.count(), 250);
+    assert_eq!(reader.get_context().snapshot().progress, 25.0f64);
+
+    io::copy(&mut reader, &mut io::sink()).unwrap();
+    assert_eq!(reader.count(), 1000);
+    assert_eq!(reader.get_context().snapshot().progress, 100.0f64);
+
+    // The end of the input isn't an update.
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    assert_eq!(reader.count(), 1000);
+}
+
+#[test]
+fn writer_counts_bytes() {
+    let mut writer = EtaWriter::new(Vec::new(), 10, TestTimer::new());
+    writer.write_all(b"hello").unwrap();
+    writer.flush().unwrap();
+
+    assert_eq!(writer.count(), 5);
+    assert_eq!(writer.get_context().snapshot().progress, 50.0f64);
+    assert_eq!(writer.get_ref(), b"hello");
+    assert_eq!(writer.into_inner(), b"hello".to_vec());
+}
+
+#[test]
+fn copy_retries_interrupted_reads() {
+    let mut reader = fail_once(ErrorKind::Interrupted);
+    let mut writer = Vec::new();
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let mut reports = 0;
+
+    let copied = copy_metered(&mut reader, &mut writer, 100, &mut ctx, |_| reports += 1).unwrap();
+    assert_eq!(copied, 100);
+    assert_eq!(writer, vec![7u8; 100]);
+    assert!(reports > 0);
+    assert_eq!(ctx.get_current(), 100);
+}
+
+#[test]
+fn copy_returns_errors() {
+    let mut reader = fail_once(ErrorKind::BrokenPipe);
+    let mut ctx = TimeContext::new(TestTimer::new());
+
+    let err = copy_metered(&mut reader, &mut io::sink(), 100, &mut ctx, |_| {}).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+    assert_eq!(ctx.get_current(), 0);
+}
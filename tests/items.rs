@@ -0,0 +1,48 @@
+use gaeta::{ItemStats, TestTimer, TimeContext};
+
+fn at(ctx: &mut TimeContext<TestTimer>, ts: u64) -> &mut TimeContext<TestTimer> {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx
+}
+
+#[test]
+fn started_and_finished() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    assert_eq!(*ctx.get_item_stats(), ItemStats::default());
+    assert_eq!(ctx.get_item_stats().get_average(), 0.0);
+
+    at(&mut ctx, 10).item_started();
+    assert!(ctx.get_item_stats().is_in_progress());
+    at(&mut ctx, 13).item_finished();
+    at(&mut ctx, 20).item_started();
+    // Starting an item finishes the one in progress.
+    at(&mut ctx, 29).item_started();
+    at(&mut ctx, 35).item_finished();
+
+    let stats = ctx.get_item_stats();
+    assert!(!stats.is_in_progress());
+    assert_eq!(stats.get_count(), 3);
+    assert_eq!(stats.get_total_duration(), 18);
+    assert_eq!(stats.get_average(), 6.0);
+    assert_eq!((stats.get_min(), stats.get_max()), (Some(3), Some(9)));
+}
+
+#[test]
+fn finished_only() {
+    // The first item is measured from the first update, and the others from the previous one.
+    let mut ctx = TimeContext::new(TestTimer::new());
+    at(&mut ctx, 5).update_eta(0, 10);
+    at(&mut ctx, 9).item_finished();
+    at(&mut ctx, 21).item_finished();
+    assert_eq!(ctx.get_item_stats().get_count(), 2);
+    assert_eq!(ctx.get_item_stats().get_max(), Some(12));
+    assert_eq!(ctx.get_item_stats().get_min(), Some(4));
+
+    // Without an update, the first item only marks the start of the next one.
+    let mut ctx = TimeContext::new(TestTimer::new());
+    at(&mut ctx, 9).item_finished();
+    assert_eq!(ctx.get_item_stats().get_count(), 0);
+    at(&mut ctx, 21).item_finished();
+    assert_eq!(ctx.get_item_stats().get_count(), 1);
+    assert_eq!(ctx.get_item_stats().get_total_duration(), 12);
+}
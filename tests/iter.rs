@@ -0,0 +1,22 @@
+use gaeta::{EtaIter, EtaIteratorExt, TestTimer};
+
+#[test]
+fn counts_items() {
+    let mut items = (0..10).track_eta(10, TestTimer::new());
+
+    assert_eq!(items.next(), Some(0));
+    assert_eq!(items.next(), Some(1));
+    assert_eq!(EtaIter::count(&items), 2);
+    assert_eq!(items.get_context().snapshot().progress, 20.0f64);
+    assert_eq!(items.size_hint(), (8, Some(8)));
+}
+
+#[test]
+fn end_of_iterator_is_not_an_update() {
+    let mut items = (0..3).track_eta(5, TestTimer::new());
+    while items.next().is_some() {}
+    assert_eq!(items.next(), None);
+
+    assert_eq!(EtaIter::count(&items), 3);
+    assert_eq!(items.get_context().snapshot().progress, 60.0f64);
+}
@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use gaeta::{eta_for, Observer, Snapshot, TestTimer, TimeContext};
+
+// Counts the progress notifications.
+struct Counter(Arc<AtomicUsize>);
+
+impl Observer for Counter {
+    fn on_progress(&mut self, _snapshot: &Snapshot) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn updates_at_most_1000_times() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.add_observer(Box::new(Counter(count.clone())));
+
+    let mut ts = 0;
+    eta_for!(ctx, _i in 0..5000 => {
+        ts += 1;
+        ctx.get_timefunc_mut().set_timestamp(ts);
+    });
+
+    assert_eq!(count.load(Ordering::Relaxed), 1000);
+    assert_eq!((ctx.get_current(), ctx.get_total()), (5000, 5000));
+}
+
+#[test]
+fn break_stops_at_started_item() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    eta_for!(ctx, i in 0..10 => {
+        if i == 5 {
+            break;
+        }
+    });
+    assert_eq!((ctx.get_current(), ctx.get_total()), (6, 10));
+}
+
+#[test]
+fn empty_collection_finishes() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let items: [u32; 0] = [];
+    eta_for!(ctx, _item in items.iter() => {});
+    assert_eq!((ctx.get_current(), ctx.get_total()), (0, 0));
+    assert_eq!(ctx.snapshot().progress, 100f64);
+}
@@ -0,0 +1,38 @@
+use gaeta::{Milestones, Observer, Snapshot, Status};
+
+fn at(progress: f64) -> Snapshot {
+    Snapshot { progress, speed: 0f64, remaining: 0, status: Status::Running }
+}
+
+#[test]
+fn thresholds_are_sorted_and_deduplicated() {
+    let mut crossed = Vec::new();
+    let mut milestones = Milestones::at(&[50.0, 25.0, f64::NAN, 25.0], |t, _: &Snapshot| {
+        crossed.push(t);
+    });
+
+    milestones.on_progress(&at(10.0));
+    milestones.on_progress(&at(30.0));
+    assert!(!milestones.is_closed());
+    milestones.on_progress(&at(60.0));
+    milestones.on_progress(&at(70.0));
+    assert!(milestones.is_closed());
+
+    assert_eq!(crossed, [25.0, 50.0]);
+}
+
+#[test]
+fn jump_crosses_every_threshold() {
+    let mut crossed = Vec::new();
+    let mut milestones = Milestones::every(25.0, |t, s: &Snapshot| crossed.push((t, s.progress)));
+    milestones.on_progress(&at(80.0));
+    milestones.on_progress(&at(100.0));
+
+    assert_eq!(crossed, [(25.0, 80.0), (50.0, 80.0), (75.0, 80.0), (100.0, 100.0)]);
+}
+
+#[test]
+fn no_step_has_no_thresholds() {
+    let milestones = Milestones::every(0.0, |_, _: &Snapshot| {});
+    assert!(milestones.is_closed());
+}
@@ -0,0 +1,28 @@
+use gaeta::{EtaNotifier, Observer, Snapshot, Status};
+
+fn left(remaining: i64) -> Snapshot {
+    Snapshot { progress: 0f64, speed: 0f64, remaining, status: Status::Running }
+}
+
+#[test]
+fn debounces_small_changes() {
+    let mut fired = Vec::new();
+    let mut notifier = EtaNotifier::new(10, |r, _: &Snapshot| fired.push(r));
+    for remaining in [100, 95, 91, 89, 85, 70, 95] {
+        notifier.on_progress(&left(remaining));
+    }
+
+    assert_eq!(fired, [100, 89, 70, 95]);
+}
+
+#[test]
+fn crossing_boundary_fires() {
+    let mut fired = Vec::new();
+    let mut notifier = EtaNotifier::new(100, |r, _: &Snapshot| fired.push(r));
+    notifier.set_boundary(60);
+    for remaining in [130, 125, 119, 100, 60, 59] {
+        notifier.on_progress(&left(remaining));
+    }
+
+    assert_eq!(fired, [130, 119, 59]);
+}
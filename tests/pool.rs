@@ -0,0 +1,48 @@
+use std::sync::Arc;
+use std::thread;
+
+use gaeta::{Shard, TestTimer, TimeContext, WorkerPool};
+
+#[test]
+fn limited_workers() {
+    let pool = WorkerPool::<2>::new(100);
+    let first = pool.join().unwrap();
+    let second = pool.join().unwrap();
+    assert!(pool.join().is_none());
+    assert_eq!(pool.get_active_workers(), 2);
+
+    first.inc(10);
+    second.inc(5);
+    drop(first);
+    assert_eq!(pool.get_active_workers(), 1);
+
+    // The slot is reused, and the progress of the worker which left stays counted.
+    let third = pool.join().unwrap();
+    third.inc(1);
+    assert_eq!(pool.get(), (16, 100));
+    drop(second);
+    drop(third);
+    assert_eq!(pool.get_active_workers(), 0);
+    assert_eq!(pool.get(), (16, 100));
+
+    pool.set_max(200);
+    assert_eq!(pool.get(), (16, 200));
+}
+
+#[test]
+fn workers_in_threads() {
+    let pool = Arc::new(WorkerPool::<8>::new(4000));
+    let workers: Vec<_> = (0..4).map(|_| {
+        let shard = Shard::new(pool.clone()).unwrap();
+        thread::spawn(move || for _ in 0..1000 { shard.inc(1) })
+    }).collect();
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    assert_eq!(pool.get_active_workers(), 0);
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.poll_update(&pool);
+    assert_eq!(ctx.snapshot().progress, 100.0);
+}
@@ -0,0 +1,39 @@
+use gaeta::{Event, Preset, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn download_preset() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.apply_preset(Preset::Download, 10);
+    let rx = ctx.subscribe();
+    update_at(&mut ctx, 0, 0, 100);
+
+    // The first interval (e.g. connecting) is discarded.
+    update_at(&mut ctx, 100, 1, 100);
+    assert_eq!(ctx.calc_speed_per_unit(), 0f64);
+    update_at(&mut ctx, 110, 11, 100);
+    assert_eq!(ctx.get_remaining_time(), 89);
+
+    // Small increases of the estimate are absorbed, and stalls are reported after 5 seconds.
+    update_at(&mut ctx, 112, 11, 100);
+    assert_eq!(ctx.get_remaining_time(), 89);
+    update_at(&mut ctx, 170, 11, 100);
+    assert!(rx.try_iter().any(|e| matches!(e, Event::Stalled)));
+}
+
+#[test]
+fn cpu_bound_preset() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.apply_preset(Preset::CpuBound, 1);
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 90);
+
+    // The estimate follows the stall at once.
+    update_at(&mut ctx, 20, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 180);
+}
@@ -0,0 +1,40 @@
+#![cfg(all(feature = "procfs", target_os = "linux"))]
+
+use std::fs::{self, File};
+use std::io::{Seek, SeekFrom, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::PathBuf;
+
+use gaeta::{FdProgress, GetProgress};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("gaeta-{}-{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn reads_position_and_size() {
+    let path = temp_path("fd.bin");
+    let mut file = File::create(&path).unwrap();
+    file.write_all(&[0u8; 100]).unwrap();
+    file.seek(SeekFrom::Start(30)).unwrap();
+
+    let source = FdProgress::new(std::process::id(), file.as_raw_fd() as u32).unwrap();
+    assert_eq!(source.get_progress(), (30, 100));
+
+    file.seek(SeekFrom::Start(60)).unwrap();
+    assert_eq!(source.get_progress(), (60, 100));
+    assert!(!source.is_closed());
+
+    // Once the descriptor is closed, the last values are kept.
+    drop(file);
+    assert_eq!(source.get_progress(), (60, 100));
+    assert!(source.is_closed());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn missing_descriptor_is_an_error() {
+    assert!(FdProgress::new(std::process::id(), 999_999).is_err());
+}
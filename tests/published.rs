@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use gaeta::{Snapshot, SnapshotCell, Status};
+
+fn numbered(i: u64) -> Snapshot {
+    let status = match i % 3 {
+        0 => Status::Running,
+        1 => Status::Finished,
+        _ => Status::Cancelled,
+    };
+
+    Snapshot { progress: i as f64, speed: i as f64 * 2.0, remaining: i as i64 * 3, status }
+}
+
+#[test]
+fn round_trip() {
+    let cell = SnapshotCell::new();
+    let empty = cell.load();
+    assert_eq!((empty.progress, empty.remaining, empty.status), (0f64, 0, Status::Running));
+
+    for i in 0..3 {
+        cell.publish(numbered(i + 7));
+        let snapshot = cell.load();
+        let expected = numbered(i + 7);
+        assert_eq!((snapshot.progress, snapshot.speed, snapshot.remaining, snapshot.status),
+                   (expected.progress, expected.speed, expected.remaining, expected.status));
+    }
+
+    cell.publish(Snapshot { remaining: -1, ..numbered(0) });
+    assert_eq!(cell.load().remaining, -1);
+}
+
+#[test]
+fn readers_never_see_torn_snapshots() {
+    static CELL: SnapshotCell = SnapshotCell::new();
+    static DONE: AtomicBool = AtomicBool::new(false);
+
+    let writers: Vec<_> = (0..2u64).map(|w| thread::spawn(move || {
+        for i in 0..20_000u64 {
+            CELL.publish(numbered(i * 2 + w));
+        }
+    })).collect();
+
+    let readers: Vec<_> = (0..2).map(|_| thread::spawn(|| {
+        while !DONE.load(Ordering::Relaxed) {
+            let snapshot = CELL.load();
+            let expected = numbered(snapshot.progress as u64);
+            assert_eq!(snapshot.speed, expected.speed);
+            assert_eq!(snapshot.remaining, expected.remaining);
+            assert_eq!(snapshot.status, expected.status);
+        }
+    })).collect();
+
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    DONE.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+}
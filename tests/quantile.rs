@@ -0,0 +1,43 @@
+use gaeta::{TestTimer, TimeContext};
+
+// Returns a context with quantiles enabled and one interval of every speed from `speeds`.
+fn measured(speeds: impl Iterator<Item = u64>) -> TimeContext<TestTimer> {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_rate_quantiles();
+    ctx.update_eta(0, u64::MAX);
+
+    let (mut ts, mut cur) = (0, 0);
+    for speed in speeds {
+        ts += 1;
+        cur += speed;
+        ctx.get_timefunc_mut().set_timestamp(ts);
+        ctx.update_eta(cur, u64::MAX);
+    }
+    ctx
+}
+
+#[test]
+fn few_intervals_are_exact() {
+    let ctx = measured([3, 1, 2].into_iter());
+    let quantiles = ctx.get_rate_quantiles().unwrap();
+    assert_eq!(quantiles.get_count(), 3);
+    assert_eq!(quantiles.get_p50(), 2f64);
+    assert_eq!(quantiles.get_p99(), 3f64);
+}
+
+#[test]
+fn estimates_quantiles_of_many_intervals() {
+    // Every speed from 1 to 1000, in a scrambled order.
+    let ctx = measured((1..=1000).map(|i| i * 389 % 1001));
+    let quantiles = ctx.get_rate_quantiles().unwrap();
+    assert_eq!(quantiles.get_count(), 1000);
+    assert!((quantiles.get_p50() - 500f64).abs() < 25f64, "{}", quantiles.get_p50());
+    assert!((quantiles.get_p95() - 950f64).abs() < 25f64, "{}", quantiles.get_p95());
+    assert!((quantiles.get_p99() - 990f64).abs() < 25f64, "{}", quantiles.get_p99());
+}
+
+#[test]
+fn disabled_by_default() {
+    let ctx = TimeContext::new(TestTimer::new());
+    assert!(ctx.get_rate_quantiles().is_none());
+}
@@ -0,0 +1,74 @@
+use gaeta::{LogEntry, SessionLog, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+fn recorded() -> TimeContext<TestTimer> {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_recording();
+    ctx.get_timefunc_mut().set_timestamp(0);
+    ctx.update_count(0);
+    update_at(&mut ctx, 10, 10, 100);
+    update_at(&mut ctx, 20, 5, 100);
+    update_at(&mut ctx, 30, 30, 200);
+    update_at(&mut ctx, u64::MAX, u64::MAX, u64::MAX);
+    ctx
+}
+
+#[test]
+fn records_updates() {
+    let ctx = recorded();
+    let entries = ctx.get_recording().unwrap().get_entries();
+    assert_eq!(entries.len(), 5);
+    assert_eq!(entries[0], LogEntry { timestamp: 0, current: 0, total: None });
+    assert_eq!(entries[2], LogEntry { timestamp: 20, current: 5, total: Some(100) });
+}
+
+#[test]
+fn replay_repeats_estimate() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_recording();
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    update_at(&mut ctx, 20, 15, 100);
+    let log = ctx.take_recording().unwrap();
+    assert!(ctx.get_recording().is_none());
+
+    let mut replayed = TimeContext::new(TestTimer::new());
+    log.replay(&mut replayed);
+    assert_eq!(replayed.get_remaining_time(), ctx.get_remaining_time());
+    assert_eq!(replayed.calc_speed_per_unit(), ctx.calc_speed_per_unit());
+}
+
+#[test]
+fn encoding_round_trip() {
+    let mut ctx = recorded();
+    let log = ctx.take_recording().unwrap();
+    let bytes = log.to_bytes();
+    assert_eq!(SessionLog::from_bytes(&bytes), Some(log));
+
+    let empty = SessionLog::new();
+    assert_eq!(SessionLog::from_bytes(&empty.to_bytes()), Some(empty));
+}
+
+#[test]
+fn corrupted_encoding_is_rejected() {
+    let mut ctx = recorded();
+    let bytes = ctx.take_recording().unwrap().to_bytes();
+
+    assert_eq!(SessionLog::from_bytes(&[]), None);
+    assert_eq!(SessionLog::from_bytes(&bytes[..bytes.len() - 1]), None);
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(SessionLog::from_bytes(&trailing), None);
+
+    let mut version = bytes.clone();
+    version[0] = 0;
+    assert_eq!(SessionLog::from_bytes(&version), None);
+
+    // A huge count with no entries doesn't allocate for them.
+    assert_eq!(SessionLog::from_bytes(&[1, 0xff, 0xff, 0xff, 0xff, 0x0f]), None);
+}
@@ -0,0 +1,28 @@
+use gaeta::{TestTimer, TimeContext};
+
+#[test]
+fn starts_with_speed_of_previous_run() {
+    let ctx = TimeContext::resumed(100, 50, 100, TestTimer::new());
+    assert_eq!(ctx.snapshot().progress, 50f64);
+    assert_eq!(ctx.calc_speed_per_unit(), 0.5f64);
+    assert_eq!(ctx.get_remaining_time(), 100);
+}
+
+#[test]
+fn further_updates_continue_previous_run() {
+    let mut timer = TestTimer::new();
+    timer.set_timestamp(1000);
+    let mut ctx = TimeContext::resumed(100, 50, 100, timer);
+
+    ctx.get_timefunc_mut().set_timestamp(1020);
+    ctx.update_eta(60, 100);
+    assert_eq!(ctx.calc_average_speed(), 0.5f64);
+    assert_eq!(ctx.get_remaining_time(), 80);
+}
+
+#[test]
+fn prior_progress_is_clamped() {
+    let ctx = TimeContext::resumed(10, 200, 100, TestTimer::new());
+    assert_eq!(ctx.get_current(), 100);
+    assert_eq!(ctx.snapshot().progress, 100f64);
+}
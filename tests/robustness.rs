@@ -0,0 +1,228 @@
+// No sequence of updates may make the estimator panic or report values out of range, whatever
+// features are enabled.
+
+use gaeta::{
+    Decimation, LogEntry, ProgressCurve, Schedule, SessionLog, SpeedHistogram, TestTimer,
+    TimeContext, TraceGenerator, Workload, MAX_REMAINING_TIME,
+};
+
+// A xorshift64* generator, so the sequences are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+// Returns a context with everything which hooks into the update path enabled, or, for
+// `variant` 0, a plain one.
+fn context(variant: u64) -> TimeContext<TestTimer> {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    if variant == 0 {
+        return ctx;
+    }
+
+    ctx.enable_history(10, 16);
+    ctx.enable_recording();
+    ctx.enable_rate_quantiles();
+    ctx.enable_speed_histogram(SpeedHistogram::exponential(0.001f64, 2.0f64, 16));
+    ctx.enable_stall_log(16);
+    ctx.enable_bias_correction();
+    ctx.enable_calibration();
+    ctx.set_stall_timeout(5);
+    ctx.set_deadline(1000);
+    ctx.set_speed_floor(0.5f64, 10);
+    ctx.set_idle_rate(0.1f64);
+    ctx.set_idle_excluded(true);
+    ctx.set_expected_curve(ProgressCurve::new(vec![(10, 0f64), (100, 100.0f64)]));
+
+    match variant % 3 {
+        0 => {
+            ctx.set_monotone_margin(3);
+            ctx.set_progress_epsilon(2);
+            ctx.set_schedule(Schedule::Rate(1.5f64));
+        }
+        1 => {
+            ctx.set_discard_intervals(2);
+            ctx.set_rate_cap(4.0f64);
+            ctx.set_schedule(Schedule::FinishBy(500));
+        }
+        _ => {
+            ctx.set_transfer_function(|cur, max| cur / 2 + max / 2);
+            ctx.set_decimation(Decimation::Every(3));
+        }
+    }
+
+    ctx
+}
+
+// Checks the invariants which hold after any update, and calls the accessors which compute
+// something on the fly.
+fn check(ctx: &TimeContext<TestTimer>) {
+    let remaining = ctx.get_remaining_time();
+    assert!((0..=MAX_REMAINING_TIME).contains(&remaining), "remaining time {}", remaining);
+
+    let snapshot = ctx.snapshot();
+    assert!(snapshot.progress >= 0f64, "progress {}", snapshot.progress);
+    assert_eq!(snapshot.remaining, remaining);
+
+    let at = ctx.get_remaining_time_at(u64::MAX);
+    assert!((0..=MAX_REMAINING_TIME).contains(&at), "remaining time {}", at);
+
+    let _ = ctx.get_eta();
+    let _ = ctx.calc_throughput();
+    let _ = ctx.calc_average_speed();
+    let _ = ctx.get_curve_status();
+    let _ = ctx.get_curve_remaining_time();
+    let _ = ctx.get_schedule_status();
+    let _ = TimeContext::from_state(&ctx.get_state(), TestTimer::new());
+}
+
+// Feeds `log` to a context of every variant, checking it after every update.
+fn replay_checked(log: &SessionLog) {
+    for variant in 0..4 {
+        let mut ctx = context(variant);
+
+        for entry in log.get_entries() {
+            ctx.get_timefunc_mut().set_timestamp(entry.timestamp);
+
+            match entry.total {
+                Some(total) => ctx.update_eta(entry.current, total),
+                None => ctx.update_count(entry.current),
+            }
+
+            if entry.current % 7 == 0 {
+                ctx.item_finished();
+            }
+
+            check(&ctx);
+        }
+
+        if let Some(recording) = ctx.take_recording() {
+            let bytes = recording.to_bytes();
+            assert_eq!(SessionLog::from_bytes(&bytes), Some(recording));
+        }
+    }
+}
+
+// Picks one of the values which are most likely to break arithmetic, or a random one.
+fn value(rng: &mut Rng, previous: u64) -> u64 {
+    match rng.below(10) {
+        0 => 0u64,
+        1 => u64::MAX,
+        2 => u64::MAX - rng.below(3),
+        3 => previous,
+        4 => previous.saturating_sub(rng.below(100)),
+        _ => previous.saturating_add(rng.below(100)),
+    }
+}
+
+#[test]
+fn random_updates() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..200 {
+        let mut log = SessionLog::new();
+        let (mut ts, mut cur, mut total) = (0u64, 0u64, rng.below(1000));
+
+        for _ in 0..rng.below(60) {
+            ts = value(&mut rng, ts);
+            cur = value(&mut rng, cur);
+            if rng.below(8) == 0 {
+                total = value(&mut rng, total);
+            }
+
+            log.push(LogEntry {
+                timestamp: ts,
+                current: cur,
+                total: if rng.below(6) == 0 { None } else { Some(total) },
+            });
+        }
+
+        replay_checked(&log);
+    }
+}
+
+#[test]
+fn edge_cases() {
+    let sequences: &[&[(u64, u64, u64)]] = &[
+        // A job of size 0.
+        &[(0, 0, 0), (1, 0, 0), (2, 5, 0)],
+        // Repeated timestamps.
+        &[(5, 0, 100), (5, 10, 100), (5, 20, 100), (5, 10, 100)],
+        // Regressions, right after the start and after completion.
+        &[(0, 50, 100), (1, 0, 100), (2, 100, 100), (3, 0, 100), (4, 1, 100)],
+        // Huge values.
+        &[(0, 0, u64::MAX), (1, u64::MAX, u64::MAX), (u64::MAX, u64::MAX, u64::MAX)],
+        &[(u64::MAX, 0, u64::MAX), (u64::MAX, 1, u64::MAX), (0, 2, u64::MAX)],
+        &[(0, u64::MAX, 1), (1, 0, 1), (2, u64::MAX, 0)],
+        // A clock going backwards.
+        &[(100, 0, 100), (50, 10, 100), (0, 20, 100), (200, 30, 100)],
+    ];
+
+    for sequence in sequences {
+        let mut log = SessionLog::new();
+        for &(timestamp, current, total) in sequence.iter() {
+            log.push(LogEntry { timestamp, current, total: Some(total) });
+        }
+
+        replay_checked(&log);
+    }
+}
+
+#[test]
+fn generated_traces() {
+    let workloads = [
+        Workload::Steady, Workload::Bursty, Workload::Stalling, Workload::Decelerating,
+    ];
+
+    for workload in workloads {
+        for seed in 1..20 {
+            let mut generator = TraceGenerator::new(workload);
+            generator.set_seed(seed);
+            generator.set_steps(50);
+            generator.set_jitter(0.5f64);
+
+            let trace = generator.generate();
+            replay_checked(&trace);
+
+            let error = TraceGenerator::evaluate(&trace, &mut context(seed));
+            assert!(error >= 0f64, "error {}", error);
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_scenarios() {
+    use gaeta::{Scenario, Step};
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..200 {
+        let bytes: Vec<u8> = (0..rng.below(512)).map(|_| rng.next() as u8).collect();
+        let scenario = match Scenario::arbitrary(&mut Unstructured::new(&bytes)) {
+            Ok(scenario) => scenario,
+            Err(_) => continue,
+        };
+
+        for variant in 0..4 {
+            scenario.replay(&mut context(variant), check);
+        }
+    }
+
+    let step = Step { elapsed: u64::MAX, progress: u64::MAX };
+    for total in [0u64, 1u64, u64::MAX] {
+        let scenario = Scenario::new(total, vec![step, step, Step { elapsed: 0, progress: 0 }]);
+        scenario.replay(&mut context(1), check);
+    }
+}
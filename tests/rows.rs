@@ -0,0 +1,33 @@
+use gaeta::{RowProgress, RowReport, Status, TestTimer};
+
+fn batch_at(rows: &mut RowProgress<TestTimer>, ts: u64, count: u64) -> RowReport {
+    rows.get_context_mut().get_timefunc_mut().set_timestamp(ts);
+    rows.batch(count)
+}
+
+#[test]
+fn reports_rows_per_unit() {
+    let mut rows = RowProgress::new(1000, TestTimer::new());
+    batch_at(&mut rows, 0, 0);
+    let report = batch_at(&mut rows, 10, 100);
+    assert_eq!((report.rows_done, report.total_rows), (100, 1000));
+    assert_eq!(report.rows_per_unit, 10f64);
+    assert_eq!(report.remaining, 90);
+    assert_eq!(report.status, Status::Running);
+
+    let report = batch_at(&mut rows, 100, 900);
+    assert_eq!(report.rows_done, 1000);
+    assert_eq!(report.status, Status::Finished);
+}
+
+#[test]
+fn total_can_grow() {
+    let mut rows = RowProgress::new(100, TestTimer::new());
+    batch_at(&mut rows, 0, 0);
+    batch_at(&mut rows, 10, 50);
+    rows.set_total(200);
+
+    let report = batch_at(&mut rows, 20, 50);
+    assert_eq!((report.rows_done, report.total_rows), (100, 200));
+    assert_eq!(report.remaining, 20);
+}
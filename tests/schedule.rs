@@ -0,0 +1,67 @@
+use gaeta::{Schedule, ScheduleStatus, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur: u64, max: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur, max);
+}
+
+#[test]
+fn needs_schedule_and_update() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_schedule(Schedule::Rate(10.0));
+    assert_eq!(ctx.get_schedule_status(), None);
+
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 1000);
+    assert_eq!(ctx.get_schedule_status(), None);
+
+    ctx.set_schedule(Schedule::Rate(0.0));
+    assert_eq!(ctx.get_schedule_status(), None);
+}
+
+#[test]
+fn rate() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_schedule(Schedule::Rate(10.0));
+    update_at(&mut ctx, 0, 0, 1000);
+    assert_eq!(ctx.get_schedule_status(), Some(ScheduleStatus { time: 0, percent: 0.0 }));
+
+    update_at(&mut ctx, 10, 150, 1000);
+    assert_eq!(ctx.get_schedule_status(), Some(ScheduleStatus { time: 5, percent: 5.0 }));
+
+    update_at(&mut ctx, 20, 150, 1000);
+    assert_eq!(ctx.get_schedule_status(), Some(ScheduleStatus { time: -5, percent: -5.0 }));
+}
+
+#[test]
+fn rate_without_maximum() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_schedule(Schedule::Rate(10.0));
+    for (ts, cur) in [(0, 0), (10, 50)] {
+        ctx.get_timefunc_mut().set_timestamp(ts);
+        ctx.update_count(cur);
+    }
+    assert_eq!(ctx.get_schedule_status(), Some(ScheduleStatus { time: -5, percent: 0.0 }));
+}
+
+#[test]
+fn finish_by() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_schedule(Schedule::FinishBy(100));
+    update_at(&mut ctx, 0, 0, 1000);
+    // Nothing is projected without a speed.
+    assert_eq!(ctx.get_schedule_status(), None);
+
+    update_at(&mut ctx, 10, 100, 1000);
+    assert_eq!(ctx.get_schedule_status(), Some(ScheduleStatus { time: 0, percent: 0.0 }));
+
+    update_at(&mut ctx, 20, 300, 1000);
+    let status = ctx.get_schedule_status().unwrap();
+    assert_eq!(status.time, 100 - (20 + ctx.get_remaining_time()));
+    assert!(status.time > 0);
+    assert_eq!(status.percent, 10.0);
+
+    // Finishing late is behind by the delay, and by nothing in percent.
+    update_at(&mut ctx, 130, 1000, 1000);
+    assert_eq!(ctx.get_schedule_status(), Some(ScheduleStatus { time: -30, percent: 0.0 }));
+}
@@ -0,0 +1,52 @@
+#![cfg(all(feature = "shm", unix))]
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use gaeta::{SharedProgress, TestTimer, TimeContext};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("gaeta-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn shared_between_mappings() {
+    let path = temp_path("shm-shared");
+    let writer = SharedProgress::create(&path, 10).unwrap();
+    let reader = SharedProgress::open(&path).unwrap();
+    assert_eq!(reader.get_path(), path);
+
+    writer.inc(3);
+    writer.inc(2);
+    assert_eq!(reader.get(), (5, 10));
+    writer.set(1);
+    writer.set_max(4);
+    assert_eq!(reader.get(), (1, 4));
+
+    let mut ctx = TimeContext::new(TestTimer::new());
+    reader.inc(3);
+    ctx.poll_update(&reader);
+    assert_eq!(ctx.snapshot().progress, 100.0);
+
+    // Created again, it starts over.
+    drop(writer);
+    let writer = SharedProgress::create(&path, 8).unwrap();
+    assert_eq!(writer.get(), (0, 8));
+    assert_eq!(reader.get(), (0, 8));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn open_rejects_other_files() {
+    let path = temp_path("shm-missing");
+    assert_eq!(SharedProgress::open(&path).err().unwrap().kind(), ErrorKind::NotFound);
+
+    let path = temp_path("shm-short");
+    fs::write(&path, b"short").unwrap();
+    assert_eq!(SharedProgress::open(&path).err().unwrap().kind(), ErrorKind::InvalidData);
+
+    fs::write(&path, [0u8; 64]).unwrap();
+    assert_eq!(SharedProgress::open(&path).err().unwrap().kind(), ErrorKind::InvalidData);
+    fs::remove_file(&path).unwrap();
+}
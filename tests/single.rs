@@ -0,0 +1,40 @@
+use gaeta::{F32Context, TestTimer, MAX_REMAINING_TIME};
+
+fn update_at<const N: usize>(ctx: &mut F32Context<TestTimer, N>, ts: u64, cur_prog: u64,
+                             max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn estimates_in_single_precision() {
+    let mut ctx: F32Context<TestTimer> = F32Context::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    assert_eq!(ctx.get_remaining_time(), 0);
+
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(ctx.get_progress(), 10f32);
+    assert_eq!(ctx.calc_speed_per_unit(), 1f32);
+    assert_eq!(ctx.get_remaining_time(), 90);
+}
+
+#[test]
+fn measures_over_window() {
+    let mut ctx: F32Context<TestTimer, 2> = F32Context::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 1, 100);
+    update_at(&mut ctx, 20, 21, 100);
+    assert_eq!(ctx.calc_speed_per_unit(), 2f32);
+}
+
+#[test]
+fn degenerate_inputs() {
+    let mut ctx: F32Context<TestTimer> = F32Context::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, u64::MAX);
+    update_at(&mut ctx, u64::MAX, u64::MAX / 1_000_000_000, u64::MAX);
+    assert_eq!(ctx.get_remaining_time(), MAX_REMAINING_TIME as u64);
+
+    update_at(&mut ctx, u64::MAX, 0, 0);
+    assert_eq!(ctx.get_progress(), 100f32);
+    assert_eq!(ctx.get_remaining_time(), 0);
+}
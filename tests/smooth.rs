@@ -0,0 +1,46 @@
+use gaeta::{SmoothEta, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+#[test]
+fn remaining_time_counts_down_between_updates() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    assert_eq!(ctx.get_remaining_time_at(100), 0);
+
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(ctx.get_remaining_time_at(10), 90);
+    assert_eq!(ctx.get_remaining_time_at(35), 65);
+    assert_eq!(ctx.get_remaining_time_at(1000), 0);
+}
+
+#[test]
+fn jumps_are_smoothed() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let mut smooth = SmoothEta::new(10);
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    assert_eq!(smooth.frame_at(&ctx, 10), 90);
+
+    // The stall doubles the estimate, which is approached over several frames.
+    update_at(&mut ctx, 20, 10, 100);
+    assert_eq!(ctx.get_remaining_time(), 180);
+    assert_eq!(smooth.frame_at(&ctx, 20), 130);
+    assert_eq!(smooth.frame_at(&ctx, 30), 145);
+    assert_eq!(smooth.frame(&ctx), 145);
+}
+
+#[test]
+fn completion_is_shown_at_once() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let mut smooth = SmoothEta::new(10);
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    smooth.frame_at(&ctx, 10);
+
+    update_at(&mut ctx, 11, 100, 100);
+    assert_eq!(smooth.frame_at(&ctx, 11), 0);
+}
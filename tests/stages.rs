@@ -0,0 +1,127 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gaeta::{GetTimestamp, StageHistory, Stages};
+
+// A timer shared by all copies, so that the stages and their contexts see the same time.
+#[derive(Clone, Default)]
+struct SharedTimer(Rc<Cell<u64>>);
+
+impl SharedTimer {
+    fn set_timestamp(&self, ts: u64) {
+        self.0.set(ts);
+    }
+}
+
+impl GetTimestamp for SharedTimer {
+    fn get_timestamp(&self) -> u64 {
+        self.0.get()
+    }
+}
+
+fn build(timer: &SharedTimer) -> Stages<SharedTimer> {
+    let mut job = Stages::new(timer.clone());
+    job.add_stage("download", 5.0);
+    job.add_stage("extract", 2.0);
+    job.add_stage("configure", 1.0);
+    job
+}
+
+#[test]
+fn projects_pending_stages_from_running_one() {
+    let timer = SharedTimer::default();
+    let mut job = build(&timer);
+    assert_eq!(job.next_stage(), Some(0));
+    job.update_eta(0, 100);
+
+    timer.set_timestamp(10);
+    job.update_eta(50, 100);
+    assert_eq!(job.get_progress(), 31.25f64);
+    assert_eq!(job.get_remaining_time(), 22);
+}
+
+#[test]
+fn uses_finished_and_expected_durations() {
+    let timer = SharedTimer::default();
+    let mut job = build(&timer);
+    job.next_stage();
+
+    timer.set_timestamp(20);
+    assert_eq!(job.next_stage(), Some(1));
+    assert_eq!(job.get_duration(0), Some(20));
+    assert_eq!(job.get_progress(), 62.5f64);
+    assert_eq!(job.get_remaining_time(), 12);
+
+    job.set_expected_duration(2, 30);
+    assert_eq!(job.get_remaining_time(), 38);
+}
+
+#[test]
+fn finishes_after_last_stage() {
+    let timer = SharedTimer::default();
+    let mut job = build(&timer);
+    job.update_eta(50, 100);
+    assert_eq!(job.get_progress(), 0f64);
+
+    for _ in 0..3 {
+        job.next_stage();
+    }
+    assert_eq!(job.next_stage(), None);
+    assert_eq!(job.get_current(), None);
+    assert_eq!(job.get_progress(), 100f64);
+    assert_eq!(job.get_remaining_time(), 0);
+}
+
+#[test]
+fn zero_weights() {
+    let mut job = Stages::new(SharedTimer::default());
+    job.add_stage("a", -1.0);
+    job.next_stage();
+    job.update_eta(50, 100);
+    assert_eq!(job.get_progress(), 0f64);
+}
+
+#[test]
+fn history_learns_mean_durations() {
+    let mut history = StageHistory::new();
+    history.record_stage("download", 20);
+    history.record_stage("download", 40);
+    assert_eq!(history.get_mean_duration("download"), Some(30));
+    assert_eq!(history.get_mean_duration("extract"), None);
+
+    let timer = SharedTimer::default();
+    let mut job = build(&timer);
+    history.apply(&mut job);
+    job.next_stage();
+    assert_eq!(job.get_remaining_time(), 30);
+}
+
+#[test]
+fn history_records_finished_stages() {
+    let timer = SharedTimer::default();
+    let mut job = build(&timer);
+    job.next_stage();
+    timer.set_timestamp(20);
+    job.next_stage();
+
+    let mut history = StageHistory::new();
+    history.record(&job);
+    assert_eq!(history.get_mean_duration("download"), Some(20));
+    assert_eq!(history.get_mean_duration("extract"), None);
+}
+
+#[test]
+fn history_round_trip() {
+    let mut history = StageHistory::new();
+    history.record_stage("down\tload", 20);
+    history.record_stage("extract", 5);
+
+    let mut buf = Vec::new();
+    history.write_to(&mut buf).unwrap();
+    buf.extend_from_slice(b"garbage\nconfigure\tx\t1\n");
+
+    let history = StageHistory::read_from(&buf[..]).unwrap();
+    assert_eq!(history.get_mean_duration("down load"), Some(20));
+    assert_eq!(history.get_mean_duration("extract"), Some(5));
+    assert_eq!(history.get_mean_duration("configure"), None);
+}
@@ -0,0 +1,67 @@
+use gaeta::{Stall, TestTimer, TimeContext};
+
+// Updates `ctx` at `ts`.
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+// Runs a job on `ctx` with a stall from 10 to 30, and another one from 40 to 70.
+fn run_with_stalls(ctx: &mut TimeContext<TestTimer>) {
+    ctx.set_stall_timeout(5);
+
+    for ts in 0..=10 {
+        update_at(ctx, ts, ts, 100);
+    }
+    for ts in 11..30 {
+        update_at(ctx, ts, 10, 100);
+    }
+    for ts in 30..=40 {
+        update_at(ctx, ts, ts - 19, 100);
+    }
+    for ts in 41..=70 {
+        update_at(ctx, ts, 21, 100);
+    }
+}
+
+#[test]
+fn logs_stalls() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_stall_log(4);
+    run_with_stalls(&mut ctx);
+
+    let log = ctx.get_stall_log().unwrap();
+    assert_eq!(log.get_stalls(), &[
+        Stall { start: 10, duration: 20, ongoing: false },
+        Stall { start: 40, duration: 30, ongoing: true },
+    ]);
+    assert_eq!(log.get_count(), 2);
+    assert_eq!(log.get_dropped(), 0);
+    assert_eq!(log.get_total_duration(), 50);
+}
+
+#[test]
+fn full_log_counts_stalls() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.enable_stall_log(1);
+    run_with_stalls(&mut ctx);
+
+    let log = ctx.get_stall_log().unwrap();
+    assert_eq!(log.get_stalls(), &[Stall { start: 10, duration: 20, ongoing: false }]);
+    assert_eq!(log.get_count(), 2);
+    assert_eq!(log.get_dropped(), 1);
+    assert_eq!(log.get_total_duration(), 20);
+}
+
+#[test]
+fn short_pauses_are_not_logged() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_stall_timeout(5);
+    ctx.enable_stall_log(4);
+
+    for ts in 0..50 {
+        update_at(&mut ctx, ts, ts / 4, 100);
+    }
+
+    assert_eq!(ctx.get_stall_log().unwrap().get_count(), 0);
+}
@@ -0,0 +1,59 @@
+use gaeta::{EtaValue, TestTimer, TimeContext};
+
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+fn restored(ctx: &TimeContext<TestTimer>) -> TimeContext<TestTimer> {
+    TimeContext::from_state(&ctx.get_state(), *ctx.get_timefunc())
+}
+
+// Returns the values which should survive restoring.
+fn values(ctx: &TimeContext<TestTimer>) -> (f64, f64, i64) {
+    let snapshot = ctx.snapshot();
+    (snapshot.progress, snapshot.speed, snapshot.remaining)
+}
+
+#[test]
+fn restores_estimate() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    update_at(&mut ctx, 0, 0, 100);
+    update_at(&mut ctx, 10, 10, 100);
+    update_at(&mut ctx, 20, 20, 100);
+
+    let restored = restored(&ctx);
+    assert_eq!(restored.get_remaining_time(), ctx.get_remaining_time());
+    assert_eq!(values(&restored), values(&ctx));
+}
+
+#[test]
+fn restores_unknown_total() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    for ts in 0..5 {
+        ctx.get_timefunc_mut().set_timestamp(ts * 10);
+        ctx.update_count(ts * 10);
+    }
+
+    let restored = restored(&ctx);
+    assert_eq!(restored.snapshot().progress, 0f64);
+    assert_eq!(restored.get_eta(), EtaValue::Unknown);
+    assert_eq!(restored.calc_throughput(), ctx.calc_throughput());
+}
+
+#[test]
+fn restores_epsilon_and_discarded_intervals() {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_progress_epsilon(5);
+    ctx.set_discard_intervals(2);
+    update_at(&mut ctx, 0, 0, 1000);
+    update_at(&mut ctx, 100, 10, 1000);
+
+    // Both continue the same way: one more interval is discarded, and small steps are ignored.
+    let mut restored = restored(&ctx);
+    for (ts, cur) in [(110, 20), (120, 23), (130, 30), (140, 40)] {
+        update_at(&mut ctx, ts, cur, 1000);
+        update_at(&mut restored, ts, cur, 1000);
+        assert_eq!(values(&restored), values(&ctx));
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(all(feature = "status", unix))]
+
+use std::fs;
+use std::io::{ErrorKind, Read};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use gaeta::{spawn_status_server, FixedTimeContext, TestTimer, TimeContext};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("gaeta-{}-{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn serves_snapshot() {
+    let path = temp_path("serve.sock");
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.update_eta(50, 100);
+
+    let server = spawn_status_server(&path, Arc::new(Mutex::new(ctx))).unwrap();
+    let mut reply = String::new();
+    UnixStream::connect(&path).unwrap().read_to_string(&mut reply).unwrap();
+    server.stop();
+
+    assert!(reply.starts_with("{\"progress\":50,"));
+    assert!(reply.ends_with("}\n"));
+    assert!(!path.exists());
+}
+
+#[test]
+fn replaces_stale_socket() {
+    let path = temp_path("stale.sock");
+    drop(UnixListener::bind(&path).unwrap());
+
+    let ctx = TimeContext::new(TestTimer::new());
+    let server = spawn_status_server(&path, Arc::new(Mutex::new(ctx))).unwrap();
+    server.stop();
+}
+
+#[test]
+fn keeps_other_files() {
+    let path = temp_path("file.txt");
+    fs::write(&path, "data").unwrap();
+
+    let ctx = TimeContext::new(TestTimer::new());
+    let err = spawn_status_server(&path, Arc::new(Mutex::new(ctx))).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::AddrInUse);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "data");
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn serves_fixed_window() {
+    let path = temp_path("fixed.sock");
+    let ctx = FixedTimeContext::<_, 4>::with_window(TestTimer::new());
+
+    let server = spawn_status_server(&path, Arc::new(Mutex::new(ctx))).unwrap();
+    server.stop();
+}
@@ -0,0 +1,49 @@
+use gaeta::{MemoryStore, RunStore, RunSummary, TestTimer, TimeContext};
+
+// Updates `ctx` at `ts`.
+fn update_at(ctx: &mut TimeContext<TestTimer>, ts: u64, cur_prog: u64, max_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, max_prog);
+}
+
+// Runs a job of 40 units at 1 unit per 10 units of time, with an idle gap of 100 in the middle.
+fn run_with_idle_gap(excluded: bool) -> TimeContext<TestTimer> {
+    let mut ctx = TimeContext::new(TestTimer::new());
+    ctx.set_idle_rate(0.05);
+    ctx.set_idle_excluded(excluded);
+
+    for ts in 0..=20 {
+        update_at(&mut ctx, ts * 10, ts, 40);
+    }
+    update_at(&mut ctx, 300, 20, 40);
+    for ts in 1..=20 {
+        update_at(&mut ctx, 300 + ts * 10, 20 + ts, 40);
+    }
+
+    ctx
+}
+
+#[test]
+fn idle_gap_is_excluded_from_summary() {
+    let ctx = run_with_idle_gap(true);
+    assert_eq!(ctx.get_idle_time(), 100);
+    assert_eq!(ctx.calc_average_speed(), 0.1f64);
+
+    let run = RunSummary::from_context(&ctx).unwrap();
+    assert_eq!(run, RunSummary { duration: 400, progress: 40 });
+    assert_eq!(run.get_speed(), ctx.calc_average_speed());
+
+    let mut store = MemoryStore::new();
+    store.record_context("job", &ctx).unwrap();
+    assert_eq!(store.get_stats("job").unwrap().mean_speed, 0.1f64);
+    assert_eq!(store.compare_speed("job", &ctx), Some(1.0f64));
+}
+
+#[test]
+fn idle_gap_is_kept_unless_excluded() {
+    let ctx = run_with_idle_gap(false);
+
+    let run = RunSummary::from_context(&ctx).unwrap();
+    assert_eq!(run, RunSummary { duration: 500, progress: 40 });
+    assert_eq!(run.get_speed(), ctx.calc_average_speed());
+}
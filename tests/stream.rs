@@ -0,0 +1,31 @@
+#![cfg(feature = "futures")]
+
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+
+use gaeta::{EtaStream, EtaStreamExt, TestTimer};
+
+#[test]
+fn counts_items() {
+    let mut items = stream::iter(0..4).track_eta(4, TestTimer::new());
+    let eta = items.handle();
+
+    block_on(async {
+        assert_eq!(items.next().await, Some(0));
+        assert_eq!(items.next().await, Some(1));
+    });
+
+    assert_eq!(EtaStream::count(&items), 2);
+    assert_eq!(eta.snapshot().progress, 50.0f64);
+}
+
+#[test]
+fn end_of_stream_completes() {
+    let mut items = stream::iter(0..2).track_eta(10, TestTimer::new());
+    let eta = items.handle();
+
+    block_on(async { while items.next().await.is_some() {} });
+
+    assert_eq!(EtaStream::count(&items), 2);
+    assert_eq!(eta.snapshot().progress, 100.0f64);
+}
@@ -0,0 +1,76 @@
+#![cfg(all(feature = "systemd", target_os = "linux"))]
+
+use std::fs;
+use std::os::unix::net::UnixDatagram;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use gaeta::{Observer, Snapshot, Status, SystemdNotifier, MAX_REMAINING_TIME};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("gaeta-{}-{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+// Returns the next notification received by `socket`.
+fn receive(socket: &UnixDatagram) -> String {
+    let mut buf = [0u8; 256];
+    let n = socket.recv(&mut buf).unwrap();
+    String::from_utf8(buf[..n].to_vec()).unwrap()
+}
+
+fn snapshot(progress: f64, remaining: i64, status: Status) -> Snapshot {
+    Snapshot { progress, speed: 0.0, remaining, status }
+}
+
+#[test]
+fn extends_timeout_from_eta() {
+    let path = temp_path("systemd");
+    let socket = UnixDatagram::bind(&path).unwrap();
+    socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+    let mut notifier = SystemdNotifier::new(&path, Duration::from_millis(1)).unwrap()
+        .with_margin(Duration::from_secs(30));
+    assert_eq!(notifier.get_margin(), Duration::from_secs(30));
+
+    notifier.on_progress(&snapshot(42.4, 90_500, Status::Running));
+    assert_eq!(receive(&socket), "EXTEND_TIMEOUT_USEC=120500000\nSTATUS=42% done, 90 s left");
+
+    // Without an estimate, only the margin is added.
+    notifier.on_progress(&snapshot(0.0, 0, Status::Running));
+    assert_eq!(receive(&socket), "EXTEND_TIMEOUT_USEC=30000000\nSTATUS=0% done");
+    notifier.on_progress(&snapshot(1.0, MAX_REMAINING_TIME, Status::Running));
+    assert_eq!(receive(&socket), "EXTEND_TIMEOUT_USEC=30000000\nSTATUS=1% done");
+
+    // Nothing is sent once the job is over.
+    notifier.on_progress(&snapshot(100.0, 0, Status::Finished));
+    notifier.ready().unwrap();
+    assert_eq!(receive(&socket), "READY=1");
+
+    notifier.extend_timeout(Duration::from_millis(5)).unwrap();
+    assert_eq!(receive(&socket), "EXTEND_TIMEOUT_USEC=5000");
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn abstract_socket() {
+    use std::os::linux::net::SocketAddrExt;
+    use std::os::unix::net::SocketAddr;
+
+    let name = format!("gaeta-{}-systemd", std::process::id());
+    let addr = SocketAddr::from_abstract_name(name.as_bytes()).unwrap();
+    let socket = UnixDatagram::bind_addr(&addr).unwrap();
+    socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+    let notifier = SystemdNotifier::new(format!("@{}", name), Duration::from_millis(1)).unwrap();
+    assert_eq!(notifier.get_margin(), Duration::from_secs(10));
+    notifier.notify("STATUS=hi").unwrap();
+    assert_eq!(receive(&socket), "STATUS=hi");
+}
+
+#[test]
+fn missing_socket() {
+    let notifier = SystemdNotifier::new(temp_path("systemd-missing"), Duration::from_millis(1));
+    assert!(notifier.unwrap().ready().is_err());
+}
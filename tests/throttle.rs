@@ -0,0 +1,42 @@
+use gaeta::{Observer, Snapshot, Status, Throttled};
+
+// Logs the progress of the delivered snapshots, and -1 for the end of the operation.
+#[derive(Default)]
+struct Log(Vec<f64>);
+
+impl Observer for Log {
+    fn on_progress(&mut self, snapshot: &Snapshot) { self.0.push(snapshot.progress); }
+    fn on_complete(&mut self) { self.0.push(-1f64); }
+    fn on_cancel(&mut self) { self.0.push(-1f64); }
+}
+
+fn at(progress: f64) -> Snapshot {
+    Snapshot { progress, speed: 0f64, remaining: 0, status: Status::Running }
+}
+
+#[test]
+fn drops_frequent_snapshots_and_flushes_the_last_one() {
+    let mut throttled = Throttled::new(Log::default(), 1);
+    for progress in [10.0, 20.0, 30.0] {
+        throttled.on_progress(&at(progress));
+    }
+    throttled.on_complete();
+    assert_eq!(throttled.into_inner().0, [10.0, 30.0, -1.0]);
+}
+
+#[test]
+fn cancel_flushes_the_last_snapshot() {
+    let mut throttled = Throttled::new(Log::default(), 0);
+    throttled.on_progress(&at(10.0));
+    throttled.on_progress(&at(20.0));
+    throttled.on_cancel();
+    assert_eq!(throttled.into_inner().0, [10.0, 20.0, -1.0]);
+}
+
+#[test]
+fn nothing_pending_is_not_repeated() {
+    let mut throttled = Throttled::new(Log::default(), 1);
+    throttled.on_progress(&at(10.0));
+    throttled.on_complete();
+    assert_eq!(throttled.into_inner().0, [10.0, -1.0]);
+}
@@ -0,0 +1,55 @@
+use gaeta::{Aggregate, TestTimer, TimeContext, TimelineSpan, Tracker};
+
+#[test]
+fn aggregate_spans() {
+    let mut aggregate = Aggregate::new();
+    aggregate.add(TimeContext::new(TestTimer::new()));
+    aggregate.add(TimeContext::new(TestTimer::new()));
+
+    for (ts, cur) in [(5, 0), (15, 10)] {
+        aggregate.get_mut(0).unwrap().get_timefunc_mut().set_timestamp(ts);
+        aggregate.update_eta(0, cur, 100);
+    }
+
+    let idle = TimelineSpan {
+        label: "1".to_string(),
+        depth: 0,
+        start: None,
+        end: None,
+        predicted_end: None,
+        projected_end: None,
+        progress: 0.0,
+    };
+    let running = TimelineSpan {
+        label: "0".to_string(),
+        depth: 0,
+        start: Some(5),
+        end: None,
+        predicted_end: Some(105),
+        projected_end: Some(105),
+        progress: 10.0,
+    };
+    assert_eq!(aggregate.timeline(), [running.clone(), idle.clone()]);
+
+    // Finishing early keeps the first prediction.
+    aggregate.get_mut(0).unwrap().get_timefunc_mut().set_timestamp(25);
+    aggregate.update_eta(0, 100, 100);
+    let finished = TimelineSpan { end: Some(25), projected_end: None, progress: 100.0, ..running };
+    assert_eq!(aggregate.timeline(), [finished, idle]);
+}
+
+#[test]
+fn tracker_spans_are_depth_first() {
+    let mut root = Tracker::new("root", TestTimer::new());
+    let a = root.add_child("a", 1.0);
+    a.add_child("a0", 1.0);
+    root.add_child("b", 1.0);
+    root.update_path(&[1], 100, 100);
+
+    let spans = root.timeline();
+    let layout: Vec<_> = spans.iter().map(|s| (s.label.as_str(), s.depth, s.progress)).collect();
+    assert_eq!(layout, [("root", 0, 50.0), ("a", 1, 0.0), ("a0", 2, 0.0), ("b", 1, 100.0)]);
+
+    assert_eq!(spans[2].start, None);
+    assert_eq!((spans[3].start, spans[3].end), (Some(0), Some(0)));
+}
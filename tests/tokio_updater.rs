@@ -0,0 +1,38 @@
+#![cfg(feature = "tokio")]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use gaeta::{spawn_tokio_updater, Status, TestTimer, TimeContext};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap()
+}
+
+#[test]
+fn exits_when_source_completes() {
+    runtime().block_on(async {
+        let done = Arc::new(AtomicU64::new(0));
+        let source = done.clone();
+        let ctx = TimeContext::new(TestTimer::new());
+        let (task, rx) = spawn_tokio_updater(ctx, Duration::from_millis(1),
+                                             move || (source.load(Ordering::Relaxed), 100));
+
+        done.store(100, Ordering::Relaxed);
+        task.await.unwrap();
+        assert_eq!(rx.borrow().progress, 100f64);
+        assert_eq!(rx.borrow().status, Status::Finished);
+    });
+}
+
+#[test]
+fn exits_when_receiver_is_dropped() {
+    runtime().block_on(async {
+        let ctx = TimeContext::new(TestTimer::new());
+        let (task, rx) = spawn_tokio_updater(ctx, Duration::from_millis(1), || (10, 100));
+
+        drop(rx);
+        task.await.unwrap();
+    });
+}
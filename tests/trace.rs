@@ -0,0 +1,48 @@
+use gaeta::{TestTimer, TimeContext, TraceGenerator, Workload};
+
+fn generator(workload: Workload) -> TraceGenerator {
+    let mut generator = TraceGenerator::new(workload);
+    generator.set_total(10_000);
+    generator.set_duration(1000);
+    generator.set_steps(100);
+    generator
+}
+
+#[test]
+fn traces_span_the_whole_job() {
+    use Workload::*;
+
+    for workload in [Steady, Bursty, Stalling, Decelerating] {
+        let trace = generator(workload).generate();
+        let entries = trace.get_entries();
+
+        assert_eq!(entries.len(), 101);
+        assert_eq!((entries[0].timestamp, entries[0].current), (0, 0));
+        assert_eq!((entries[100].timestamp, entries[100].current), (1000, 10_000));
+        assert!(entries.iter().all(|e| e.total == Some(10_000)));
+        assert!(entries.windows(2).all(|w| w[0].current <= w[1].current));
+    }
+}
+
+#[test]
+fn seed_decides_the_trace() {
+    let mut generator = generator(Workload::Bursty);
+    let first = generator.generate();
+    assert_eq!(generator.generate(), first);
+
+    generator.set_seed(2);
+    assert_ne!(generator.generate(), first);
+}
+
+#[test]
+fn evaluates_estimator() {
+    let mut steady = generator(Workload::Steady);
+    steady.set_jitter(0.0);
+    let mut ctx = TimeContext::new(TestTimer::new());
+    let error = TraceGenerator::evaluate(&steady.generate(), &mut ctx);
+    assert!(error < 1.0, "{}", error);
+
+    let stalling = generator(Workload::Stalling).generate();
+    let stalled = TraceGenerator::evaluate(&stalling, &mut TimeContext::new(TestTimer::new()));
+    assert!(stalled > error);
+}
@@ -0,0 +1,34 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gaeta::{GetTimestamp, TranscodeContext};
+
+// A timer shared by all copies, so that both contexts see the same time.
+#[derive(Clone, Default)]
+struct SharedTimer(Rc<Cell<u64>>);
+
+impl GetTimestamp for SharedTimer {
+    fn get_timestamp(&self) -> u64 {
+        self.0.get()
+    }
+}
+
+#[test]
+fn tracks_input_and_output() {
+    let timer = SharedTimer::default();
+    let mut ctx = TranscodeContext::new(timer.clone());
+    assert_eq!(ctx.get_ratio(), 0f64);
+
+    ctx.update(0, 1000, 0);
+    timer.0.set(10);
+    ctx.update(100, 1000, 25);
+
+    assert_eq!(ctx.get_input_rate(), 10f64);
+    assert_eq!(ctx.get_output_rate(), 2.5f64);
+    assert_eq!(ctx.get_ratio(), 0.25f64);
+    assert_eq!(ctx.get_remaining_time(), 90);
+
+    let snapshot = ctx.snapshot();
+    assert_eq!((snapshot.output, snapshot.ratio), (25, 0.25f64));
+    assert_eq!(snapshot.input.progress, 10f64);
+}
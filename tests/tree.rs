@@ -0,0 +1,76 @@
+use gaeta::{TestTimer, Tracker};
+
+#[test]
+fn empty_leaves_finish() {
+    let mut root = Tracker::new("root", TestTimer::new());
+    root.add_child("a", 1.0);
+    root.add_child("b", 1.0);
+    assert_eq!(root.get_progress(), 0f64);
+
+    root.update_path(&[0], 0, 0);
+    assert_eq!(root.child(0).unwrap().get_progress(), 100.0f64);
+    assert_eq!(root.get_progress(), 50.0f64);
+    assert!(!root.is_finished());
+
+    root.update_path(&[1], 0, 0);
+    assert!(root.is_finished());
+    assert_eq!(root.snapshot().current, 2);
+}
+
+// Builds a root with a child "a" of weight 3 with two leaves, and a leaf "b" of weight 1.
+fn build() -> Tracker<TestTimer> {
+    let mut root = Tracker::new("root", TestTimer::new());
+    let a = root.add_child("a", 3.0);
+    a.add_child("a0", 1.0);
+    a.add_child("a1", 1.0);
+    root.add_child("b", 1.0);
+    root
+}
+
+#[test]
+fn weighs_children() {
+    let mut root = build();
+    root.update_path(&[0, 0], 50, 100);
+    assert_eq!(root.child(0).unwrap().get_progress(), 25.0f64);
+    assert_eq!(root.get_progress(), 18.75f64);
+
+    root.update_path(&[1], 100, 100);
+    assert_eq!(root.get_progress(), 43.75f64);
+    assert_eq!(root.get_context().snapshot().progress, 43.75f64);
+}
+
+#[test]
+fn unknown_paths_and_inner_updates_are_ignored() {
+    let mut root = build();
+    root.update_path(&[0, 5], 50, 100);
+    root.update_path(&[7], 50, 100);
+    root.update_path(&[1, 0], 50, 100);
+    root.update_eta(1, 1);
+    assert_eq!(root.get_progress(), 0f64);
+    assert_eq!(root.get_context().snapshot().progress, 0f64);
+}
+
+#[test]
+fn refresh_updates_contexts() {
+    let mut root = build();
+    root.child_mut(1).unwrap().update_eta(100, 100);
+    assert_eq!(root.get_progress(), 25.0f64);
+    assert_eq!(root.get_context().snapshot().progress, 0f64);
+
+    root.refresh();
+    assert_eq!(root.get_context().snapshot().progress, 25.0f64);
+}
+
+#[test]
+fn snapshot_counts_finished_children() {
+    let mut root = build();
+    root.update_path(&[0, 1], 10, 10);
+    root.update_path(&[1], 10, 10);
+
+    let snapshot = root.snapshot();
+    assert_eq!((snapshot.current, snapshot.total), (1, 2));
+    assert_eq!(snapshot.children[0].label, "a");
+    assert_eq!((snapshot.children[0].current, snapshot.children[0].total), (1, 2));
+    assert_eq!((snapshot.children[0].children[1].current, snapshot.children[0].children[1].total),
+               (10, 10));
+}
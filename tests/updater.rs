@@ -0,0 +1,43 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use gaeta::{spawn_updater, AtomicProgress, TestTimer, TimeContext};
+
+#[test]
+fn exits_when_source_completes() {
+    let ctx = Arc::new(Mutex::new(TimeContext::new(TestTimer::new())));
+    let calls = Arc::new(AtomicU64::new(0));
+    let counter = calls.clone();
+
+    let source = move || {
+        let call = counter.fetch_add(1, Ordering::Relaxed) + 1;
+        (call.min(3), 3)
+    };
+
+    let updater = spawn_updater(ctx.clone(), Duration::from_millis(1), source);
+    let start = Instant::now();
+    while updater.is_running() {
+        assert!(start.elapsed() < Duration::from_secs(10));
+        thread::sleep(Duration::from_millis(1));
+    }
+    updater.stop();
+
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+    assert_eq!(ctx.lock().unwrap().get_current(), 3);
+}
+
+#[test]
+fn stop_ends_unfinished_updater() {
+    let ctx = Arc::new(Mutex::new(TimeContext::new(TestTimer::new())));
+    let progress = Arc::new(AtomicProgress::new(100));
+    progress.set(40);
+
+    let updater = spawn_updater(ctx.clone(), Duration::from_millis(1), progress.clone());
+    thread::sleep(Duration::from_millis(20));
+    assert!(updater.is_running());
+
+    updater.stop();
+    assert_eq!(ctx.lock().unwrap().get_current(), 40);
+}
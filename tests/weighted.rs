@@ -0,0 +1,84 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gaeta::{GetTimestamp, WeightedItems};
+
+// A timer shared with the tracker, which owns a clone of it.
+#[derive(Clone, Default)]
+struct SharedTimer(Rc<Cell<u64>>);
+
+impl GetTimestamp for SharedTimer {
+    fn get_timestamp(&self) -> u64 {
+        self.0.get()
+    }
+}
+
+// Completes the item at `index` at `ts`.
+fn complete_at(items: &mut WeightedItems<SharedTimer>, timer: &SharedTimer, ts: u64, index: usize) {
+    timer.0.set(ts);
+    items.complete(index);
+}
+
+#[test]
+fn fits_overhead_and_cost() {
+    let timer = SharedTimer::default();
+    let mut items = WeightedItems::new(vec![10, 30, 1, 1, 1, 7], timer.clone());
+    assert_eq!(items.get_remaining_time(), 0);
+
+    // Each item takes 2 units of time plus 1 per unit of weight.
+    complete_at(&mut items, &timer, 12, 0);
+    assert_eq!(items.get_model(), (0.0, 1.2));
+    complete_at(&mut items, &timer, 44, 1);
+    assert_eq!(items.get_model(), (2.0, 1.0));
+    assert_eq!(items.get_progress(), 80.0);
+    assert_eq!(items.get_remaining_items(), 4);
+    assert_eq!(items.get_remaining_time(), 18);
+
+    // The time taken by the current item counts, but the ETA stays above 0.
+    timer.0.set(48);
+    assert_eq!(items.get_remaining_time(), 14);
+    timer.0.set(100);
+    assert_eq!(items.get_remaining_time(), 1);
+
+    assert_eq!(items.complete_next(), Some(2));
+    assert_eq!(items.get_remaining_items(), 3);
+}
+
+#[test]
+fn cost_is_not_negative() {
+    let timer = SharedTimer::default();
+    let mut items = WeightedItems::new(vec![10, 20, 30], timer.clone());
+    complete_at(&mut items, &timer, 20, 0);
+    complete_at(&mut items, &timer, 30, 1);
+    assert_eq!(items.get_model(), (15.0, 0.0));
+    assert_eq!(items.get_remaining_time(), 15);
+}
+
+#[test]
+fn repeated_and_unknown_items_are_ignored() {
+    let timer = SharedTimer::default();
+    let mut items = WeightedItems::new(vec![10, 10], timer.clone());
+    complete_at(&mut items, &timer, 5, 0);
+    complete_at(&mut items, &timer, 9, 0);
+    complete_at(&mut items, &timer, 9, 2);
+    assert_eq!(items.get_remaining_items(), 1);
+    assert_eq!(items.get_progress(), 50.0);
+
+    complete_at(&mut items, &timer, 10, 1);
+    assert_eq!(items.get_remaining_time(), 0);
+    assert_eq!(items.complete_next(), None);
+    assert_eq!(items.get_context().snapshot().progress, 100.0);
+}
+
+#[test]
+fn weightless_items() {
+    let timer = SharedTimer::default();
+    assert_eq!(WeightedItems::new(Vec::new(), timer.clone()).get_progress(), 100.0);
+
+    let mut items = WeightedItems::new(vec![0, 0], timer.clone());
+    assert_eq!(items.get_progress(), 0.0);
+    complete_at(&mut items, &timer, 4, 0);
+    assert_eq!(items.get_model(), (4.0, 0.0));
+    complete_at(&mut items, &timer, 8, 1);
+    assert_eq!(items.get_progress(), 100.0);
+}
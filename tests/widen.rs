@@ -0,0 +1,33 @@
+use std::cell::Cell;
+
+use gaeta::{GetTimestamp, WideTimer};
+
+#[test]
+fn extends_wrapping_counter() {
+    let counter = Cell::new(250u8);
+    let timer = WideTimer::new(|| counter.get());
+    assert_eq!(timer.get_timestamp(), 250);
+
+    counter.set(255);
+    assert_eq!(timer.get_timestamp(), 255);
+    counter.set(4);
+    assert_eq!(timer.get_timestamp(), 260);
+    counter.set(4);
+    assert_eq!(timer.get_timestamp(), 260);
+
+    for _ in 0..3 {
+        counter.set(200);
+        timer.get_timestamp();
+        counter.set(10);
+        timer.get_timestamp();
+    }
+    assert_eq!(timer.get_timestamp(), 4 * 256 + 10);
+}
+
+#[test]
+fn wide_counters_wrap_too() {
+    let counter = Cell::new(u32::MAX);
+    let timer = WideTimer::new(|| counter.get());
+    counter.set(1);
+    assert_eq!(timer.get_timestamp(), (1u64 << 32) + 1);
+}
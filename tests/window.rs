@@ -0,0 +1,40 @@
+use gaeta::{FixedWindow, HeapWindow, TestTimer, TimeContext, Window};
+
+fn context<W: Window>() -> TimeContext<TestTimer, W> {
+    TimeContext::with_window(TestTimer::new())
+}
+
+fn run<W: Window>(ctx: &mut TimeContext<TestTimer, W>, ts: u64, cur_prog: u64) {
+    ctx.get_timefunc_mut().set_timestamp(ts);
+    ctx.update_eta(cur_prog, 10_000);
+}
+
+#[test]
+fn fixed_window_wraps_like_heap_window() {
+    let mut fixed = context::<FixedWindow<10>>();
+    let mut heap = context::<HeapWindow>();
+
+    let mut cur = 0;
+    for i in 0..50u64 {
+        cur += 10 + i % 7 * 13;
+        run(&mut fixed, i * 10, cur);
+        run(&mut heap, i * 10, cur);
+
+        assert_eq!(fixed.calc_speed_per_unit(), heap.calc_speed_per_unit());
+        assert_eq!(fixed.get_remaining_time(), heap.get_remaining_time());
+    }
+}
+
+#[test]
+fn tiny_windows() {
+    let mut empty = context::<FixedWindow<0>>();
+    let mut single = context::<FixedWindow<1>>();
+
+    for i in 0..5u64 {
+        run(&mut empty, i * 10, i * 100);
+        run(&mut single, i * 10, i * 100);
+    }
+    assert_eq!(empty.calc_speed_per_unit(), 0f64);
+    assert_eq!(single.calc_speed_per_unit(), 0.1f64);
+    assert_eq!(single.get_remaining_time(), 960);
+}